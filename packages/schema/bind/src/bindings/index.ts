@@ -1,5 +1,6 @@
 import { OutputDirectory, TargetLanguage } from "../";
 import * as WasmAs from "./wasm-as";
+import * as WasmRs from "./wasm-rs";
 
 import { TypeInfo } from "@web3api/schema-parse";
 
@@ -10,6 +11,8 @@ export function generateBinding(
   switch (language) {
     case "wasm-as":
       return WasmAs.generateBinding(typeInfo);
+    case "wasm-rs":
+      return WasmRs.generateBinding(typeInfo);
     default:
       throw Error(`Error: Language binding unsupported - ${language}`);
   }
@@ -0,0 +1,92 @@
+import { isScalarType, toRustScalar } from "./types";
+import { camelCase, rustIdentifier, snakeCase } from "./utils";
+
+type MustacheFunction = () => (
+  value: string,
+  render: (template: string) => string
+) => string;
+
+export const toLower: MustacheFunction = () => {
+  return (value: string, render: (template: string) => string) => {
+    return render(value).toLowerCase();
+  };
+};
+
+export const toSnakeCase: MustacheFunction = () => {
+  return (value: string, render: (template: string) => string) => {
+    return rustIdentifier(snakeCase(render(value)));
+  };
+};
+
+// `#[w3_module]`/`#[w3_import]` name arguments and methods
+// after their camelCased identifiers; other names are renamed
+export const toW3Rename: MustacheFunction = () => {
+  return (value: string, render: (template: string) => string) => {
+    const name = render(value);
+
+    if (camelCase(rustIdentifier(snakeCase(name))) === name) {
+      return "";
+    }
+
+    return `#[w3(rename = "${name}")] `;
+  };
+};
+
+export const toRust: MustacheFunction = () => {
+  return (value: string, render: (template: string) => string) => {
+    return toRustType(render(value), false);
+  };
+};
+
+// Object type fields box nullable objects, so that
+// types may refer to each other, e.g. `circular: CustomType`
+export const toRustProperty: MustacheFunction = () => {
+  return (value: string, render: (template: string) => string) => {
+    return toRustType(render(value), true);
+  };
+};
+
+const toRustType = (type: string, boxed: boolean): string => {
+  let nullable = false;
+  if (type[type.length - 1] === "!") {
+    type = type.substr(0, type.length - 1);
+  } else {
+    nullable = true;
+  }
+
+  if (type[0] === "[") {
+    return applyNullable(toRustArray(type), nullable, false);
+  }
+
+  if (isScalarType(type)) {
+    return applyNullable(toRustScalar(type), nullable, false);
+  } else if (type.includes("Enum_")) {
+    return applyNullable(type.replace("Enum_", ""), nullable, false);
+  } else {
+    return applyNullable(type, nullable, boxed);
+  }
+};
+
+const toRustArray = (type: string): string => {
+  const result = type.match(/(\[)([[\]A-Za-z0-9_.!]+)(\])/);
+
+  if (!result || result.length !== 4) {
+    throw Error(`Invalid Array: ${type}`);
+  }
+
+  return "Vec<" + toRustType(result[2], false) + ">";
+};
+
+const applyNullable = (
+  type: string,
+  nullable: boolean,
+  boxed: boolean
+): string => {
+  if (!nullable) {
+    return type;
+  } else if (boxed) {
+    return `Option<Box<${type}>>`;
+  } else {
+    return `Option<${type}>`;
+  }
+};
@@ -0,0 +1,287 @@
+/* eslint-disable @typescript-eslint/naming-convention */
+import { OutputDirectory, OutputEntry } from "../../";
+import { readDirectory } from "../../utils/fs";
+import * as Functions from "./functions";
+import { isRuntimeScalar } from "./types";
+import { snakeCase } from "./utils";
+
+import {
+  transformTypeInfo,
+  extendType,
+  addFirstLast,
+  toPrefixedGraphQLType,
+  AnyDefinition,
+  EnumDefinition,
+  TypeInfo,
+} from "@web3api/schema-parse";
+import path from "path";
+import Mustache from "mustache";
+
+export function generateBinding(typeInfo: TypeInfo): OutputDirectory {
+  const entries: OutputEntry[] = [];
+
+  // Transform the TypeInfo to our liking
+  const transforms = [
+    extendType(Functions),
+    addFirstLast,
+    toPrefixedGraphQLType,
+  ];
+
+  for (const transform of transforms) {
+    typeInfo = transformTypeInfo(typeInfo, transform);
+  }
+
+  const templatesDir = path.join(__dirname, "./templates");
+  const directory = readDirectory(templatesDir);
+  const subTemplates = loadSubTemplates(directory.entries);
+
+  // Generate object type modules
+  for (const objectType of typeInfo.objectTypes) {
+    entries.push({
+      type: "Directory",
+      name: snakeCase(objectType.type),
+      data: generateFiles(
+        "./templates/object-type",
+        withImports(objectType, objectType.properties, objectType.type),
+        subTemplates
+      ),
+    });
+  }
+
+  const hasImports =
+    typeInfo.importedQueryTypes.length > 0 ||
+    typeInfo.importedObjectTypes.length > 0 ||
+    typeInfo.importedEnumTypes.length > 0;
+
+  // Generate imported module
+  if (hasImports) {
+    const importEntries: OutputEntry[] = [];
+
+    // Generate imported query type modules
+    for (const importedQueryType of typeInfo.importedQueryTypes) {
+      const properties: AnyDefinition[] = [];
+      for (const method of importedQueryType.methods) {
+        properties.push(...method.arguments, method.return);
+      }
+
+      importEntries.push({
+        type: "Directory",
+        name: snakeCase(importedQueryType.type),
+        data: generateFiles(
+          "./templates/imported/query-type",
+          withImports(importedQueryType, properties),
+          subTemplates
+        ),
+      });
+    }
+
+    // Generate imported enum type modules
+    for (const importedEnumType of typeInfo.importedEnumTypes) {
+      importEntries.push({
+        type: "Directory",
+        name: snakeCase(importedEnumType.type),
+        data: generateFiles(
+          "./templates/imported/enum-type",
+          withVariants(importedEnumType),
+          subTemplates
+        ),
+      });
+    }
+
+    // Generate imported object type modules
+    for (const importedObectType of typeInfo.importedObjectTypes) {
+      importEntries.push({
+        type: "Directory",
+        name: snakeCase(importedObectType.type),
+        data: generateFiles(
+          "./templates/imported/object-type",
+          withImports(
+            importedObectType,
+            importedObectType.properties,
+            importedObectType.type
+          ),
+          subTemplates
+        ),
+      });
+    }
+
+    entries.push({
+      type: "Directory",
+      name: "imported",
+      data: [
+        ...importEntries,
+        ...generateFiles("./templates/imported", typeInfo, subTemplates),
+      ],
+    });
+  }
+
+  // Generate query type modules
+  for (const queryType of typeInfo.queryTypes) {
+    const properties: AnyDefinition[] = [];
+    for (const method of queryType.methods) {
+      properties.push(...method.arguments, method.return);
+    }
+
+    entries.push({
+      type: "Directory",
+      name: snakeCase(queryType.type),
+      data: generateFiles(
+        "./templates/query-type",
+        withImports(queryType, properties),
+        subTemplates
+      ),
+    });
+  }
+
+  // Generate enum type modules
+  for (const enumType of typeInfo.enumTypes) {
+    entries.push({
+      type: "Directory",
+      name: snakeCase(enumType.type),
+      data: generateFiles(
+        "./templates/enum-type",
+        withVariants(enumType),
+        subTemplates
+      ),
+    });
+  }
+
+  // Generate root module and entry files
+  entries.push(
+    ...generateFiles(
+      "./templates",
+      {
+        ...typeInfo,
+        __imported: hasImports,
+      },
+      subTemplates
+    )
+  );
+
+  return {
+    entries,
+  };
+}
+
+// Adds the types a module's fields refer to: `__typeImports` are
+// the generated object and enum types, `use`d from the parent
+// module, and `__scalarImports` are the runtime's `BigInt`/`Bytes`
+function withImports<TDefinition>(
+  def: TDefinition,
+  properties: AnyDefinition[],
+  self?: string
+): TDefinition & { __typeImports: string[]; __scalarImports: string[] } {
+  const types: Record<string, boolean> = {};
+  const scalars: Record<string, boolean> = {};
+
+  const addImports = (property: AnyDefinition) => {
+    if (property.array) {
+      addImports(property.array);
+    } else if (property.object) {
+      types[property.object.type] = true;
+    } else if (property.enum) {
+      types[property.enum.type] = true;
+    } else if (property.scalar && isRuntimeScalar(property.scalar.type)) {
+      scalars[property.scalar.type] = true;
+    }
+  };
+
+  properties.forEach(addImports);
+
+  if (self) {
+    delete types[self];
+  }
+
+  return {
+    ...def,
+    __typeImports: Object.keys(types).sort(),
+    __scalarImports: Object.keys(scalars).sort(),
+  };
+}
+
+// Enum constants as `__variants`, marking the first one as the default
+function withVariants<TDefinition extends EnumDefinition>(
+  def: TDefinition
+): TDefinition & { __variants: { name: string; default: boolean }[] } {
+  return {
+    ...def,
+    __variants: def.constants.map((name, index) => ({
+      name,
+      default: index === 0,
+    })),
+  };
+}
+
+function generateFiles(
+  subpath: string,
+  config: unknown,
+  subTemplates: Record<string, string>,
+  subDirectories = false
+): OutputEntry[] {
+  const output: OutputEntry[] = [];
+  const absolutePath = path.join(__dirname, subpath);
+  const directory = readDirectory(absolutePath);
+
+  const processDirectory = (entries: OutputEntry[], output: OutputEntry[]) => {
+    subTemplates = loadSubTemplates(entries, subTemplates);
+
+    // Generate all files, recurse all directories
+    for (const dirent of entries) {
+      if (dirent.type === "File") {
+        const name = path.parse(dirent.name).name;
+
+        // file templates don't contain '_'
+        if (name.indexOf("_") === -1) {
+          const data = Mustache.render(dirent.data, config, subTemplates);
+
+          // If the file isn't empty, add it to the output
+          if (data) {
+            output.push({
+              type: "File",
+              name: name.replace("-", "."),
+              data,
+            });
+          }
+        }
+      } else if (dirent.type === "Directory" && subDirectories) {
+        const subOutput: OutputEntry[] = [];
+
+        processDirectory(dirent.data as OutputEntry[], subOutput);
+
+        output.push({
+          type: "Directory",
+          name: dirent.name,
+          data: subOutput,
+        });
+      }
+    }
+  };
+
+  processDirectory(directory.entries, output);
+
+  return output;
+}
+
+function loadSubTemplates(
+  entries: OutputEntry[],
+  existingSubTemplates?: Record<string, string>
+): Record<string, string> {
+  const subTemplates: Record<string, string> = existingSubTemplates
+    ? existingSubTemplates
+    : {};
+
+  for (const file of entries) {
+    if (file.type !== "File") {
+      continue;
+    }
+
+    const name = path.parse(file.name).name;
+
+    // sub-templates contain '_' in their file names
+    if (name.indexOf("_") > -1) {
+      subTemplates[name] = file.data as string;
+    }
+  }
+
+  return subTemplates;
+}
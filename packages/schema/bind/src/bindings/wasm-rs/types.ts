@@ -0,0 +1,33 @@
+const scalarTypes = {
+  Int: "i32",
+  Int8: "i8",
+  Int16: "i16",
+  Int32: "i32",
+  Int64: "i64",
+  UInt: "u32",
+  UInt8: "u8",
+  UInt16: "u16",
+  UInt32: "u32",
+  UInt64: "u64",
+  String: "String",
+  Boolean: "bool",
+  Bytes: "Bytes",
+  BigInt: "BigInt",
+};
+
+export type ScalarTypes = typeof scalarTypes;
+
+export type ScalarType = keyof ScalarTypes;
+
+export function isScalarType(type: string): type is ScalarType {
+  return type in scalarTypes;
+}
+
+export function toRustScalar(type: ScalarType): string {
+  return scalarTypes[type];
+}
+
+// Scalars whose Rust types come from `web3api_wasm_rs`
+export function isRuntimeScalar(type: string): boolean {
+  return type === "Bytes" || type === "BigInt";
+}
@@ -0,0 +1,110 @@
+const keywords = [
+  "abstract",
+  "as",
+  "async",
+  "await",
+  "become",
+  "box",
+  "break",
+  "const",
+  "continue",
+  "do",
+  "dyn",
+  "else",
+  "enum",
+  "extern",
+  "false",
+  "final",
+  "fn",
+  "for",
+  "gen",
+  "if",
+  "impl",
+  "in",
+  "let",
+  "loop",
+  "macro",
+  "match",
+  "mod",
+  "move",
+  "mut",
+  "override",
+  "priv",
+  "pub",
+  "ref",
+  "return",
+  "static",
+  "struct",
+  "trait",
+  "true",
+  "try",
+  "type",
+  "typeof",
+  "unsafe",
+  "unsized",
+  "use",
+  "virtual",
+  "where",
+  "while",
+  "yield",
+];
+
+// Keywords that cannot be raw identifiers
+const pathKeywords = ["crate", "self", "Self", "super"];
+
+// `optStrOptArray` becomes `opt_str_opt_array`, `TestImport_Query`
+// becomes `test_import_query`
+export function snakeCase(name: string): string {
+  let result = "";
+
+  for (let i = 0; i < name.length; ++i) {
+    const char = name[i];
+    const lower = char.toLowerCase();
+
+    if (char !== lower && i > 0) {
+      const prev = name[i - 1];
+      const next = i + 1 < name.length ? name[i + 1] : "";
+      const prevIsUpper = prev !== prev.toLowerCase();
+      const nextIsLower = next !== "" && next !== next.toUpperCase();
+
+      if (prev !== "_" && (!prevIsUpper || nextIsLower)) {
+        result += "_";
+      }
+    }
+
+    result += lower;
+  }
+
+  return result;
+}
+
+// The inverse of `snakeCase`, as done by `#[w3_module]` and
+// `#[w3_import]` to name arguments and methods in the schema
+export function camelCase(ident: string): string {
+  let result = "";
+  let upper = false;
+
+  for (const char of ident.replace(/^r#/, "")) {
+    if (char === "_") {
+      upper = result.length > 0;
+    } else if (upper) {
+      result += char.toUpperCase();
+      upper = false;
+    } else {
+      result += char;
+    }
+  }
+
+  return result;
+}
+
+// Escapes Rust keywords, e.g. a `type` property becomes `r#type`
+export function rustIdentifier(ident: string): string {
+  if (pathKeywords.indexOf(ident) > -1) {
+    return `${ident}_`;
+  } else if (keywords.indexOf(ident) > -1) {
+    return `r#${ident}`;
+  } else {
+    return ident;
+  }
+}
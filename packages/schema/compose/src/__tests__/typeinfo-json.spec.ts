@@ -0,0 +1,23 @@
+import path from "path";
+import { readFileSync } from "fs";
+
+import {
+  composeTypeInfoJson,
+  renderTypeInfoJson,
+} from "@web3api/test-cases/scripts/typeinfo-json";
+import { GetPathToComposeTestFiles } from "@web3api/test-cases";
+import { normalizeLineEndings } from "@web3api/os-js";
+
+// The JSON copies of the TypeInfo, read by the Rust schema crate,
+// must match the TypeInfo they were generated from
+describe("Web3API Schema Composer Test Cases JSON", () => {
+  for (const file of composeTypeInfoJson()) {
+    it(path.relative(GetPathToComposeTestFiles(), file.json), async () => {
+      const json = normalizeLineEndings(
+        readFileSync(file.json, { encoding: "utf-8" }),
+        "\n"
+      );
+      expect(json).toEqual(await renderTypeInfoJson(file));
+    });
+  }
+});
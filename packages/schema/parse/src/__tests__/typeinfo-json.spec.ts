@@ -0,0 +1,20 @@
+import path from "path";
+import { readFileSync } from "fs";
+
+import {
+  parseTypeInfoJson,
+  renderTypeInfoJson,
+} from "@web3api/test-cases/scripts/typeinfo-json";
+import { GetPathToParseTestFiles } from "@web3api/test-cases";
+
+// The JSON copies of the TypeInfo, read by the Rust schema crate,
+// must match the TypeInfo they were generated from
+describe("Web3API Schema Parser Test Cases JSON", () => {
+  for (const file of parseTypeInfoJson()) {
+    it(path.relative(GetPathToParseTestFiles(), file.json), async () => {
+      const json = readFileSync(file.json, { encoding: "utf-8" })
+        .replace(/\r\n/g, "\n");
+      expect(json).toEqual(await renderTypeInfoJson(file));
+    });
+  }
+});
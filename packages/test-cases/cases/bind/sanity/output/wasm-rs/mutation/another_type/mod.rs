@@ -0,0 +1,64 @@
+use super::{
+    CustomType,
+};
+use std::io::Result;
+use web3api_wasm_rs::{
+    read_object,
+    Contextual,
+    ReadDecoder,
+    W3Object,
+    W3Read,
+    W3Write,
+    Write,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AnotherType {
+    pub prop: Option<String>,
+    pub circular: Option<Box<CustomType>>,
+}
+
+impl W3Object for AnotherType {
+    const TYPE_NAME: &'static str = "AnotherType";
+    const REQUIRED_FIELDS: &'static [(&'static str, &'static str)] = &[
+    ];
+
+    fn read_field(&mut self, field: &str, reader: &mut ReadDecoder) -> Result<bool> {
+        match field {
+            "prop" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<String>",
+                    "type found, reading property",
+                );
+                self.prop = W3Read::read(&mut reader)?;
+            }
+            "circular" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Box<CustomType>>",
+                    "type found, reading property",
+                );
+                self.circular = W3Read::read(&mut reader)?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+impl W3Read for AnotherType {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        read_object(reader)
+    }
+}
+
+impl W3Write for AnotherType {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_map_length(2);
+        writer.write_string("prop");
+        self.prop.write(writer);
+        writer.write_string("circular");
+        self.circular.write(writer);
+    }
+}
@@ -0,0 +1,13 @@
+//! Types that the query and mutation modules both define, each
+//! module with its own copy of them
+
+pub use super::another_type::AnotherType;
+pub use super::custom_type::CustomType;
+pub use super::custom_enum::CustomEnum;
+pub use super::imported::test_import_query::{
+    test_import_query,
+    TestImport_Query,
+};
+pub use super::imported::TestImport_Object;
+pub use super::imported::TestImport_AnotherObject;
+pub use super::imported::TestImport_Enum;
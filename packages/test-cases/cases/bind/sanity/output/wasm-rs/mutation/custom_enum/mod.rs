@@ -0,0 +1,9 @@
+use web3api_wasm_rs::W3Enum;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, W3Enum)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum CustomEnum {
+    #[default]
+    STRING,
+    BYTES,
+}
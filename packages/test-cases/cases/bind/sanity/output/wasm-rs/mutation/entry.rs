@@ -0,0 +1,9 @@
+use super::mutation::wrapped;
+use super::mutation::Mutation;
+
+/// Registers `M`'s methods with the runtime, as the module's
+/// `_w3_init` exported by `w3_mutation_exports!` does
+pub fn w3_init<M: Mutation + 'static>() {
+    ::web3api_wasm_rs::w3_add_invoke("mutationMethod", wrapped::mutation_method_wrapped::<M>);
+    ::web3api_wasm_rs::w3_add_invoke("objectMethod", wrapped::object_method_wrapped::<M>);
+}
@@ -0,0 +1,8 @@
+pub mod test_import_query;
+pub use test_import_query::TestImport_Query;
+pub mod test_import_object;
+pub use test_import_object::TestImport_Object;
+pub mod test_import_another_object;
+pub use test_import_another_object::TestImport_AnotherObject;
+pub mod test_import_enum;
+pub use test_import_enum::TestImport_Enum;
@@ -0,0 +1,136 @@
+use super::{
+    TestImport_AnotherObject,
+    TestImport_Enum,
+};
+use std::io::Result;
+use web3api_wasm_rs::{
+    read_object,
+    Contextual,
+    ReadDecoder,
+    W3Object,
+    W3Read,
+    W3Write,
+    Write,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[allow(non_camel_case_types)]
+pub struct TestImport_Object {
+    pub object: TestImport_AnotherObject,
+    pub opt_object: Option<Box<TestImport_AnotherObject>>,
+    pub object_array: Vec<TestImport_AnotherObject>,
+    pub opt_object_array: Option<Vec<Option<TestImport_AnotherObject>>>,
+    pub en: TestImport_Enum,
+    pub opt_enum: Option<TestImport_Enum>,
+    pub enum_array: Vec<TestImport_Enum>,
+    pub opt_enum_array: Option<Vec<Option<TestImport_Enum>>>,
+}
+
+impl W3Object for TestImport_Object {
+    const TYPE_NAME: &'static str = "TestImport_Object";
+    const REQUIRED_FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("object", "TestImport_AnotherObject"),
+        ("objectArray", "[TestImport_AnotherObject]"),
+        ("en", "TestImport_Enum"),
+        ("enumArray", "[TestImport_Enum]"),
+    ];
+
+    fn read_field(&mut self, field: &str, reader: &mut ReadDecoder) -> Result<bool> {
+        match field {
+            "object" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "TestImport_AnotherObject",
+                    "type found, reading property",
+                );
+                self.object = W3Read::read(&mut reader)?;
+            }
+            "optObject" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Box<TestImport_AnotherObject>>",
+                    "type found, reading property",
+                );
+                self.opt_object = W3Read::read(&mut reader)?;
+            }
+            "objectArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Vec<TestImport_AnotherObject>",
+                    "type found, reading property",
+                );
+                self.object_array = W3Read::read(&mut reader)?;
+            }
+            "optObjectArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Vec<Option<TestImport_AnotherObject>>>",
+                    "type found, reading property",
+                );
+                self.opt_object_array = W3Read::read(&mut reader)?;
+            }
+            "en" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "TestImport_Enum",
+                    "type found, reading property",
+                );
+                self.en = W3Read::read(&mut reader)?;
+            }
+            "optEnum" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<TestImport_Enum>",
+                    "type found, reading property",
+                );
+                self.opt_enum = W3Read::read(&mut reader)?;
+            }
+            "enumArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Vec<TestImport_Enum>",
+                    "type found, reading property",
+                );
+                self.enum_array = W3Read::read(&mut reader)?;
+            }
+            "optEnumArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Vec<Option<TestImport_Enum>>>",
+                    "type found, reading property",
+                );
+                self.opt_enum_array = W3Read::read(&mut reader)?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+impl W3Read for TestImport_Object {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        read_object(reader)
+    }
+}
+
+impl W3Write for TestImport_Object {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_map_length(8);
+        writer.write_string("object");
+        self.object.write(writer);
+        writer.write_string("optObject");
+        self.opt_object.write(writer);
+        writer.write_string("objectArray");
+        self.object_array.write(writer);
+        writer.write_string("optObjectArray");
+        self.opt_object_array.write(writer);
+        writer.write_string("en");
+        self.en.write(writer);
+        writer.write_string("optEnum");
+        self.opt_enum.write(writer);
+        writer.write_string("enumArray");
+        self.enum_array.write(writer);
+        writer.write_string("optEnumArray");
+        self.opt_enum_array.write(writer);
+    }
+}
@@ -0,0 +1,28 @@
+use super::{
+    TestImport_Enum,
+    TestImport_Object,
+};
+use web3api_wasm_rs::w3_import;
+
+#[w3_import(uri = "testimport.uri.eth", module = "query")]
+pub trait TestImport_Query {
+    fn imported_method(
+        str: String,
+        opt_str: Option<String>,
+        u: u32,
+        opt_u: Option<u32>,
+        u_array_array: Vec<Option<Vec<Option<u32>>>>,
+        object: TestImport_Object,
+        opt_object: Option<TestImport_Object>,
+        object_array: Vec<TestImport_Object>,
+        opt_object_array: Option<Vec<Option<TestImport_Object>>>,
+        en: TestImport_Enum,
+        opt_enum: Option<TestImport_Enum>,
+        enum_array: Vec<TestImport_Enum>,
+        opt_enum_array: Option<Vec<Option<TestImport_Enum>>>,
+    ) -> Option<TestImport_Object>;
+
+    fn another_method(
+        arg: Vec<String>,
+    ) -> i64;
+}
@@ -0,0 +1,21 @@
+//! Calls the module's methods, as implemented by `M`
+
+use super::{
+    Mutation,
+    Input_mutationMethod,
+    Input_objectMethod,
+};
+use std::convert::Infallible;
+use web3api_wasm_rs::wrap_invoke;
+
+pub fn mutation_method_wrapped<M: Mutation>(args: &[u8]) -> Result<Vec<u8>, String> {
+    wrap_invoke("mutation", "mutationMethod", args, |input: Input_mutationMethod| {
+        Ok::<_, Infallible>(M::mutation_method(input))
+    })
+}
+
+pub fn object_method_wrapped<M: Mutation>(args: &[u8]) -> Result<Vec<u8>, String> {
+    wrap_invoke("mutation", "objectMethod", args, |input: Input_objectMethod| {
+        Ok::<_, Infallible>(M::object_method(input))
+    })
+}
@@ -0,0 +1,452 @@
+use super::{
+    AnotherType,
+    CustomEnum,
+};
+use std::io::Result;
+use web3api_wasm_rs::{
+    read_object,
+    BigInt,
+    Bytes,
+    Contextual,
+    ReadDecoder,
+    W3Object,
+    W3Read,
+    W3Write,
+    Write,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CustomType {
+    pub str: String,
+    pub opt_str: Option<String>,
+    pub u: u32,
+    pub opt_u: Option<u32>,
+    pub u8: u8,
+    pub u16: u16,
+    pub u32: u32,
+    pub u64: u64,
+    pub i: i32,
+    pub i8: i8,
+    pub i16: i16,
+    pub i32: i32,
+    pub i64: i64,
+    pub bigint: BigInt,
+    pub opt_bigint: Option<BigInt>,
+    pub bytes: Bytes,
+    pub opt_bytes: Option<Bytes>,
+    pub boolean: bool,
+    pub opt_boolean: Option<bool>,
+    pub u_array: Vec<u32>,
+    pub u_opt_array: Option<Vec<u32>>,
+    pub opt_u_opt_array: Option<Vec<Option<u32>>>,
+    pub opt_str_opt_array: Option<Vec<Option<String>>>,
+    pub u_array_array: Vec<Vec<u32>>,
+    pub u_opt_array_opt_array: Vec<Option<Vec<Option<u64>>>>,
+    pub u_array_opt_array_array: Vec<Option<Vec<Vec<u64>>>>,
+    pub crazy_array: Option<Vec<Option<Vec<Vec<Option<Vec<u64>>>>>>>,
+    pub object: AnotherType,
+    pub opt_object: Option<Box<AnotherType>>,
+    pub object_array: Vec<AnotherType>,
+    pub opt_object_array: Option<Vec<Option<AnotherType>>>,
+    pub en: CustomEnum,
+    pub opt_enum: Option<CustomEnum>,
+    pub enum_array: Vec<CustomEnum>,
+    pub opt_enum_array: Option<Vec<Option<CustomEnum>>>,
+}
+
+impl W3Object for CustomType {
+    const TYPE_NAME: &'static str = "CustomType";
+    const REQUIRED_FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("str", "String"),
+        ("u", "UInt"),
+        ("u8", "UInt8"),
+        ("u16", "UInt16"),
+        ("u32", "UInt32"),
+        ("u64", "UInt64"),
+        ("i", "Int"),
+        ("i8", "Int8"),
+        ("i16", "Int16"),
+        ("i32", "Int32"),
+        ("i64", "Int64"),
+        ("bigint", "BigInt"),
+        ("bytes", "Bytes"),
+        ("boolean", "Boolean"),
+        ("uArray", "[UInt]"),
+        ("uArrayArray", "[[UInt]]"),
+        ("uOptArrayOptArray", "[[UInt64]]"),
+        ("uArrayOptArrayArray", "[[[UInt64]]]"),
+        ("object", "AnotherType"),
+        ("objectArray", "[AnotherType]"),
+        ("en", "CustomEnum"),
+        ("enumArray", "[CustomEnum]"),
+    ];
+
+    fn read_field(&mut self, field: &str, reader: &mut ReadDecoder) -> Result<bool> {
+        match field {
+            "str" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "String",
+                    "type found, reading property",
+                );
+                self.str = W3Read::read(&mut reader)?;
+            }
+            "optStr" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<String>",
+                    "type found, reading property",
+                );
+                self.opt_str = W3Read::read(&mut reader)?;
+            }
+            "u" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "u32",
+                    "type found, reading property",
+                );
+                self.u = W3Read::read(&mut reader)?;
+            }
+            "optU" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<u32>",
+                    "type found, reading property",
+                );
+                self.opt_u = W3Read::read(&mut reader)?;
+            }
+            "u8" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "u8",
+                    "type found, reading property",
+                );
+                self.u8 = W3Read::read(&mut reader)?;
+            }
+            "u16" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "u16",
+                    "type found, reading property",
+                );
+                self.u16 = W3Read::read(&mut reader)?;
+            }
+            "u32" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "u32",
+                    "type found, reading property",
+                );
+                self.u32 = W3Read::read(&mut reader)?;
+            }
+            "u64" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "u64",
+                    "type found, reading property",
+                );
+                self.u64 = W3Read::read(&mut reader)?;
+            }
+            "i" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "i32",
+                    "type found, reading property",
+                );
+                self.i = W3Read::read(&mut reader)?;
+            }
+            "i8" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "i8",
+                    "type found, reading property",
+                );
+                self.i8 = W3Read::read(&mut reader)?;
+            }
+            "i16" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "i16",
+                    "type found, reading property",
+                );
+                self.i16 = W3Read::read(&mut reader)?;
+            }
+            "i32" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "i32",
+                    "type found, reading property",
+                );
+                self.i32 = W3Read::read(&mut reader)?;
+            }
+            "i64" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "i64",
+                    "type found, reading property",
+                );
+                self.i64 = W3Read::read(&mut reader)?;
+            }
+            "bigint" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "BigInt",
+                    "type found, reading property",
+                );
+                self.bigint = W3Read::read(&mut reader)?;
+            }
+            "optBigint" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<BigInt>",
+                    "type found, reading property",
+                );
+                self.opt_bigint = W3Read::read(&mut reader)?;
+            }
+            "bytes" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Bytes",
+                    "type found, reading property",
+                );
+                self.bytes = W3Read::read(&mut reader)?;
+            }
+            "optBytes" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Bytes>",
+                    "type found, reading property",
+                );
+                self.opt_bytes = W3Read::read(&mut reader)?;
+            }
+            "boolean" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "bool",
+                    "type found, reading property",
+                );
+                self.boolean = W3Read::read(&mut reader)?;
+            }
+            "optBoolean" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<bool>",
+                    "type found, reading property",
+                );
+                self.opt_boolean = W3Read::read(&mut reader)?;
+            }
+            "uArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Vec<u32>",
+                    "type found, reading property",
+                );
+                self.u_array = W3Read::read(&mut reader)?;
+            }
+            "uOptArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Vec<u32>>",
+                    "type found, reading property",
+                );
+                self.u_opt_array = W3Read::read(&mut reader)?;
+            }
+            "optUOptArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Vec<Option<u32>>>",
+                    "type found, reading property",
+                );
+                self.opt_u_opt_array = W3Read::read(&mut reader)?;
+            }
+            "optStrOptArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Vec<Option<String>>>",
+                    "type found, reading property",
+                );
+                self.opt_str_opt_array = W3Read::read(&mut reader)?;
+            }
+            "uArrayArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Vec<Vec<u32>>",
+                    "type found, reading property",
+                );
+                self.u_array_array = W3Read::read(&mut reader)?;
+            }
+            "uOptArrayOptArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Vec<Option<Vec<Option<u64>>>>",
+                    "type found, reading property",
+                );
+                self.u_opt_array_opt_array = W3Read::read(&mut reader)?;
+            }
+            "uArrayOptArrayArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Vec<Option<Vec<Vec<u64>>>>",
+                    "type found, reading property",
+                );
+                self.u_array_opt_array_array = W3Read::read(&mut reader)?;
+            }
+            "crazyArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Vec<Option<Vec<Vec<Option<Vec<u64>>>>>>>",
+                    "type found, reading property",
+                );
+                self.crazy_array = W3Read::read(&mut reader)?;
+            }
+            "object" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "AnotherType",
+                    "type found, reading property",
+                );
+                self.object = W3Read::read(&mut reader)?;
+            }
+            "optObject" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Box<AnotherType>>",
+                    "type found, reading property",
+                );
+                self.opt_object = W3Read::read(&mut reader)?;
+            }
+            "objectArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Vec<AnotherType>",
+                    "type found, reading property",
+                );
+                self.object_array = W3Read::read(&mut reader)?;
+            }
+            "optObjectArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Vec<Option<AnotherType>>>",
+                    "type found, reading property",
+                );
+                self.opt_object_array = W3Read::read(&mut reader)?;
+            }
+            "en" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "CustomEnum",
+                    "type found, reading property",
+                );
+                self.en = W3Read::read(&mut reader)?;
+            }
+            "optEnum" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<CustomEnum>",
+                    "type found, reading property",
+                );
+                self.opt_enum = W3Read::read(&mut reader)?;
+            }
+            "enumArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Vec<CustomEnum>",
+                    "type found, reading property",
+                );
+                self.enum_array = W3Read::read(&mut reader)?;
+            }
+            "optEnumArray" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "Option<Vec<Option<CustomEnum>>>",
+                    "type found, reading property",
+                );
+                self.opt_enum_array = W3Read::read(&mut reader)?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+impl W3Read for CustomType {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        read_object(reader)
+    }
+}
+
+impl W3Write for CustomType {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_map_length(35);
+        writer.write_string("str");
+        self.str.write(writer);
+        writer.write_string("optStr");
+        self.opt_str.write(writer);
+        writer.write_string("u");
+        self.u.write(writer);
+        writer.write_string("optU");
+        self.opt_u.write(writer);
+        writer.write_string("u8");
+        self.u8.write(writer);
+        writer.write_string("u16");
+        self.u16.write(writer);
+        writer.write_string("u32");
+        self.u32.write(writer);
+        writer.write_string("u64");
+        self.u64.write(writer);
+        writer.write_string("i");
+        self.i.write(writer);
+        writer.write_string("i8");
+        self.i8.write(writer);
+        writer.write_string("i16");
+        self.i16.write(writer);
+        writer.write_string("i32");
+        self.i32.write(writer);
+        writer.write_string("i64");
+        self.i64.write(writer);
+        writer.write_string("bigint");
+        self.bigint.write(writer);
+        writer.write_string("optBigint");
+        self.opt_bigint.write(writer);
+        writer.write_string("bytes");
+        self.bytes.write(writer);
+        writer.write_string("optBytes");
+        self.opt_bytes.write(writer);
+        writer.write_string("boolean");
+        self.boolean.write(writer);
+        writer.write_string("optBoolean");
+        self.opt_boolean.write(writer);
+        writer.write_string("uArray");
+        self.u_array.write(writer);
+        writer.write_string("uOptArray");
+        self.u_opt_array.write(writer);
+        writer.write_string("optUOptArray");
+        self.opt_u_opt_array.write(writer);
+        writer.write_string("optStrOptArray");
+        self.opt_str_opt_array.write(writer);
+        writer.write_string("uArrayArray");
+        self.u_array_array.write(writer);
+        writer.write_string("uOptArrayOptArray");
+        self.u_opt_array_opt_array.write(writer);
+        writer.write_string("uArrayOptArrayArray");
+        self.u_array_opt_array_array.write(writer);
+        writer.write_string("crazyArray");
+        self.crazy_array.write(writer);
+        writer.write_string("object");
+        self.object.write(writer);
+        writer.write_string("optObject");
+        self.opt_object.write(writer);
+        writer.write_string("objectArray");
+        self.object_array.write(writer);
+        writer.write_string("optObjectArray");
+        self.opt_object_array.write(writer);
+        writer.write_string("en");
+        self.en.write(writer);
+        writer.write_string("optEnum");
+        self.opt_enum.write(writer);
+        writer.write_string("enumArray");
+        self.enum_array.write(writer);
+        writer.write_string("optEnumArray");
+        self.opt_enum_array.write(writer);
+    }
+}
@@ -0,0 +1,9 @@
+use super::query::wrapped;
+use super::query::Query;
+
+/// Registers `M`'s methods with the runtime, as the module's
+/// `_w3_init` exported by `w3_query_exports!` does
+pub fn w3_init<M: Query + 'static>() {
+    ::web3api_wasm_rs::w3_add_invoke("queryMethod", wrapped::query_method_wrapped::<M>);
+    ::web3api_wasm_rs::w3_add_invoke("objectMethod", wrapped::object_method_wrapped::<M>);
+}
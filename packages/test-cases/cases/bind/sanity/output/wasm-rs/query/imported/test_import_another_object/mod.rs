@@ -0,0 +1,52 @@
+use std::io::Result;
+use web3api_wasm_rs::{
+    read_object,
+    Contextual,
+    ReadDecoder,
+    W3Object,
+    W3Read,
+    W3Write,
+    Write,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[allow(non_camel_case_types)]
+pub struct TestImport_AnotherObject {
+    pub prop: String,
+}
+
+impl W3Object for TestImport_AnotherObject {
+    const TYPE_NAME: &'static str = "TestImport_AnotherObject";
+    const REQUIRED_FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("prop", "String"),
+    ];
+
+    fn read_field(&mut self, field: &str, reader: &mut ReadDecoder) -> Result<bool> {
+        match field {
+            "prop" => {
+                let mut reader = reader.push_scoped(
+                    field,
+                    "String",
+                    "type found, reading property",
+                );
+                self.prop = W3Read::read(&mut reader)?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+impl W3Read for TestImport_AnotherObject {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        read_object(reader)
+    }
+}
+
+impl W3Write for TestImport_AnotherObject {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_map_length(1);
+        writer.write_string("prop");
+        self.prop.write(writer);
+    }
+}
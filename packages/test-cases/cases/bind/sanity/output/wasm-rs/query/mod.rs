@@ -0,0 +1,27 @@
+// Nested schema types, e.g. `[[UInt64]]`, make for long Rust types
+#![allow(clippy::type_complexity)]
+
+pub mod query;
+pub use query::{
+    Query,
+    Input_queryMethod,
+    Input_objectMethod,
+};
+
+pub mod custom_type;
+pub use custom_type::CustomType;
+pub mod another_type;
+pub use another_type::AnotherType;
+pub mod custom_enum;
+pub use custom_enum::CustomEnum;
+
+pub mod imported;
+pub use imported::{
+    TestImport_Query,
+    TestImport_Object,
+    TestImport_AnotherObject,
+    TestImport_Enum,
+};
+
+pub mod common;
+pub mod entry;
@@ -0,0 +1,203 @@
+pub mod wrapped;
+
+use super::{
+    AnotherType,
+    CustomEnum,
+};
+use std::io::Result;
+use web3api_wasm_rs::{
+    Contextual,
+    Read,
+    ReadDecoder,
+    RequiredFields,
+    W3Read,
+    W3Write,
+    Write,
+};
+
+/// The module's methods, for the crate to implement and
+/// export with `w3_query_exports!`
+pub trait Query {
+    fn query_method(input: Input_queryMethod) -> i32;
+
+    fn object_method(input: Input_objectMethod) -> Option<AnotherType>;
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[allow(non_camel_case_types)]
+pub struct Input_queryMethod {
+    pub str: String,
+    pub opt_str: Option<String>,
+    pub en: CustomEnum,
+    pub opt_enum: Option<CustomEnum>,
+    pub enum_array: Vec<CustomEnum>,
+    pub opt_enum_array: Option<Vec<Option<CustomEnum>>>,
+}
+
+impl W3Read for Input_queryMethod {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        let mut input = Self::default();
+        let mut required = RequiredFields::arguments(&[
+            ("str", "String"),
+            ("en", "CustomEnum"),
+            ("enumArray", "[CustomEnum]"),
+        ]);
+
+        let num_of_fields = reader.read_map_length()?;
+        for _ in 0..num_of_fields {
+            let field = reader.read_string()?;
+            let mut reader = reader.push_scoped(&field, "unknown", "searching for property type");
+            match field.as_str() {
+                "str" => {
+                    let mut reader = reader.push_scoped(
+                        &field,
+                        "String",
+                        "type found, reading property",
+                    );
+                    input.str = W3Read::read(&mut reader)?;
+                }
+                "optStr" => {
+                    let mut reader = reader.push_scoped(
+                        &field,
+                        "Option<String>",
+                        "type found, reading property",
+                    );
+                    input.opt_str = W3Read::read(&mut reader)?;
+                }
+                "en" => {
+                    let mut reader = reader.push_scoped(
+                        &field,
+                        "CustomEnum",
+                        "type found, reading property",
+                    );
+                    input.en = W3Read::read(&mut reader)?;
+                }
+                "optEnum" => {
+                    let mut reader = reader.push_scoped(
+                        &field,
+                        "Option<CustomEnum>",
+                        "type found, reading property",
+                    );
+                    input.opt_enum = W3Read::read(&mut reader)?;
+                }
+                "enumArray" => {
+                    let mut reader = reader.push_scoped(
+                        &field,
+                        "Vec<CustomEnum>",
+                        "type found, reading property",
+                    );
+                    input.enum_array = W3Read::read(&mut reader)?;
+                }
+                "optEnumArray" => {
+                    let mut reader = reader.push_scoped(
+                        &field,
+                        "Option<Vec<Option<CustomEnum>>>",
+                        "type found, reading property",
+                    );
+                    input.opt_enum_array = W3Read::read(&mut reader)?;
+                }
+                _ => reader.skip()?,
+            }
+            required.mark(&field);
+        }
+        required.check(reader.get_context())?;
+
+        Ok(input)
+    }
+}
+
+impl W3Write for Input_queryMethod {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_map_length(6);
+        writer.write_string("str");
+        self.str.write(writer);
+        writer.write_string("optStr");
+        self.opt_str.write(writer);
+        writer.write_string("en");
+        self.en.write(writer);
+        writer.write_string("optEnum");
+        self.opt_enum.write(writer);
+        writer.write_string("enumArray");
+        self.enum_array.write(writer);
+        writer.write_string("optEnumArray");
+        self.opt_enum_array.write(writer);
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[allow(non_camel_case_types)]
+pub struct Input_objectMethod {
+    pub object: AnotherType,
+    pub opt_object: Option<AnotherType>,
+    pub object_array: Vec<AnotherType>,
+    pub opt_object_array: Option<Vec<Option<AnotherType>>>,
+}
+
+impl W3Read for Input_objectMethod {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        let mut input = Self::default();
+        let mut required = RequiredFields::arguments(&[
+            ("object", "AnotherType"),
+            ("objectArray", "[AnotherType]"),
+        ]);
+
+        let num_of_fields = reader.read_map_length()?;
+        for _ in 0..num_of_fields {
+            let field = reader.read_string()?;
+            let mut reader = reader.push_scoped(&field, "unknown", "searching for property type");
+            match field.as_str() {
+                "object" => {
+                    let mut reader = reader.push_scoped(
+                        &field,
+                        "AnotherType",
+                        "type found, reading property",
+                    );
+                    input.object = W3Read::read(&mut reader)?;
+                }
+                "optObject" => {
+                    let mut reader = reader.push_scoped(
+                        &field,
+                        "Option<AnotherType>",
+                        "type found, reading property",
+                    );
+                    input.opt_object = W3Read::read(&mut reader)?;
+                }
+                "objectArray" => {
+                    let mut reader = reader.push_scoped(
+                        &field,
+                        "Vec<AnotherType>",
+                        "type found, reading property",
+                    );
+                    input.object_array = W3Read::read(&mut reader)?;
+                }
+                "optObjectArray" => {
+                    let mut reader = reader.push_scoped(
+                        &field,
+                        "Option<Vec<Option<AnotherType>>>",
+                        "type found, reading property",
+                    );
+                    input.opt_object_array = W3Read::read(&mut reader)?;
+                }
+                _ => reader.skip()?,
+            }
+            required.mark(&field);
+        }
+        required.check(reader.get_context())?;
+
+        Ok(input)
+    }
+}
+
+impl W3Write for Input_objectMethod {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_map_length(4);
+        writer.write_string("object");
+        self.object.write(writer);
+        writer.write_string("optObject");
+        self.opt_object.write(writer);
+        writer.write_string("objectArray");
+        self.object_array.write(writer);
+        writer.write_string("optObjectArray");
+        self.opt_object_array.write(writer);
+    }
+}
@@ -0,0 +1,21 @@
+//! Calls the module's methods, as implemented by `M`
+
+use super::{
+    Query,
+    Input_queryMethod,
+    Input_objectMethod,
+};
+use std::convert::Infallible;
+use web3api_wasm_rs::wrap_invoke;
+
+pub fn query_method_wrapped<M: Query>(args: &[u8]) -> Result<Vec<u8>, String> {
+    wrap_invoke("query", "queryMethod", args, |input: Input_queryMethod| {
+        Ok::<_, Infallible>(M::query_method(input))
+    })
+}
+
+pub fn object_method_wrapped<M: Query>(args: &[u8]) -> Result<Vec<u8>, String> {
+    wrap_invoke("query", "objectMethod", args, |input: Input_objectMethod| {
+        Ok::<_, Infallible>(M::object_method(input))
+    })
+}
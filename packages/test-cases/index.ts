@@ -2,3 +2,4 @@ export const GetPathToBindTestFiles = () => `${__dirname}/cases/bind`
 export const GetPathToComposeTestFiles = () => `${__dirname}/cases/compose`
 export const GetPathToParseTestFiles = () => `${__dirname}/cases/parse`
 export const GetPathToTestApis = () => `${__dirname}/cases/apis`
+export const GetPathToMsgpackTestFiles = () => `${__dirname}/cases/msgpack`
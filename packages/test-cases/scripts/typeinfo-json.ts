@@ -0,0 +1,82 @@
+// The Rust schema crate (packages/wasm/rs/schema) reads the TypeInfo of
+// the parse and compose test cases as JSON. This script writes each
+// case's `.ts` TypeInfo to the `.json` file beside it; the schema
+// packages' tests check that the JSON files are up to date.
+//
+// Usage: yarn generate:typeinfo-json
+
+import path from "path";
+import { readdirSync, writeFileSync, existsSync } from "fs";
+
+import { GetPathToComposeTestFiles, GetPathToParseTestFiles } from "../";
+
+export interface TypeInfoJson {
+  // The `.ts` module, and its export holding the TypeInfo
+  module: string;
+  exportName: string;
+  // The `.json` file it is written to
+  json: string;
+}
+
+export function parseTypeInfoJson(): TypeInfoJson[] {
+  const root = GetPathToParseTestFiles();
+
+  return readdirSync(root, { withFileTypes: true })
+    .filter((dirent) => dirent.isDirectory())
+    .map((dirent) => ({
+      module: path.join(root, dirent.name, "output.ts"),
+      exportName: "output",
+      json: path.join(root, dirent.name, "typeinfo.json"),
+    }))
+    .filter((file) => existsSync(file.module));
+}
+
+export function composeTypeInfoJson(): TypeInfoJson[] {
+  const root = GetPathToComposeTestFiles();
+  const files: TypeInfoJson[] = [];
+
+  for (const dirent of readdirSync(root, { withFileTypes: true })) {
+    if (!dirent.isDirectory()) {
+      continue;
+    }
+
+    for (const name of ["query", "mutation", "schema"]) {
+      const output = path.join(root, dirent.name, "output");
+      files.push({
+        module: path.join(output, `${name}.ts`),
+        exportName: "typeInfo",
+        json: path.join(output, `${name}.json`),
+      });
+    }
+  }
+
+  return files.filter((file) => existsSync(file.module));
+}
+
+// The JSON written for `file`
+export async function renderTypeInfoJson(file: TypeInfoJson): Promise<string> {
+  const module = await import(file.module);
+  const typeInfo = module[file.exportName];
+
+  if (!typeInfo) {
+    throw Error(
+      `Required named export "${file.exportName}" is missing in ${file.module}`
+    );
+  }
+
+  return JSON.stringify(typeInfo, null, 2) + "\n";
+}
+
+async function main(): Promise<void> {
+  for (const file of [...parseTypeInfoJson(), ...composeTypeInfoJson()]) {
+    writeFileSync(file.json, await renderTypeInfoJson(file));
+    console.log(`Wrote ${path.relative(process.cwd(), file.json)}`);
+  }
+}
+
+if (require.main === module) {
+  main().catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
+}
@@ -1,3 +1,8 @@
+const fs = require("fs");
+const path = require("path");
+
+const msgpackFixtures = path.join(__dirname, "../../test-cases/cases/msgpack/sanity");
+
 module.exports = {
   /**
    * A set of globs passed to the glob package that qualify typescript files for testing.
@@ -28,12 +33,33 @@ module.exports = {
   /**
    * Add your required AssemblyScript imports here.
    */
-  imports: {
-    w3: {
-      __w3_invoke_args: () => { },
-      __w3_invoke_result: () => { },
-      __w3_invoke_error: () => { }
-    }
+  imports(memory, createImports, instantiateSync, binary) {
+    let instance;
+    const readBytes = (ptr, len) =>
+      Buffer.from(instance.exports.memory.buffer, ptr, len);
+
+    instance = instantiateSync(binary, createImports({
+      w3: {
+        __w3_invoke_args: () => { },
+        __w3_invoke_result: () => { },
+        __w3_invoke_error: () => { }
+      },
+      fixtures: {
+        /** Compares bytes with a msgpack fixture, or rewrites it if UPDATE_MSGPACK_FIXTURES is set */
+        __fixture_matches: (namePtr, nameLen, bufPtr, bufLen) => {
+          const name = readBytes(namePtr, nameLen).toString("utf-8");
+          const bytes = readBytes(bufPtr, bufLen);
+          const file = path.join(msgpackFixtures, `${name}.msgpack`);
+
+          if (process.env.UPDATE_MSGPACK_FIXTURES) {
+            fs.writeFileSync(file, bytes);
+            return true;
+          }
+          return fs.existsSync(file) && bytes.equals(fs.readFileSync(file));
+        }
+      }
+    }));
+    return instance;
   },
   /**
    * All performance statistics reporting can be configured here.
@@ -0,0 +1,192 @@
+/* eslint-disable @typescript-eslint/no-inferrable-types */
+
+// Generates the golden msgpack vectors in test-cases/cases/msgpack/sanity,
+// which the Rust runtime's codec is checked against, by serializing the
+// types of the bind/sanity test case with their generated bindings.
+// A fixture that differs from the encoded bytes fails its test; run with
+// UPDATE_MSGPACK_FIXTURES=1 to rewrite the fixtures instead.
+
+// The generated bindings import the runtime as a package, so their types
+// are those of "@web3api/wasm-as" rather than "../"
+import {
+  Nullable,
+  BigInt,
+  Write,
+  WriteEncoder,
+  WriteSizer,
+} from "@web3api/wasm-as";
+import {
+  AnotherType,
+  CustomEnum,
+  CustomType,
+} from "../../../../test-cases/cases/bind/sanity/output/wasm-as/query";
+
+@external("fixtures", "__fixture_matches")
+declare function __fixture_matches(
+  namePtr: u32,
+  nameLen: u32,
+  bufPtr: u32,
+  bufLen: u32
+): bool;
+
+function fixtureMatches(name: string, buffer: ArrayBuffer): bool {
+  const nameBuf = String.UTF8.encode(name);
+  return __fixture_matches(
+    changetype<u32>(nameBuf),
+    nameBuf.byteLength,
+    changetype<u32>(buffer),
+    buffer.byteLength
+  );
+}
+
+function bytes(values: Array<u8>): ArrayBuffer {
+  const buffer = new ArrayBuffer(values.length);
+  const view = Uint8Array.wrap(buffer);
+  for (let i = 0; i < values.length; i++) {
+    view[i] = values[i];
+  }
+  return buffer;
+}
+
+function serialize(write: (writer: Write) => void): ArrayBuffer {
+  const sizer = new WriteSizer();
+  write(sizer);
+  const buffer = new ArrayBuffer(sizer.length);
+  write(new WriteEncoder(buffer));
+  return buffer;
+}
+
+function anotherType(
+  prop: string | null,
+  circular: CustomType | null
+): AnotherType {
+  return { prop: prop, circular: circular };
+}
+
+function customTypeDefaults(): CustomType {
+  return {
+    str: "",
+    optStr: null,
+    u: 0,
+    optU: Nullable.fromNull<u32>(),
+    u8: 0,
+    u16: 0,
+    u32: 0,
+    u64: 0,
+    i: 0,
+    i8: 0,
+    i16: 0,
+    i32: 0,
+    i64: 0,
+    bigint: BigInt.fromString("0"),
+    optBigint: null,
+    bytes: new ArrayBuffer(0),
+    optBytes: null,
+    boolean: false,
+    optBoolean: Nullable.fromNull<bool>(),
+    uArray: [],
+    uOptArray: null,
+    optUOptArray: null,
+    optStrOptArray: null,
+    uArrayArray: [],
+    uOptArrayOptArray: [],
+    uArrayOptArrayArray: [],
+    crazyArray: null,
+    object: anotherType(null, null),
+    optObject: null,
+    objectArray: [],
+    optObjectArray: null,
+    en: CustomEnum.STRING,
+    optEnum: Nullable.fromNull<CustomEnum>(),
+    enumArray: [],
+    optEnumArray: null,
+  };
+}
+
+function customType(): CustomType {
+  const allBytes = new Array<u8>(300);
+  for (let i = 0; i < allBytes.length; i++) {
+    allBytes[i] = <u8>i;
+  }
+
+  return {
+    str: "Hello, 世界",
+    optStr: "opt",
+    u: 200,
+    optU: Nullable.fromValue<u32>(70000),
+    u8: u8.MAX_VALUE,
+    u16: u16.MAX_VALUE,
+    u32: u32.MAX_VALUE,
+    u64: u64.MAX_VALUE,
+    i: -33,
+    i8: i8.MIN_VALUE,
+    i16: i16.MIN_VALUE,
+    i32: i32.MIN_VALUE,
+    i64: i64.MIN_VALUE,
+    bigint: BigInt.fromString("-123456789012345678901234567890"),
+    optBigint: BigInt.fromString("170141183460469231731687303715884105727"),
+    bytes: bytes(allBytes),
+    optBytes: bytes([0xde, 0xad, 0xbe, 0xef]),
+    boolean: true,
+    optBoolean: Nullable.fromValue<bool>(false),
+    uArray: [0, 127, 128, 65536],
+    uOptArray: [],
+    optUOptArray: [
+      Nullable.fromValue<u32>(1),
+      Nullable.fromNull<u32>(),
+      Nullable.fromValue<u32>(3),
+    ],
+    optStrOptArray: ["a", null, "x".repeat(40)],
+    uArrayArray: [[1, 2], [], [3]],
+    uOptArrayOptArray: [
+      [Nullable.fromValue<u64>(1), Nullable.fromNull<u64>()],
+      null,
+      [],
+    ],
+    uArrayOptArrayArray: [null, [[1], [2, 3]]],
+    crazyArray: [null, [[[4], null], []]],
+    object: anotherType("object", null),
+    optObject: anotherType(null, customTypeDefaults()),
+    objectArray: [anotherType("a", null), anotherType(null, null)],
+    optObjectArray: [null, anotherType("b", null)],
+    en: CustomEnum.BYTES,
+    optEnum: Nullable.fromValue<CustomEnum>(CustomEnum.STRING),
+    enumArray: [CustomEnum.STRING, CustomEnum.BYTES],
+    optEnumArray: [
+      Nullable.fromValue<CustomEnum>(CustomEnum.BYTES),
+      Nullable.fromNull<CustomEnum>(),
+    ],
+  };
+}
+
+describe("MsgPack sanity fixtures", () => {
+  it("custom-type", () => {
+    const buffer = CustomType.toBuffer(customType());
+    expect(fixtureMatches("custom-type", buffer)).toBe(true);
+  });
+
+  it("custom-type-defaults", () => {
+    const buffer = CustomType.toBuffer(customTypeDefaults());
+    expect(fixtureMatches("custom-type-defaults", buffer)).toBe(true);
+  });
+
+  it("another-type", () => {
+    const circular = anotherType("circular", customType());
+    expect(
+      fixtureMatches("another-type", AnotherType.toBuffer(circular))
+    ).toBe(true);
+
+    const nulls = anotherType(null, null);
+    expect(
+      fixtureMatches("another-type-nulls", AnotherType.toBuffer(nulls))
+    ).toBe(true);
+  });
+
+  it("custom-enum", () => {
+    // Enums are written as their i32 values
+    const buffer = serialize((writer: Write) => {
+      writer.writeInt32(CustomEnum.BYTES);
+    });
+    expect(fixtureMatches("custom-enum", buffer)).toBe(true);
+  });
+});
@@ -0,0 +1,42 @@
+//! Parsing of `#[w3(...)]` attributes
+
+use syn::{Attribute, LitStr, Result};
+
+/// Options given as `#[w3(key = "value", ...)]`
+#[derive(Default)]
+pub struct W3Attrs {
+    pub name: Option<LitStr>,
+    pub rename: Option<LitStr>,
+    pub r#type: Option<LitStr>,
+}
+
+impl W3Attrs {
+    /// Parses every `w3` attribute in `attrs`, accepting only `allowed` keys
+    pub fn parse(attrs: &[Attribute], allowed: &[&str]) -> Result<Self> {
+        let mut parsed = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("w3")) {
+            attr.parse_nested_meta(|meta| {
+                let key = meta
+                    .path
+                    .get_ident()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or_default();
+                if !allowed.contains(&key.as_str()) {
+                    return Err(meta.error(format!(
+                        "unsupported w3 attribute, expected one of: {}",
+                        allowed.join(", ")
+                    )));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                match key.as_str() {
+                    "name" => parsed.name = Some(value),
+                    "rename" => parsed.rename = Some(value),
+                    "type" => parsed.r#type = Some(value),
+                    _ => unreachable!(),
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
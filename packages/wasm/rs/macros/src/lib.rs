@@ -0,0 +1,75 @@
+//! Procedural macros for the Web3API Rust Runtime.
+//! Generated code refers to the runtime as `::web3api_wasm_rs`.
+
+extern crate proc_macro;
+
+mod attrs;
+mod w3_enum;
+mod w3_import;
+mod w3_module;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Implements `W3Enum`, `W3Read` and `W3Write` for a fieldless enum.
+/// Values are the variants' positions; keys are their names, unless
+/// renamed with `#[w3(rename = "KEY")]`. The schema name defaults to
+/// the enum's and can be set with `#[w3(name = "Name")]`.
+#[proc_macro_derive(W3Enum, attributes(w3))]
+pub fn derive_w3_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    w3_enum::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Exports the public functions of an inline module, or the public
+/// associated functions of an impl block, as Web3API methods. Each
+/// gets an `Input_<method>` type, read from the invoke args, and a
+/// `<fn>_wrapped` function that calls it and serializes its result;
+/// `_w3_init` registers them and `_w3_invoke` dispatches to them.
+///
+/// Schema names are the camelCase function and argument names, or
+/// set with `#[w3(rename = "name")]`. Arguments of type `Option<T>`
+/// are optional. Errors name arguments with their schema types, e.g.
+/// `[UInt32]` for `Vec<u32>`; `#[w3(type = "[UInt]")]` sets another.
+/// Functions may return a value or a `Result` whose
+/// error is reported through `__w3_invoke_error`. The module type
+/// (`query` or `mutation`) is taken from the module or type name,
+/// e.g. `mod query` or `impl crate::Query`, or given as
+/// `#[w3_module(mutation)]`; other names are rejected.
+///
+/// A crate may define both a query and a mutation module, sharing
+/// its types between them. Building with the runtime's `query` or
+/// `mutation` feature then leaves out the other module's exports;
+/// without either, native builds export neither, for testing.
+#[proc_macro_attribute]
+pub fn w3_module(attr: TokenStream, item: TokenStream) -> TokenStream {
+    w3_module::expand(attr.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Turns a trait declaring another Web3API's methods into a client
+/// stub of the same name. For `TestImport_Query`, each method gets
+/// an `Input_<method>` type in a `test_import_query` module and becomes
+/// `TestImport_Query::method(&test_import_query::Input_method) -> io::Result<Out>`,
+/// which subinvokes the import at `TestImport_Query::URI`:
+///
+/// ```ignore
+/// #[w3_import(uri = "testimport.uri.eth")]
+/// pub trait TestImport_Query {
+///     fn another_method(arg: Vec<String>) -> i64;
+/// }
+/// ```
+///
+/// The imported module is taken from the `_Query`/`_Mutation` suffix,
+/// or given as `module = "mutation"`. Names follow `#[w3_module]`.
+/// The inputs' module glob-imports the trait's parent module, so
+/// their argument types resolve as they do in the trait.
+#[proc_macro_attribute]
+pub fn w3_import(attr: TokenStream, item: TokenStream) -> TokenStream {
+    w3_import::expand(attr.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
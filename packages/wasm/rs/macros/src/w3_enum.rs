@@ -0,0 +1,95 @@
+use crate::attrs::W3Attrs;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Result};
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "W3Enum can only be derived for enums",
+            ))
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "W3Enum enums cannot be generic",
+        ));
+    }
+
+    let ident = &input.ident;
+    let name = W3Attrs::parse(&input.attrs, &["name"])?
+        .name
+        .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+
+    let mut variants = Vec::new();
+    let mut values = Vec::new();
+    let mut keys = Vec::new();
+    for (position, variant) in data.variants.iter().enumerate() {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "W3Enum variants cannot have fields",
+            ));
+        }
+        if let Some((_, discriminant)) = &variant.discriminant {
+            return Err(Error::new_spanned(
+                discriminant,
+                "W3Enum values are the variants' positions, remove the discriminant",
+            ));
+        }
+        let key = W3Attrs::parse(&variant.attrs, &["rename"])?
+            .rename
+            .unwrap_or_else(|| LitStr::new(&variant.ident.to_string(), variant.ident.span()));
+        variants.push(&variant.ident);
+        values.push(position as i32);
+        keys.push(key);
+    }
+
+    Ok(quote! {
+        impl ::web3api_wasm_rs::W3Enum for #ident {
+            const NAME: &'static str = #name;
+
+            fn from_value(value: i32) -> ::std::option::Option<Self> {
+                match value {
+                    #(#values => ::std::option::Option::Some(Self::#variants),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn from_key(key: &str) -> ::std::option::Option<Self> {
+                match key {
+                    #(#keys => ::std::option::Option::Some(Self::#variants),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn value(self) -> i32 {
+                match self {
+                    #(Self::#variants => #values,)*
+                }
+            }
+
+            fn key(self) -> &'static str {
+                match self {
+                    #(Self::#variants => #keys,)*
+                }
+            }
+        }
+
+        impl ::web3api_wasm_rs::W3Read for #ident {
+            fn read(reader: &mut ::web3api_wasm_rs::ReadDecoder) -> ::std::io::Result<Self> {
+                ::web3api_wasm_rs::read_enum(reader)
+            }
+        }
+
+        impl ::web3api_wasm_rs::W3Write for #ident {
+            fn write<W: ::web3api_wasm_rs::Write>(&self, writer: &mut W) {
+                ::web3api_wasm_rs::write_enum(*self, writer);
+            }
+        }
+    })
+}
@@ -0,0 +1,157 @@
+use crate::w3_module::{generate_input, input_ident, parse_method, Method};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::meta::parser;
+use syn::parse::Parser;
+use syn::{
+    parse2, Attribute, Error, Ident, ItemTrait, LitStr, Result, ReturnType, TraitItem, Type,
+};
+
+pub fn expand(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let mut uri: Option<LitStr> = None;
+    let mut module_type: Option<LitStr> = None;
+    parser(|meta| {
+        if meta.path.is_ident("uri") {
+            uri = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("module") {
+            module_type = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("unsupported w3_import option, expected `uri` or `module`"));
+        }
+        Ok(())
+    })
+    .parse2(attr)?;
+
+    let mut item_trait: ItemTrait = parse2(item)?;
+    let uri = uri.ok_or_else(|| {
+        Error::new_spanned(&item_trait.ident, "#[w3_import] needs the import's `uri`")
+    })?;
+    let module_type = match module_type {
+        Some(module_type) => match module_type.value().as_str() {
+            "query" | "mutation" => module_type.value(),
+            _ => {
+                return Err(Error::new_spanned(
+                    module_type,
+                    "expected `query` or `mutation`",
+                ))
+            }
+        },
+        // `TestImport_Query` imports the `query` module
+        None => {
+            let ident = item_trait.ident.to_string();
+            match ident.rsplit('_').next().unwrap_or_default() {
+                "Query" => "query".to_string(),
+                "Mutation" => "mutation".to_string(),
+                _ => {
+                    return Err(Error::new_spanned(
+                        &item_trait.ident,
+                        "cannot infer the imported module, name it `<Namespace>_Query` \
+                         or `<Namespace>_Mutation`, or set `module = \"...\"`",
+                    ))
+                }
+            }
+        }
+    };
+
+    // `TestImport_Query`'s inputs go in `test_import_query`, so
+    // that imports sharing a method name don't clash
+    let inputs_mod = Ident::new(&snake_case(&item_trait.ident), item_trait.ident.span());
+    let mut methods = Vec::new();
+    let mut stubs = Vec::new();
+    for item in item_trait.items.iter_mut() {
+        let trait_fn = match item {
+            TraitItem::Fn(trait_fn) => trait_fn,
+            item => {
+                return Err(Error::new_spanned(
+                    item,
+                    "#[w3_import] traits may only declare methods",
+                ))
+            }
+        };
+        if let Some(body) = &trait_fn.default {
+            return Err(Error::new_spanned(
+                body,
+                "imported methods are declared without a body",
+            ));
+        }
+        let output = match &trait_fn.sig.output {
+            ReturnType::Type(_, ty) => (**ty).clone(),
+            ReturnType::Default => {
+                return Err(Error::new_spanned(
+                    &trait_fn.sig,
+                    "imported methods must declare their return type",
+                ))
+            }
+        };
+        let method = parse_method(&mut trait_fn.attrs, &mut trait_fn.sig)?;
+        stubs.push(generate_stub(
+            &module_type,
+            &inputs_mod,
+            &method,
+            &trait_fn.attrs,
+            &output,
+        ));
+        methods.push(method);
+    }
+
+    let attrs = &item_trait.attrs;
+    let vis = &item_trait.vis;
+    let ident = &item_trait.ident;
+    let inputs = methods.iter().map(generate_input);
+
+    Ok(quote! {
+        #(#attrs)*
+        #[allow(non_camel_case_types)]
+        #vis struct #ident;
+
+        impl #ident {
+            pub const URI: &'static str = #uri;
+
+            #(#stubs)*
+        }
+
+        #vis mod #inputs_mod {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #(#inputs)*
+        }
+    })
+}
+
+/// A method serializing its `Input_<method>` and subinvoking the import
+fn generate_stub(
+    module_type: &str,
+    inputs_mod: &Ident,
+    method: &Method,
+    attrs: &[Attribute],
+    output: &Type,
+) -> TokenStream {
+    let ident = &method.ident;
+    let name = &method.name;
+    let input = input_ident(method);
+    quote! {
+        #(#attrs)*
+        pub fn #ident(input: &#inputs_mod::#input) -> ::std::io::Result<#output> {
+            ::web3api_wasm_rs::subinvoke(Self::URI, #module_type, #name, input)
+        }
+    }
+}
+
+/// `TestImport_Query` becomes `test_import_query`
+fn snake_case(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    let chars: Vec<char> = ident.trim_start_matches("r#").chars().collect();
+    let mut result = String::with_capacity(chars.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 && !result.ends_with('_') {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
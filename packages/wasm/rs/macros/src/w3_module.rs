@@ -0,0 +1,429 @@
+use crate::attrs::W3Attrs;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse2, Attribute, Error, FnArg, GenericArgument, Ident, ImplItem, Item, ItemImpl, ItemMod,
+    LitStr, Pat, PathArguments, Result, ReturnType, Signature, Type, Visibility,
+};
+
+/// A method exported through `_w3_invoke`, or imported from another Web3API
+pub struct Method {
+    pub ident: Ident,
+    /// The method's name in the schema, e.g. `queryMethod`
+    pub name: LitStr,
+    pub args: Vec<Arg>,
+    pub fallible: bool,
+}
+
+pub struct Arg {
+    pub ident: Ident,
+    /// The argument's name in the schema, e.g. `optStr`
+    pub name: LitStr,
+    pub ty: Type,
+    /// The argument's type in the schema, e.g. `[CustomEnum]`
+    pub type_name: String,
+}
+
+pub fn expand(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let module_type = parse_module_type(attr)?;
+    match parse2::<Item>(item)? {
+        Item::Mod(item_mod) => expand_mod(module_type, item_mod),
+        Item::Impl(item_impl) => expand_impl(module_type, item_impl),
+        item => Err(Error::new_spanned(
+            item,
+            "#[w3_module] applies to an inline module or an impl block",
+        )),
+    }
+}
+
+/// `#[w3_module(query)]` or `#[w3_module(mutation)]`; without
+/// an argument the type is taken from the module or type name
+fn parse_module_type(attr: TokenStream) -> Result<Option<String>> {
+    if attr.is_empty() {
+        return Ok(None);
+    }
+    let ident: Ident = parse2(attr)?;
+    match ident.to_string().as_str() {
+        "query" | "mutation" => Ok(Some(ident.to_string())),
+        _ => Err(Error::new_spanned(ident, "expected `query` or `mutation`")),
+    }
+}
+
+const MODULE_TYPE_UNKNOWN: &str = "cannot infer the module type, name it `query` or `mutation`, \
+     or set it with `#[w3_module(query)]` or `#[w3_module(mutation)]`";
+
+/// `mod query` or `impl Query` is the query module, and likewise
+/// for mutations; other names must set the type explicitly
+fn infer_module_type(ident: &Ident) -> Result<String> {
+    match ident.to_string().to_lowercase().as_str() {
+        module_type @ ("query" | "mutation") => Ok(module_type.to_string()),
+        _ => Err(Error::new_spanned(ident, MODULE_TYPE_UNKNOWN)),
+    }
+}
+
+fn expand_mod(module_type: Option<String>, mut item_mod: ItemMod) -> Result<TokenStream> {
+    let module_type = match module_type {
+        Some(module_type) => module_type,
+        None => infer_module_type(&item_mod.ident)?,
+    };
+    let (_, items) = match &mut item_mod.content {
+        Some(content) => content,
+        None => {
+            return Err(Error::new_spanned(
+                &item_mod,
+                "#[w3_module] needs the module's content inline",
+            ))
+        }
+    };
+
+    let mut methods = Vec::new();
+    for item in items.iter_mut() {
+        if let Item::Fn(item_fn) = item {
+            if matches!(item_fn.vis, Visibility::Public(_)) {
+                methods.push(parse_method(&mut item_fn.attrs, &mut item_fn.sig)?);
+            }
+        }
+    }
+
+    let generated = generate(&module_type, &methods, None);
+    items.push(Item::Verbatim(generated));
+    Ok(item_mod.into_token_stream())
+}
+
+fn expand_impl(module_type: Option<String>, mut item_impl: ItemImpl) -> Result<TokenStream> {
+    let self_ty = item_impl.self_ty.clone();
+    let module_type = match (module_type, &*self_ty) {
+        (Some(module_type), _) => module_type,
+        // `impl crate::Query` is the query module
+        (None, Type::Path(type_path)) if type_path.qself.is_none() => {
+            match type_path.path.segments.last() {
+                Some(segment) => infer_module_type(&segment.ident)?,
+                None => return Err(Error::new_spanned(&self_ty, MODULE_TYPE_UNKNOWN)),
+            }
+        }
+        (None, _) => return Err(Error::new_spanned(&self_ty, MODULE_TYPE_UNKNOWN)),
+    };
+
+    let mut methods = Vec::new();
+    for item in item_impl.items.iter_mut() {
+        if let ImplItem::Fn(impl_fn) = item {
+            let has_receiver = matches!(impl_fn.sig.inputs.first(), Some(FnArg::Receiver(_)));
+            if matches!(impl_fn.vis, Visibility::Public(_)) && !has_receiver {
+                methods.push(parse_method(&mut impl_fn.attrs, &mut impl_fn.sig)?);
+            }
+        }
+    }
+
+    let generated = generate(&module_type, &methods, Some(&self_ty));
+    Ok(quote! {
+        #item_impl
+        #generated
+    })
+}
+
+/// Reads a method's signature, removing its `#[w3(...)]` attributes
+pub fn parse_method(attrs: &mut Vec<Attribute>, sig: &mut Signature) -> Result<Method> {
+    if !sig.generics.params.is_empty() || sig.asyncness.is_some() {
+        return Err(Error::new_spanned(
+            &sig.ident,
+            "#[w3_module] methods cannot be generic or async",
+        ));
+    }
+    let name = W3Attrs::parse(attrs, &["rename"])?
+        .rename
+        .unwrap_or_else(|| LitStr::new(&camel_case(&sig.ident), sig.ident.span()));
+    attrs.retain(|attr| !attr.path().is_ident("w3"));
+
+    let mut args = Vec::new();
+    for input in sig.inputs.iter_mut() {
+        let pat_type = match input {
+            FnArg::Typed(pat_type) => pat_type,
+            FnArg::Receiver(receiver) => {
+                return Err(Error::new_spanned(receiver, "unexpected receiver"))
+            }
+        };
+        let ident = match &*pat_type.pat {
+            Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+            pat => {
+                return Err(Error::new_spanned(
+                    pat,
+                    "#[w3_module] arguments must be plain identifiers",
+                ))
+            }
+        };
+        let attrs = W3Attrs::parse(&pat_type.attrs, &["rename", "type"])?;
+        let name = attrs
+            .rename
+            .unwrap_or_else(|| LitStr::new(&camel_case(&ident), ident.span()));
+        let type_name = match attrs.r#type {
+            Some(type_name) => type_name.value(),
+            None => schema_type(&pat_type.ty),
+        };
+        pat_type.attrs.retain(|attr| !attr.path().is_ident("w3"));
+        args.push(Arg {
+            ident,
+            name,
+            ty: (*pat_type.ty).clone(),
+            type_name,
+        });
+    }
+
+    let fallible = match &sig.output {
+        ReturnType::Type(_, ty) => last_segment_is(ty, "Result"),
+        ReturnType::Default => false,
+    };
+
+    Ok(Method {
+        ident: sig.ident.clone(),
+        name,
+        args,
+        fallible,
+    })
+}
+
+fn generate(module_type: &str, methods: &[Method], self_ty: Option<&Type>) -> TokenStream {
+    let wrappers = methods
+        .iter()
+        .map(|method| generate_method(module_type, method, self_ty));
+    let names = methods.iter().map(|method| &method.name);
+    let wrapped = methods
+        .iter()
+        .map(|method| format_ident!("{}_wrapped", method.ident));
+
+    let exports = quote! {
+        pub extern "C" fn _w3_init() {
+            #(::web3api_wasm_rs::w3_add_invoke(#names, #wrapped);)*
+        }
+
+        pub extern "C" fn _w3_invoke(method_size: u32, args_size: u32) -> bool {
+            ::web3api_wasm_rs::w3_invoke(method_size, args_size)
+        }
+    };
+    // Only the module selected by the runtime's `query`/`mutation`
+    // feature exports its methods; the rest stays usable as Rust.
+    // The runtime adds `#[no_mangle]`, as its features decide it
+    let exports = match module_type {
+        "query" => quote!(::web3api_wasm_rs::__w3_query_exports! { #exports }),
+        _ => quote!(::web3api_wasm_rs::__w3_mutation_exports! { #exports }),
+    };
+
+    quote! {
+        #(#wrappers)*
+
+        #exports
+    }
+}
+
+/// The method's `Input_<method>` type and its wrapped function
+fn generate_method(module_type: &str, method: &Method, self_ty: Option<&Type>) -> TokenStream {
+    let Method {
+        ident,
+        name,
+        args,
+        fallible,
+    } = method;
+    let input = input_ident(method);
+    let wrapped = format_ident!("{}_wrapped", ident);
+    let fields = args.iter().map(|arg| &arg.ident);
+    let input_type = generate_input(method);
+
+    let callee = match self_ty {
+        Some(self_ty) => quote!(<#self_ty>::#ident),
+        None => quote!(#ident),
+    };
+    let call = quote!(#callee(#(input.#fields),*));
+    let call = if *fallible {
+        call
+    } else {
+        quote!(::std::result::Result::Ok::<_, ::std::convert::Infallible>(#call))
+    };
+
+    quote! {
+        #input_type
+
+        pub fn #wrapped(args: &[u8]) -> ::std::result::Result<::std::vec::Vec<u8>, ::std::string::String> {
+            ::web3api_wasm_rs::wrap_invoke(#module_type, #name, args, |input: #input| #call)
+        }
+    }
+}
+
+/// `Input_<method>`, named after the method's schema name
+pub fn input_ident(method: &Method) -> Ident {
+    Ident::new(&format!("Input_{}", method.name.value()), Span::call_site())
+}
+
+/// The `Input_<method>` type holding the method's arguments,
+/// read from and written as a msgpack map
+pub fn generate_input(method: &Method) -> TokenStream {
+    let args = &method.args;
+    let input = input_ident(method);
+    let fields: Vec<&Ident> = args.iter().map(|arg| &arg.ident).collect();
+    let types: Vec<&Type> = args.iter().map(|arg| &arg.ty).collect();
+    let names: Vec<&LitStr> = args.iter().map(|arg| &arg.name).collect();
+    let type_names: Vec<&String> = args.iter().map(|arg| &arg.type_name).collect();
+    let locals: Vec<Ident> = fields
+        .iter()
+        .map(|field| format_ident!("_{}", field))
+        .collect();
+    let num_of_fields = args.len() as u32;
+
+    let required = args
+        .iter()
+        .zip(&type_names)
+        .filter(|(arg, _)| !last_segment_is(&arg.ty, "Option"))
+        .map(|(arg, type_name)| {
+            let name = &arg.name;
+            quote!((#name, #type_name))
+        });
+    let declarations = args.iter().zip(&locals).map(|(arg, local)| {
+        let ty = &arg.ty;
+        if last_segment_is(ty, "Option") {
+            quote!(let mut #local: #ty = ::std::option::Option::None;)
+        } else {
+            quote!(let mut #local: ::std::option::Option<#ty> = ::std::option::Option::None;)
+        }
+    });
+    let reads = args.iter().zip(&locals).map(|(arg, local)| {
+        let ty = &arg.ty;
+        if last_segment_is(ty, "Option") {
+            quote!(#local = <#ty as ::web3api_wasm_rs::W3Read>::read(&mut reader)?;)
+        } else {
+            quote!(#local = ::std::option::Option::Some(
+                <#ty as ::web3api_wasm_rs::W3Read>::read(&mut reader)?
+            );)
+        }
+    });
+    let values = args.iter().zip(&locals).map(|(arg, local)| {
+        if last_segment_is(&arg.ty, "Option") {
+            quote!(#local)
+        } else {
+            quote!(match #local {
+                ::std::option::Option::Some(value) => value,
+                ::std::option::Option::None => unreachable!("checked by RequiredFields"),
+            })
+        }
+    });
+
+    quote! {
+        #[allow(non_camel_case_types)]
+        pub struct #input {
+            #(pub #fields: #types,)*
+        }
+
+        impl ::web3api_wasm_rs::W3Read for #input {
+            fn read(reader: &mut ::web3api_wasm_rs::ReadDecoder) -> ::std::io::Result<Self> {
+                use ::web3api_wasm_rs::{Contextual, Read};
+
+                let mut required = ::web3api_wasm_rs::RequiredFields::arguments(&[#(#required),*]);
+                #(#declarations)*
+
+                let num_of_fields = reader.read_map_length()?;
+                for _ in 0..num_of_fields {
+                    let field = reader.read_string()?;
+                    let mut reader =
+                        reader.push_scoped(&field, "unknown", "searching for property type");
+                    match field.as_str() {
+                        #(#names => {
+                            let mut reader = reader.push_scoped(
+                                &field,
+                                #type_names,
+                                "type found, reading property",
+                            );
+                            #reads
+                        })*
+                        _ => reader.skip()?,
+                    }
+                    required.mark(&field);
+                }
+                required.check(reader.get_context())?;
+
+                ::std::result::Result::Ok(Self {
+                    #(#fields: #values,)*
+                })
+            }
+        }
+
+        impl ::web3api_wasm_rs::W3Write for #input {
+            fn write<W: ::web3api_wasm_rs::Write>(&self, writer: &mut W) {
+                writer.write_map_length(#num_of_fields);
+                #(
+                    writer.write_string(#names);
+                    ::web3api_wasm_rs::W3Write::write(&self.#fields, writer);
+                )*
+            }
+        }
+    }
+}
+
+fn last_segment_is(ty: &Type, ident: &str) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == ident),
+        _ => false,
+    }
+}
+
+/// The schema type of a Rust type, for context and error messages,
+/// without the `!` of required types as in `@web3api/wasm-as`:
+/// `Vec<Option<u32>>` is `[UInt32]`. Types without a scalar of
+/// their own, e.g. `CustomType`, keep their name.
+fn schema_type(ty: &Type) -> String {
+    let segment = match ty {
+        Type::Path(type_path) => type_path.path.segments.last(),
+        Type::Reference(reference) => return schema_type(&reference.elem),
+        Type::Paren(paren) => return schema_type(&paren.elem),
+        Type::Group(group) => return schema_type(&group.elem),
+        _ => None,
+    };
+    let segment = match segment {
+        Some(segment) => segment,
+        None => return ty.to_token_stream().to_string().replace(' ', ""),
+    };
+
+    let argument = match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => {
+            arguments.args.iter().find_map(|argument| match argument {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+        }
+        _ => None,
+    };
+    let ident = segment.ident.to_string();
+    match (ident.as_str(), argument) {
+        ("Vec", Some(item)) => format!("[{}]", schema_type(item)),
+        ("Option" | "Box", Some(inner)) => schema_type(inner),
+        ("i8", _) => "Int8".to_string(),
+        ("i16", _) => "Int16".to_string(),
+        ("i32", _) => "Int32".to_string(),
+        ("i64", _) => "Int64".to_string(),
+        ("u8", _) => "UInt8".to_string(),
+        ("u16", _) => "UInt16".to_string(),
+        ("u32", _) => "UInt32".to_string(),
+        ("u64", _) => "UInt64".to_string(),
+        ("bool", _) => "Boolean".to_string(),
+        _ => ident,
+    }
+}
+
+/// `u_opt_array_opt_array` becomes `uOptArrayOptArray`
+fn camel_case(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    let ident = ident.trim_start_matches("r#");
+    let mut result = String::with_capacity(ident.len());
+    let mut upper = false;
+    for c in ident.chars() {
+        if c == '_' {
+            upper = !result.is_empty();
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
@@ -0,0 +1,29 @@
+use crate::error::{ManifestError, Result};
+use crate::formats::{AnyManifest, Manifest};
+use crate::migrate::migrate_manifest;
+use crate::validate::validate_manifest;
+use serde_yaml::Value;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DeserializeOptions {
+    pub no_validate: bool,
+}
+
+/// Reads a `web3api.yaml` of any of the `MANIFEST_FORMATS`,
+/// migrated to the latest
+pub fn deserialize_manifest(manifest: &str, options: DeserializeOptions) -> Result<Manifest> {
+    let value: Value = serde_yaml::from_str(manifest)
+        .map_err(|e| ManifestError::new(format!("Unable to parse manifest: {}", e)))?;
+    if value.is_null() {
+        return Err(ManifestError::new(format!(
+            "Unable to parse manifest: {}",
+            manifest
+        )));
+    }
+
+    if !options.no_validate {
+        validate_manifest(&value)?;
+    }
+
+    migrate_manifest(AnyManifest::from_value(value)?)
+}
@@ -0,0 +1,32 @@
+use std::error;
+use std::fmt;
+
+/// A manifest that cannot be read, or is not valid for its format
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestError {
+    pub message: String,
+}
+
+impl ManifestError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl error::Error for ManifestError {}
+
+impl From<serde_yaml::Error> for ManifestError {
+    fn from(e: serde_yaml::Error) -> Self {
+        Self::new(e.to_string())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ManifestError>;
@@ -0,0 +1,55 @@
+//! The manifest's formats, one module each, named after
+//! their `format` version
+
+pub mod v0_0_1_prealpha_1;
+
+use crate::error::{ManifestError, Result};
+use serde_yaml::Value;
+
+/// The formats a manifest may have, oldest first
+pub const MANIFEST_FORMATS: &[&str] = &["0.0.1-prealpha.1"];
+
+pub const LATEST_FORMAT: &str = "0.0.1-prealpha.1";
+
+/// A manifest of the latest format
+pub type Manifest = v0_0_1_prealpha_1::Manifest;
+
+/// A manifest of any of the `MANIFEST_FORMATS`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnyManifest {
+    V0_0_1Prealpha1(v0_0_1_prealpha_1::Manifest),
+}
+
+impl AnyManifest {
+    /// Parses the manifest as the format its `format` names
+    pub fn from_value(manifest: Value) -> Result<Self> {
+        let format = format_of(&manifest)?.to_string();
+        match format.as_str() {
+            "0.0.1-prealpha.1" => Ok(Self::V0_0_1Prealpha1(serde_yaml::from_value(manifest)?)),
+            _ => Err(unrecognized_format(&format)),
+        }
+    }
+
+    pub fn format(&self) -> &str {
+        match self {
+            Self::V0_0_1Prealpha1(manifest) => &manifest.format,
+        }
+    }
+}
+
+/// The manifest's `format`, if it is a string
+pub(crate) fn format_of(manifest: &Value) -> Result<&str> {
+    match manifest.get("format") {
+        Some(Value::String(format)) => Ok(format),
+        Some(_) => Err(ManifestError::new(
+            "Property format has a type error: is not of a type(s) string",
+        )),
+        None => Err(ManifestError::new(
+            "Missing field: format. Please add it to the manifest",
+        )),
+    }
+}
+
+pub(crate) fn unrecognized_format(format: &str) -> ManifestError {
+    ManifestError::new(format!("Unrecognized manifest format \"{}\"", format))
+}
@@ -0,0 +1,47 @@
+//! The `0.0.1-prealpha.1` format, as described by
+//! `@web3api/manifest-schema/formats/0.0.1-prealpha.1.json`
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mutation: Option<Module>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<Module>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_redirects: Option<Vec<ImportRedirect>>,
+}
+
+/// The query or mutation module of the API
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Module {
+    pub schema: SchemaFile,
+    pub module: ModuleFile,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaFile {
+    /// A relative path, e.g. `./src/query/schema.graphql`
+    pub file: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleFile {
+    /// e.g. `wasm/assemblyscript`
+    pub language: String,
+    /// A relative path, e.g. `./src/query/index.ts`
+    pub file: String,
+}
+
+/// Resolves an import's URI to a local schema instead
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRedirect {
+    pub uri: String,
+    pub schema: String,
+}
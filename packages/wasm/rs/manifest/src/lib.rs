@@ -0,0 +1,16 @@
+//! Reads Web3API manifests, `web3api.yaml`, as `@web3api/core-js`
+//! does: validated against the JSON schema of their format, from
+//! `@web3api/manifest-schema`, then parsed into that format's types
+//! and migrated to the latest format
+
+mod deserialize;
+mod error;
+pub mod formats;
+mod migrate;
+mod validate;
+
+pub use deserialize::{deserialize_manifest, DeserializeOptions};
+pub use error::{ManifestError, Result};
+pub use formats::{AnyManifest, Manifest, LATEST_FORMAT, MANIFEST_FORMATS};
+pub use migrate::{migrate_manifest, migrate_with, Formatted, Migrator};
+pub use validate::{validate_manifest, validate_manifest_with};
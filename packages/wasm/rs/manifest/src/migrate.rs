@@ -0,0 +1,70 @@
+//! Upgrades manifests of older formats to the latest, a format at a
+//! time, as `@web3api/core-js` does
+
+use crate::error::{ManifestError, Result};
+use crate::formats::{AnyManifest, Manifest, LATEST_FORMAT};
+
+/// A manifest of some format, as migrators see it
+pub trait Formatted {
+    /// The manifest's `format`, e.g. `0.0.1-prealpha.1`
+    fn format(&self) -> &str;
+}
+
+impl Formatted for AnyManifest {
+    fn format(&self) -> &str {
+        AnyManifest::format(self)
+    }
+}
+
+/// Upgrades a manifest of one format to the format after it
+pub type Migrator<M = AnyManifest> = fn(M) -> Result<M>;
+
+/// The migrator of each format but the latest, by the format it
+/// upgrades from. A format added to `MANIFEST_FORMATS` registers
+/// the migrator from the previous latest here, e.g.
+/// `("0.0.1-prealpha.1", migrators::v0_0_1_prealpha_1_to_v0_0_1_prealpha_2)`
+const MIGRATORS: &[(&str, Migrator)] = &[];
+
+/// Migrates a manifest of any format to the latest
+pub fn migrate_manifest(manifest: AnyManifest) -> Result<Manifest> {
+    match migrate_with(manifest, LATEST_FORMAT, MIGRATORS)? {
+        AnyManifest::V0_0_1Prealpha1(manifest) => Ok(manifest),
+    }
+}
+
+/// Migrates a manifest to the format `to`, chaining `migrators`,
+/// each given with the format it upgrades from
+pub fn migrate_with<M: Formatted>(
+    manifest: M,
+    to: &str,
+    migrators: &[(&str, Migrator<M>)],
+) -> Result<M> {
+    let mut manifest = manifest;
+    let mut migrated = vec![];
+    while manifest.format() != to {
+        let from = manifest.format().to_string();
+        let migrator = migrators
+            .iter()
+            .find(|(format, _)| *format == from)
+            .map(|(_, migrator)| migrator)
+            .ok_or_else(|| {
+                ManifestError::new(format!(
+                    "Format to update {} is not available in migrator of format {}",
+                    to, from
+                ))
+            })?;
+        manifest = migrator(manifest)?;
+
+        // A migrator going back to a format already migrated
+        // from, e.g. its own, would never reach `to`
+        let returned = manifest.format();
+        if returned == from || migrated.iter().any(|format| format == returned) {
+            return Err(ManifestError::new(format!(
+                "The migrator of format {} returned format {}, which it does not upgrade",
+                from, returned
+            )));
+        }
+        migrated.push(from);
+    }
+    Ok(manifest)
+}
@@ -0,0 +1,254 @@
+//! Validates manifests against the JSON schema of their format,
+//! with the errors `@web3api/core-js` gives. Only the keywords
+//! the schemas use are supported: `type`, `properties`, `required`,
+//! `additionalProperties`, `items` and the `format`s below; schemas
+//! using any other are rejected rather than partly applied.
+
+use crate::error::{ManifestError, Result};
+use crate::formats::{format_of, MANIFEST_FORMATS};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_yaml::Value;
+
+/// The JSON schema of the format, if it is one of `MANIFEST_FORMATS`.
+/// `schemas` holds copies of `@web3api/manifest-schema`'s formats.
+fn manifest_schema(format: &str) -> Option<Value> {
+    let schema = match format {
+        "0.0.1-prealpha.1" => include_str!("../schemas/0.0.1-prealpha.1.json"),
+        _ => return None,
+    };
+    // JSON is YAML, whose mappings keep the schema's order
+    Some(serde_yaml::from_str(schema).expect("manifest schemas are valid JSON"))
+}
+
+pub fn validate_manifest(manifest: &Value) -> Result<()> {
+    let format = format_of(manifest)?;
+    let schema = manifest_schema(format).ok_or_else(|| {
+        ManifestError::new(format!(
+            "Unrecognized manifest schema format \"{}\"",
+            format
+        ))
+    })?;
+    validate_manifest_with(manifest, &schema)
+}
+
+/// Validates a manifest against the given JSON schema of its format
+pub fn validate_manifest_with(manifest: &Value, schema: &Value) -> Result<()> {
+    check_schema(schema, &mut vec![])?;
+
+    let mut validator = Validator {
+        format: format_of(manifest)?,
+        path: vec![],
+    };
+    validator.validate(manifest, schema)
+}
+
+const TYPES: &[&str] = &[
+    "object", "array", "string", "number", "integer", "boolean", "null",
+];
+
+const FORMATS: &[&str] = &["file", "manifestFormat"];
+
+/// Annotations, which do not take part in validation
+const ANNOTATIONS: &[&str] = &[
+    "id",
+    "$id",
+    "$schema",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+];
+
+/// Fails on the first keyword of the schema, or of its
+/// subschemas, that `Validator` does not implement
+fn check_schema(schema: &Value, path: &mut Vec<String>) -> Result<()> {
+    let unsupported = |path: &[String], keyword: &str| {
+        let location = match path.is_empty() {
+            true => "the root".to_string(),
+            false => path.join(" -> "),
+        };
+        Err(ManifestError::new(format!(
+            "The manifest schema uses {} at {}, which is not supported",
+            keyword, location
+        )))
+    };
+
+    let schema = match schema {
+        Value::Mapping(schema) => schema,
+        _ => return unsupported(path, "a schema that is not an object"),
+    };
+    for (key, value) in schema.iter() {
+        let keyword = key_name(key);
+        let supported = match (keyword.as_str(), value) {
+            (keyword, _) if ANNOTATIONS.contains(&keyword) => true,
+            ("type", Value::String(r#type)) => TYPES.contains(&r#type.as_str()),
+            ("format", Value::String(format)) => FORMATS.contains(&format.as_str()),
+            ("additionalProperties", Value::Bool(_)) => true,
+            ("required", Value::Sequence(names)) => names.iter().all(Value::is_string),
+            ("properties", Value::Mapping(properties)) => {
+                for (name, property) in properties.iter() {
+                    path.push(key_name(name));
+                    check_schema(property, path)?;
+                    path.pop();
+                }
+                true
+            }
+            ("items", Value::Sequence(items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    path.push(i.to_string());
+                    check_schema(item, path)?;
+                    path.pop();
+                }
+                true
+            }
+            ("items", item @ Value::Mapping(_)) => {
+                path.push("items".to_string());
+                check_schema(item, path)?;
+                path.pop();
+                true
+            }
+            _ => false,
+        };
+        if !supported {
+            let keyword = match value {
+                Value::Mapping(_) | Value::Sequence(_) => format!("`{}`", keyword),
+                value => format!("`{}: {}`", keyword, key_name(value)),
+            };
+            return unsupported(path, &keyword);
+        }
+    }
+    Ok(())
+}
+
+struct Validator<'m> {
+    /// The manifest's format
+    format: &'m str,
+    /// The properties and indices leading to the value validated
+    path: Vec<String>,
+}
+
+impl Validator<'_> {
+    fn validate(&mut self, instance: &Value, schema: &Value) -> Result<()> {
+        if let Some(r#type) = schema.get("type").and_then(Value::as_str) {
+            if !is_of_type(instance, r#type) {
+                return Err(ManifestError::new(format!(
+                    "Property {} has a type error: is not of a type(s) {}",
+                    self.path.join(" -> "),
+                    r#type
+                )));
+            }
+        }
+
+        if let Value::Mapping(object) = instance {
+            let properties = schema.get("properties").and_then(Value::as_mapping);
+
+            if schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+                let accepted = |key: &Value| properties.is_some_and(|p| p.contains_key(key));
+                if let Some(key) = object.iter().map(|(key, _)| key).find(|key| !accepted(key)) {
+                    return Err(ManifestError::new(format!(
+                        "Field {} is not accepted in the schema of format {}",
+                        key_name(key),
+                        self.format
+                    )));
+                }
+            }
+
+            for (name, property_schema) in properties.into_iter().flatten() {
+                if let Some(property) = object.get(name) {
+                    self.path.push(key_name(name));
+                    self.validate(property, property_schema)?;
+                    self.path.pop();
+                }
+            }
+
+            let required = schema.get("required").and_then(Value::as_sequence);
+            for name in required.into_iter().flatten() {
+                if !object.contains_key(name) {
+                    let field = match self.path.is_empty() {
+                        true => key_name(name),
+                        false => format!("{} in {}", key_name(name), self.path.join(" -> ")),
+                    };
+                    return Err(ManifestError::new(format!(
+                        "Missing field: {}. Please add it to the manifest",
+                        field
+                    )));
+                }
+            }
+        }
+
+        if let Value::Sequence(items) = instance {
+            // An array of schemas validates the items at the same index only
+            let item_schemas: Box<dyn Iterator<Item = &Value>> = match schema.get("items") {
+                Some(Value::Sequence(schemas)) => Box::new(schemas.iter()),
+                Some(item_schema) => Box::new(std::iter::repeat(item_schema)),
+                None => Box::new(std::iter::empty()),
+            };
+            for (i, (item, item_schema)) in items.iter().zip(item_schemas).enumerate() {
+                self.path.push(i.to_string());
+                self.validate(item, item_schema)?;
+                self.path.pop();
+            }
+        }
+
+        if let (Some(format), Value::String(instance)) =
+            (schema.get("format").and_then(Value::as_str), instance)
+        {
+            self.validate_format(instance, format)?;
+        }
+        Ok(())
+    }
+
+    fn validate_format(&self, instance: &str, format: &str) -> Result<()> {
+        match format {
+            "manifestFormat" if !MANIFEST_FORMATS.contains(&instance) => {
+                Err(ManifestError::new(format!(
+                    "The manifest's format is not correct. Given: {}\nAccepted formats: {}",
+                    instance,
+                    MANIFEST_FORMATS.join(",")
+                )))
+            }
+            "file" if !is_file_path(instance) => Err(ManifestError::new(format!(
+                "Property {} has the value \"{}\", which is not a valid file path. \
+                 Please use unix style relative paths.",
+                self.path.join(" -> "),
+                instance
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn is_of_type(instance: &Value, r#type: &str) -> bool {
+    match r#type {
+        "object" => instance.is_mapping(),
+        "array" => instance.is_sequence(),
+        "string" => instance.is_string(),
+        "number" => instance.is_number(),
+        "integer" => instance.is_i64() || instance.is_u64(),
+        "boolean" => instance.is_bool(),
+        "null" => instance.is_null(),
+        _ => unreachable!("checked by check_schema"),
+    }
+}
+
+fn key_name(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        key => serde_yaml::to_string(key)
+            .map(|key| key.trim_start_matches("---").trim().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// The pattern of `@web3api/core-js`: segments starting with `./`, or
+/// with two characters and a `/`. As in JavaScript, `.` is any
+/// character but a line terminator.
+static FILE_PATH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^((\./|[^\n\r\u{2028}\u{2029}]{2}/)[^/ ]*)+/?$").expect("valid pattern")
+});
+
+fn is_file_path(path: &str) -> bool {
+    FILE_PATH.is_match(path)
+}
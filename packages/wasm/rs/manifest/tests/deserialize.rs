@@ -0,0 +1,155 @@
+//! Ports `@web3api/core-js`'s manifest validation specs, reading
+//! the same `__tests__/manifest/validator` fixtures
+
+use std::fs;
+use std::path::Path;
+use web3api_manifest::{
+    deserialize_manifest, DeserializeOptions, Manifest, ManifestError, LATEST_FORMAT,
+};
+
+fn read_manifest(path: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../..")
+        .join(path)
+        .join("web3api.yaml");
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+fn validator_error(case: &str) -> ManifestError {
+    let manifest = read_manifest(&format!(
+        "js/core/src/__tests__/manifest/validator/{}",
+        case
+    ));
+    deserialize_manifest(&manifest, DeserializeOptions::default()).unwrap_err()
+}
+
+#[test]
+fn deserializes_a_valid_manifest() {
+    let manifest = read_manifest("test-cases/cases/apis/simple-storage");
+    let manifest: Manifest =
+        deserialize_manifest(&manifest, DeserializeOptions::default()).unwrap();
+
+    assert_eq!(manifest.format, LATEST_FORMAT);
+    let query = manifest.query.unwrap();
+    assert_eq!(query.schema.file, "./query/schema.graphql");
+    assert_eq!(query.module.language, "wasm/assemblyscript");
+    assert_eq!(query.module.file, "./query/index.ts");
+    assert_eq!(
+        manifest.mutation.unwrap().module.file,
+        "./mutation/index.ts"
+    );
+    assert_eq!(manifest.import_redirects, None);
+}
+
+#[test]
+fn rejects_malformed_file_paths() {
+    assert_eq!(
+        validator_error("file-string-malformed").message,
+        "Property mutation -> schema -> file has the value \".9-../schema.graphql\", \
+         which is not a valid file path. Please use unix style relative paths."
+    );
+}
+
+#[test]
+fn accepts_unix_style_relative_paths() {
+    let manifest_with = |file: &str| {
+        format!(
+            "format: 0.0.1-prealpha.1\nquery:\n  schema:\n    file: \"{}\"\n  \
+             module:\n    language: wasm/assemblyscript\n    file: ./index.ts\n",
+            file
+        )
+    };
+    let options = DeserializeOptions::default();
+
+    for file in &[
+        "./schema.graphql",
+        "../../src/",
+        "./a/./b.graphql",
+        "ab/schema",
+    ] {
+        let manifest = deserialize_manifest(&manifest_with(file), options);
+        assert!(manifest.is_ok(), "{}: {:?}", file, manifest);
+    }
+    for file in &["schema.graphql", "/abs/schema.graphql", "./a b", ""] {
+        let error = deserialize_manifest(&manifest_with(file), options).unwrap_err();
+        assert!(
+            error.message.contains("is not a valid file path"),
+            "{}: {}",
+            file,
+            error
+        );
+    }
+}
+
+#[test]
+fn rejects_unrecognized_formats() {
+    assert_eq!(
+        validator_error("incorrect-version-format").message,
+        "Unrecognized manifest schema format \"BAD_FORMAT_VERSION\""
+    );
+}
+
+#[test]
+fn rejects_fields_not_in_the_schema() {
+    assert_eq!(
+        validator_error("not-accepted-field").message,
+        "Field not_accepted_field is not accepted in the schema of format 0.0.1-prealpha.1"
+    );
+}
+
+#[test]
+fn rejects_missing_required_fields() {
+    assert_eq!(
+        validator_error("required-field-missing").message,
+        "Missing field: file in mutation -> module. Please add it to the manifest"
+    );
+
+    let error =
+        deserialize_manifest("description: no format", DeserializeOptions::default()).unwrap_err();
+    assert_eq!(
+        error.message,
+        "Missing field: format. Please add it to the manifest"
+    );
+}
+
+#[test]
+fn rejects_wrong_types() {
+    assert_eq!(
+        validator_error("wrong-type").message,
+        "Property mutation -> schema -> file has a type error: is not of a type(s) string"
+    );
+}
+
+#[test]
+fn validates_import_redirects() {
+    let manifest = "\
+format: 0.0.1-prealpha.1
+import_redirects:
+  - uri: w3://ens/api.eth
+";
+    let error = deserialize_manifest(manifest, DeserializeOptions::default()).unwrap_err();
+    assert_eq!(
+        error.message,
+        "Missing field: schema in import_redirects -> 0. Please add it to the manifest"
+    );
+}
+
+#[test]
+fn skips_validation_when_asked() {
+    let manifest = read_manifest("js/core/src/__tests__/manifest/validator/file-string-malformed");
+    let options = DeserializeOptions { no_validate: true };
+    let manifest = deserialize_manifest(&manifest, options).unwrap();
+    assert_eq!(
+        manifest.mutation.unwrap().schema.file,
+        ".9-../schema.graphql"
+    );
+}
+
+#[test]
+fn rejects_empty_manifests() {
+    let error = deserialize_manifest("~", DeserializeOptions::default()).unwrap_err();
+    assert_eq!(error.message, "Unable to parse manifest: ~");
+
+    let error = deserialize_manifest("", DeserializeOptions::default()).unwrap_err();
+    assert!(error.message.starts_with("Unable to parse manifest: "));
+}
@@ -0,0 +1,151 @@
+//! Ports `@web3api/core-js`'s manifest migration specs, reading
+//! the same `__tests__/manifest/migrator` fixtures
+
+use std::fs;
+use std::path::Path;
+use web3api_manifest::formats::v0_0_1_prealpha_1::{Module, ModuleFile, SchemaFile};
+use web3api_manifest::{
+    deserialize_manifest, migrate_manifest, migrate_with, AnyManifest, DeserializeOptions,
+    Formatted, Manifest, Migrator, LATEST_FORMAT,
+};
+
+fn read_migrator_case(case: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../../js/core/src/__tests__/manifest/migrator")
+        .join(case)
+        .join("web3api.yaml");
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+fn any_manifest(yaml: &str) -> web3api_manifest::Result<AnyManifest> {
+    AnyManifest::from_value(serde_yaml::from_str(yaml).unwrap())
+}
+
+fn module(kind: &str) -> Module {
+    Module {
+        schema: SchemaFile {
+            file: format!("../{}/schema.graphql", kind),
+        },
+        module: ModuleFile {
+            language: "wasm/assemblyscript".to_string(),
+            file: format!("../{}/index.ts", kind),
+        },
+    }
+}
+
+#[test]
+fn keeps_manifests_of_the_latest_format() {
+    let manifest = any_manifest(&read_migrator_case("format-0.0.1-alpha.1")).unwrap();
+    assert_eq!(manifest.format(), LATEST_FORMAT);
+
+    let expected = Manifest {
+        format: LATEST_FORMAT.to_string(),
+        description: Some("Awesome Web3API project".to_string()),
+        repository: None,
+        mutation: Some(module("mutation")),
+        query: Some(module("query")),
+        import_redirects: None,
+    };
+    assert_eq!(migrate_manifest(manifest).unwrap(), expected);
+}
+
+#[test]
+fn rejects_unrecognized_formats() {
+    let manifest = read_migrator_case("unrecognized-format");
+    assert_eq!(
+        any_manifest(&manifest).unwrap_err().message,
+        "Unrecognized manifest format \"0.0.1-prealpha.0\""
+    );
+
+    let options = DeserializeOptions { no_validate: true };
+    assert_eq!(
+        deserialize_manifest(&manifest, options)
+            .unwrap_err()
+            .message,
+        "Unrecognized manifest format \"0.0.1-prealpha.0\""
+    );
+}
+
+/// A manifest of made-up formats `a`, `b` and `c`, the latest
+#[derive(Debug, PartialEq)]
+struct FakeManifest {
+    format: String,
+    /// The formats migrated from, in order
+    history: Vec<String>,
+}
+
+impl FakeManifest {
+    fn new(format: &str) -> Self {
+        Self {
+            format: format.to_string(),
+            history: vec![],
+        }
+    }
+
+    fn upgrade(mut self, to: &str) -> Self {
+        self.history.push(self.format);
+        self.format = to.to_string();
+        self
+    }
+}
+
+impl Formatted for FakeManifest {
+    fn format(&self) -> &str {
+        &self.format
+    }
+}
+
+fn a_to_b(manifest: FakeManifest) -> web3api_manifest::Result<FakeManifest> {
+    Ok(manifest.upgrade("b"))
+}
+
+fn b_to_c(manifest: FakeManifest) -> web3api_manifest::Result<FakeManifest> {
+    Ok(manifest.upgrade("c"))
+}
+
+const MIGRATORS: &[(&str, Migrator<FakeManifest>)] = &[("b", b_to_c), ("a", a_to_b)];
+
+#[test]
+fn chains_migrators_to_the_latest_format() {
+    let migrated = migrate_with(FakeManifest::new("a"), "c", MIGRATORS).unwrap();
+    assert_eq!(migrated.format, "c");
+    assert_eq!(migrated.history, ["a", "b"]);
+
+    let migrated = migrate_with(FakeManifest::new("b"), "c", MIGRATORS).unwrap();
+    assert_eq!(migrated.history, ["b"]);
+
+    let latest = migrate_with(FakeManifest::new("c"), "c", MIGRATORS).unwrap();
+    assert_eq!(latest, FakeManifest::new("c"));
+}
+
+#[test]
+fn rejects_formats_without_a_migrator() {
+    let error = migrate_with(FakeManifest::new("a"), "c", &[("a", a_to_b)]).unwrap_err();
+    assert_eq!(
+        error.message,
+        "Format to update c is not available in migrator of format b"
+    );
+}
+
+#[test]
+fn rejects_migrators_that_do_not_upgrade() {
+    fn b_to_b(manifest: FakeManifest) -> web3api_manifest::Result<FakeManifest> {
+        Ok(manifest.upgrade("b"))
+    }
+    fn b_to_a(manifest: FakeManifest) -> web3api_manifest::Result<FakeManifest> {
+        Ok(manifest.upgrade("a"))
+    }
+
+    let error = migrate_with(FakeManifest::new("b"), "c", &[("b", b_to_b)]).unwrap_err();
+    assert_eq!(
+        error.message,
+        "The migrator of format b returned format b, which it does not upgrade"
+    );
+
+    let migrators: &[(&str, Migrator<FakeManifest>)] = &[("a", a_to_b), ("b", b_to_a)];
+    let error = migrate_with(FakeManifest::new("a"), "c", migrators).unwrap_err();
+    assert_eq!(
+        error.message,
+        "The migrator of format b returned format a, which it does not upgrade"
+    );
+}
@@ -0,0 +1,67 @@
+//! The JSON schemas bundled in `schemas`, which must stay copies
+//! of `@web3api/manifest-schema`'s formats
+
+use std::fs;
+use std::path::Path;
+use web3api_manifest::{validate_manifest_with, MANIFEST_FORMATS};
+
+fn schema_of(dir: &Path, format: &str) -> String {
+    let path = dir.join(format!("{}.json", format));
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+#[test]
+fn bundles_the_manifest_schema_formats() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let formats_dir = crate_dir.join("../../../manifest-schema/formats");
+    if !formats_dir.exists() {
+        // Outside the monorepo, e.g. once packaged
+        return;
+    }
+
+    let mut formats: Vec<String> = fs::read_dir(&formats_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+    formats.sort();
+    assert_eq!(formats, MANIFEST_FORMATS);
+
+    for format in MANIFEST_FORMATS {
+        assert_eq!(
+            schema_of(&crate_dir.join("schemas"), format),
+            schema_of(&formats_dir, format),
+            "schemas/{}.json differs from @web3api/manifest-schema's, copy it over",
+            format
+        );
+    }
+}
+
+#[test]
+fn rejects_schemas_with_unsupported_keywords() {
+    let manifest = serde_yaml::from_str("format: 0.0.2\nlanguage: rust").unwrap();
+    let schema = serde_yaml::from_str(
+        r#"{
+            "type": "object",
+            "properties": {
+                "format": { "type": "string" },
+                "language": { "type": "string", "enum": ["rust", "assemblyscript"] }
+            }
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        validate_manifest_with(&manifest, &schema)
+            .unwrap_err()
+            .message,
+        "The manifest schema uses `enum` at language, which is not supported"
+    );
+
+    let schema = serde_yaml::from_str(r#"{ "type": "map" }"#).unwrap();
+    assert_eq!(
+        validate_manifest_with(&manifest, &schema)
+            .unwrap_err()
+            .message,
+        "The manifest schema uses `type: map` at the root, which is not supported"
+    );
+}
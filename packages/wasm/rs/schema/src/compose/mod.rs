@@ -0,0 +1,88 @@
+//! Composes a module's schemas with the types they import, as
+//! `@web3api/schema-compose` does. External imports are namespaced
+//! and marked `@imported`; local imports are copied as they are.
+//!
+//! ```graphql
+//! #import { Query, CustomType } into Namespace from "test.eth"
+//! #import { CommonType } from "../common/schema.graphql"
+//! ```
+
+mod render;
+mod resolve;
+mod resolvers;
+mod statements;
+
+pub use resolvers::{FileSystemResolver, InMemoryResolver, SchemaResolver, SchemaResolvers};
+
+use crate::error::SchemaErrors;
+use crate::type_info::{combine_type_info, TypeInfo};
+use std::path::PathBuf;
+
+pub const SYNTAX_REFERENCE: &str =
+    "External Import: import { Type, Query } into Namespace from \"external.uri\"\n\
+     Local Import: import { Type } from \"./local/path/file.graphql\"";
+
+/// A schema, and the path local imports are relative to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaFile {
+    pub schema: String,
+    pub absolute_path: PathBuf,
+}
+
+pub struct ComposerOptions<'r> {
+    pub query: Option<SchemaFile>,
+    pub mutation: Option<SchemaFile>,
+    pub resolvers: SchemaResolvers<'r>,
+}
+
+/// A composed schema, rendered with the Web3API header
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchemaInfo {
+    pub schema: String,
+    pub type_info: TypeInfo,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ComposerOutput {
+    pub query: Option<SchemaInfo>,
+    pub mutation: Option<SchemaInfo>,
+    /// Both modules' types, or those of the only one given
+    pub combined: Option<SchemaInfo>,
+}
+
+pub fn compose_schema(options: &ComposerOptions) -> Result<ComposerOutput, SchemaErrors> {
+    let compose = |file: &Option<SchemaFile>, mutation| {
+        file.as_ref()
+            .filter(|file| !file.schema.is_empty())
+            .map(|file| {
+                resolve::resolve_imports_and_parse_schema(
+                    &file.schema,
+                    &file.absolute_path,
+                    mutation,
+                    &options.resolvers,
+                )
+            })
+            .transpose()
+    };
+    let query = compose(&options.query, false)?;
+    let mutation = compose(&options.mutation, true)?;
+
+    let schema_info = |type_info: TypeInfo| SchemaInfo {
+        schema: render::render_schema(&type_info, true),
+        type_info,
+    };
+    let combined = match (&query, &mutation) {
+        (Some(query), Some(mutation)) => Some(schema_info(combine_type_info(&[
+            query.clone(),
+            mutation.clone(),
+        ])?)),
+        (Some(type_info), None) | (None, Some(type_info)) => Some(schema_info(type_info.clone())),
+        (None, None) => None,
+    };
+
+    Ok(ComposerOutput {
+        query: query.map(schema_info),
+        mutation: mutation.map(schema_info),
+        combined,
+    })
+}
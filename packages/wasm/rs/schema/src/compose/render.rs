@@ -0,0 +1,179 @@
+//! Renders a `TypeInfo` as a schema, as the `schema.mustache`
+//! template of `@web3api/schema-compose` does
+
+use crate::header::HEADER;
+use crate::type_info::{
+    ArrayDefinition, ArrayItem, MethodDefinition, PropertyDefinition, TypeInfo,
+};
+use std::fmt::Write;
+
+pub(super) fn render_schema(type_info: &TypeInfo, header: bool) -> String {
+    let mut schema = String::from("\n");
+
+    for query in &type_info.query_types {
+        write!(schema, "type {} ", query.r#type).unwrap();
+        if !query.imports.is_empty() {
+            let imports: Vec<_> = query
+                .imports
+                .iter()
+                .map(|import| format!("    \"{}\"", import.r#type))
+                .collect();
+            write!(
+                schema,
+                "@imports(\n  types: [\n{}\n  ]\n) ",
+                imports.join(",\n")
+            )
+            .unwrap();
+        }
+        schema += "{\n";
+        methods(&mut schema, &query.methods, false);
+        schema += "}\n\n";
+    }
+
+    for object in &type_info.object_types {
+        writeln!(schema, "type {} {{", object.r#type).unwrap();
+        properties(&mut schema, &object.properties);
+        schema += "}\n\n";
+    }
+
+    for r#enum in &type_info.enum_types {
+        writeln!(schema, "enum {} {{", r#enum.r#type).unwrap();
+        constants(&mut schema, &r#enum.constants);
+        schema += "}\n\n";
+    }
+
+    schema += "### Imported Queries START ###\n\n";
+    for query in &type_info.imported_query_types {
+        write!(
+            schema,
+            "type {} @imported(\n  uri: \"{}\",\n  namespace: \"{}\",\n  nativeType: \"{}\"\n) {{\n",
+            query.r#type, query.uri, query.namespace, query.native_type
+        )
+        .unwrap();
+        methods(&mut schema, &query.methods, true);
+        schema += "}\n\n";
+    }
+    schema += "### Imported Queries END ###\n\n";
+
+    schema += "### Imported Objects START ###\n\n";
+    for object in &type_info.imported_object_types {
+        write!(
+            schema,
+            "type {} @imported(\n  uri: \"{}\",\n  namespace: \"{}\",\n  nativeType: \"{}\"\n) {{\n",
+            object.r#type, object.uri, object.namespace, object.native_type
+        )
+        .unwrap();
+        properties(&mut schema, &object.properties);
+        schema += "}\n\n";
+    }
+    for r#enum in &type_info.imported_enum_types {
+        write!(
+            schema,
+            "enum {} @imported(\n  namespace: \"{}\",\n  uri: \"{}\",\n  nativeType: \"{}\"\n) {{\n",
+            r#enum.r#type, r#enum.namespace, r#enum.uri, r#enum.native_type
+        )
+        .unwrap();
+        constants(&mut schema, &r#enum.constants);
+        schema += "}\n\n";
+    }
+    schema += "### Imported Objects END ###";
+
+    if header {
+        schema = format!("{}\n{}\n", HEADER, schema);
+    }
+    collapse_blank_lines(&schema)
+}
+
+/// Imported query types' methods have parentheses even without arguments
+fn methods(schema: &mut String, methods: &[MethodDefinition], imported: bool) {
+    for (i, method) in methods.iter().enumerate() {
+        if i > 0 {
+            *schema += "\n";
+        }
+        write!(schema, "  {}", method.name.as_deref().unwrap_or_default()).unwrap();
+        if imported || !method.arguments.is_empty() {
+            *schema += "(\n";
+            for argument in &method.arguments {
+                writeln!(
+                    schema,
+                    "    {}: {}",
+                    argument.name.as_deref().unwrap_or_default(),
+                    graphql_type(argument)
+                )
+                .unwrap();
+            }
+            *schema += "  )";
+        }
+        writeln!(schema, ": {}", graphql_type(&method.r#return)).unwrap();
+    }
+}
+
+fn properties(schema: &mut String, properties: &[PropertyDefinition]) {
+    for property in properties {
+        writeln!(
+            schema,
+            "  {}: {}",
+            property.name.as_deref().unwrap_or_default(),
+            graphql_type(property)
+        )
+        .unwrap();
+    }
+}
+
+fn constants(schema: &mut String, constants: &[String]) {
+    for constant in constants {
+        writeln!(schema, "  {}", constant).unwrap();
+    }
+}
+
+fn required(r#type: &str, required: bool) -> String {
+    if required {
+        format!("{}!", r#type)
+    } else {
+        r#type.to_string()
+    }
+}
+
+/// The property's type as written in a schema, e.g. `[String!]`
+fn graphql_type(property: &PropertyDefinition) -> String {
+    if let Some(object) = &property.object {
+        required(&object.r#type, object.required)
+    } else if let Some(array) = &property.array {
+        array_type(array)
+    } else if let Some(scalar) = &property.scalar {
+        required(&scalar.r#type, scalar.required)
+    } else if let Some(r#enum) = &property.r#enum {
+        required(&r#enum.r#type, r#enum.required)
+    } else {
+        required(&property.r#type, property.required)
+    }
+}
+
+fn array_type(array: &ArrayDefinition) -> String {
+    let item = match array.item() {
+        Some(ArrayItem::Array(item)) => array_type(item),
+        Some(ArrayItem::Scalar(item)) => required(&item.r#type, item.required),
+        Some(ArrayItem::Object(item)) => required(&item.r#type, item.required),
+        Some(ArrayItem::Enum(item)) => required(&item.r#type, item.required),
+        None => String::new(),
+    };
+    required(&format!("[{}]", item), array.required)
+}
+
+/// Blank lines are left by the template's empty sections
+fn collapse_blank_lines(schema: &str) -> String {
+    let mut collapsed = String::with_capacity(schema.len());
+    let mut newlines = 0;
+    for c in schema.chars() {
+        if c == '\n' {
+            newlines += 1;
+            if newlines > 2 {
+                continue;
+            }
+        } else {
+            newlines = 0;
+        }
+        collapsed.push(c);
+    }
+    collapsed
+}
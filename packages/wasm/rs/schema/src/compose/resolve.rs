@@ -0,0 +1,600 @@
+//! Resolves a schema's imports into a `TypeInfo`, renders it after
+//! the schema, and parses the whole, as `@web3api/schema-compose` does
+
+use super::render::render_schema;
+use super::resolvers::{SchemaResolver, SchemaResolvers};
+use super::statements::{self, ExternalImport, LocalImport};
+use super::SYNTAX_REFERENCE;
+use crate::error::{Result, SchemaError, SchemaErrors};
+use crate::header::HEADER;
+use crate::parse_schema;
+use crate::type_info::{
+    ArrayDefinition, EnumDefinition, ImportedDefinition, ImportedEnumDefinition,
+    ImportedObjectDefinition, ImportedQueryDefinition, MethodDefinition, ObjectDefinition,
+    PropertyDefinition, TypeInfo,
+};
+use std::path::Path;
+
+pub(super) fn resolve_imports_and_parse_schema(
+    schema: &str,
+    schema_path: &Path,
+    mutation: bool,
+    resolvers: &SchemaResolvers,
+) -> std::result::Result<TypeInfo, SchemaErrors> {
+    let (statements, keywords) = statements::find_statements(schema);
+    if keywords != statements.len() {
+        return Err(SchemaError::new(
+            format!(
+                "Invalid import statement found in file {}.\n\
+                 Please use one of the following syntaxes...\n{}",
+                schema_path.display(),
+                SYNTAX_REFERENCE
+            ),
+            None,
+        )
+        .into());
+    }
+
+    let (external, local): (Vec<_>, Vec<_>) = statements
+        .iter()
+        .partition(|statement| statement.namespace.is_some());
+    let external = statements::parse_external_imports(&external, mutation)?;
+    let local = statements::parse_local_imports(&local, schema_path)?;
+
+    let mut sub_type_info = TypeInfo::default();
+    let imported_types =
+        resolve_external_imports(&external, resolvers.external, &mut sub_type_info)?;
+    resolve_local_imports(&local, resolvers.local, &mut sub_type_info)?;
+
+    // Remove all import statements
+    let mut new_schema = String::with_capacity(schema.len());
+    let mut end = 0;
+    for statement in &statements {
+        new_schema += &schema[end..statement.range.start];
+        end = statement.range.end;
+    }
+    new_schema += &schema[end..];
+
+    let new_schema = add_query_imports_directive(&new_schema, &imported_types, mutation);
+
+    // Parse the newly formed schema, along with the imported types
+    parse_schema(&format!(
+        "{}{}{}",
+        HEADER,
+        new_schema,
+        render_schema(&sub_type_info, false)
+    ))
+}
+
+fn resolve(resolver: &dyn SchemaResolver, uri_or_path: &str) -> Result<String> {
+    match resolver.resolve(uri_or_path) {
+        Ok(schema) if !schema.is_empty() => Ok(schema),
+        Ok(_) => Err(SchemaError::new(
+            format!("Unable to resolve schema at \"{}\"", uri_or_path),
+            None,
+        )),
+        Err(e) => Err(SchemaError::new(
+            format!("Unable to resolve schema at \"{}\": {}", uri_or_path, e),
+            None,
+        )),
+    }
+}
+
+fn append_namespace(namespace: &str, r#type: &str) -> String {
+    format!("{}_{}", namespace, r#type)
+}
+
+/// Gives the `@imports` directive to the module's query type
+fn add_query_imports_directive(schema: &str, imported_types: &[String], mutation: bool) -> String {
+    if imported_types.is_empty() {
+        return schema.to_string();
+    }
+
+    let query_type = if mutation { "Mutation" } else { "Query" };
+    let imported_types: Vec<_> = imported_types
+        .iter()
+        .map(|r#type| format!("\"{}\"", r#type))
+        .collect();
+    let replacement = format!(
+        "type {} @imports(\n  types: [\n    {}\n  ]\n) {{",
+        query_type,
+        imported_types.join(",\n    ")
+    );
+
+    let mut result = String::with_capacity(schema.len() + replacement.len());
+    let mut rest = schema;
+    while let Some(start) = rest.find("type") {
+        let whitespace = |s: &str| s.trim_start_matches(|c| " \n\t".contains(c)).len();
+        let after_type = &rest[start + "type".len()..];
+        let name = &after_type[after_type.len() - whitespace(after_type)..];
+        let opening = name
+            .strip_prefix(query_type)
+            .map(|after_name| &after_name[after_name.len() - whitespace(after_name)..])
+            .filter(|brace| brace.starts_with('{'));
+
+        match opening {
+            Some(brace) => {
+                result += &rest[..start];
+                result += &replacement;
+                rest = &brace[1..];
+            }
+            None => {
+                result += &rest[..start + "type".len()];
+                rest = after_type;
+            }
+        }
+    }
+    result + rest
+}
+
+/// An imported type, namespaced
+enum Import {
+    Object(ImportedObjectDefinition),
+    Query(ImportedQueryDefinition),
+    Enum(ImportedEnumDefinition),
+}
+
+impl Import {
+    fn r#type(&self) -> &str {
+        match self {
+            Self::Object(def) => &def.r#type,
+            Self::Query(def) => &def.r#type,
+            Self::Enum(def) => &def.r#type,
+        }
+    }
+}
+
+/// Returns the names of the imported types, for the `@imports` directive
+fn resolve_external_imports(
+    imports_to_resolve: &[ExternalImport],
+    resolver: &dyn SchemaResolver,
+    type_info: &mut TypeInfo,
+) -> std::result::Result<Vec<String>, SchemaErrors> {
+    // Keep track of all imported types, in the order found
+    let mut imports = vec![];
+
+    for import in imports_to_resolve {
+        let schema = resolve(resolver, &import.uri)?;
+        let ext_type_info = parse_schema(&schema)?;
+
+        let mut importer = ExternalImporter {
+            ext_type_info: &ext_type_info,
+            namespace: &import.namespace,
+            uri: &import.uri,
+            imports: &mut imports,
+        };
+        for imported_type in &import.imported_types {
+            importer.import_type(imported_type)?;
+        }
+    }
+
+    // Add all imported types into the aggregate TypeInfo
+    let types = imports
+        .iter()
+        .map(|import| import.r#type().to_string())
+        .collect();
+    for import in imports {
+        match import {
+            Import::Object(def) => type_info.imported_object_types.push(def),
+            Import::Query(def) => type_info.imported_query_types.push(def),
+            Import::Enum(def) => type_info.imported_enum_types.push(def),
+        }
+    }
+    Ok(types)
+}
+
+/// Imports types from one URI's schema, along with
+/// the object and enum types they depend on
+struct ExternalImporter<'i> {
+    ext_type_info: &'i TypeInfo,
+    namespace: &'i str,
+    uri: &'i str,
+    imports: &'i mut Vec<Import>,
+}
+
+impl<'i> ExternalImporter<'i> {
+    fn import_type(&mut self, imported_type: &str) -> Result<()> {
+        let ext = self.ext_type_info;
+
+        if imported_type.ends_with("_Query") || imported_type.ends_with("_Mutation") {
+            return Err(SchemaError::new(
+                format!(
+                    "Cannot import an import's imported query type. Tried to import {} from {}.",
+                    imported_type, self.uri
+                ),
+                None,
+            ));
+        }
+        let not_found = |found: Vec<&str>| {
+            let found: Vec<_> = found.iter().map(|r#type| format!("{} ", r#type)).collect();
+            SchemaError::new(
+                format!(
+                    "Cannot find type \"{}\" in the schema at {}.\nFound: [ {}]",
+                    imported_type,
+                    self.uri,
+                    found.join(",")
+                ),
+                None,
+            )
+        };
+
+        let namespaced_type = append_namespace(self.namespace, imported_type);
+        if self.is_imported(&namespaced_type) {
+            return Ok(());
+        }
+
+        if imported_type == "Query" || imported_type == "Mutation" {
+            let query = ext
+                .query_types
+                .iter()
+                .find(|def| def.r#type == imported_type)
+                .ok_or_else(|| {
+                    not_found(ext.query_types.iter().map(|d| d.r#type.as_str()).collect())
+                })?;
+            self.add_query(namespaced_type, imported_type, &query.methods)
+        } else if let Some(object) = ext
+            .object_types
+            .iter()
+            .find(|def| def.r#type == imported_type)
+        {
+            self.add_object(namespaced_type, imported_type, &object.properties)
+        } else if let Some(object) = ext
+            .imported_object_types
+            .iter()
+            .find(|def| def.r#type == imported_type)
+        {
+            self.add_object(namespaced_type, imported_type, &object.properties)
+        } else if let Some(r#enum) = ext
+            .imported_enum_types
+            .iter()
+            .find(|def| def.r#type == imported_type)
+        {
+            self.add_enum(namespaced_type, imported_type, &r#enum.constants);
+            Ok(())
+        } else {
+            let r#enum = ext
+                .enum_types
+                .iter()
+                .find(|def| def.r#type == imported_type)
+                .ok_or_else(|| {
+                    not_found(ext.enum_types.iter().map(|d| d.r#type.as_str()).collect())
+                })?;
+            self.add_enum(namespaced_type, imported_type, &r#enum.constants);
+            Ok(())
+        }
+    }
+
+    fn is_imported(&self, namespaced_type: &str) -> bool {
+        self.imports
+            .iter()
+            .any(|import| import.r#type() == namespaced_type)
+    }
+
+    fn imported(&self, native_type: &str) -> ImportedDefinition {
+        ImportedDefinition {
+            uri: self.uri.to_string(),
+            namespace: self.namespace.to_string(),
+            native_type: native_type.to_string(),
+        }
+    }
+
+    fn add_query(
+        &mut self,
+        namespaced_type: String,
+        native_type: &str,
+        methods: &'i [MethodDefinition],
+    ) -> Result<()> {
+        let mut def = ImportedQueryDefinition::new(&namespaced_type, self.imported(native_type))
+            .expect("imported query types are query types");
+        def.methods = methods.to_vec();
+        for method in &mut def.methods {
+            for argument in &mut method.arguments {
+                namespace_property(argument, self.namespace);
+            }
+            namespace_property(&mut method.r#return, self.namespace);
+        }
+        self.imports.push(Import::Query(def));
+
+        for method in methods {
+            for argument in &method.arguments {
+                self.import_dependencies(argument)?;
+            }
+            self.import_dependencies(&method.r#return)?;
+        }
+        Ok(())
+    }
+
+    fn add_object(
+        &mut self,
+        namespaced_type: String,
+        native_type: &str,
+        properties: &'i [PropertyDefinition],
+    ) -> Result<()> {
+        let mut def = ImportedObjectDefinition::new(&namespaced_type, self.imported(native_type));
+        def.properties = properties.to_vec();
+        for property in &mut def.properties {
+            namespace_property(property, self.namespace);
+        }
+        self.imports.push(Import::Object(def));
+
+        for property in properties {
+            self.import_dependencies(property)?;
+        }
+        Ok(())
+    }
+
+    fn add_enum(&mut self, namespaced_type: String, native_type: &str, constants: &[String]) {
+        let imported = self.imported(native_type);
+        let def = ImportedEnumDefinition::new(&namespaced_type, constants.to_vec(), imported);
+        self.imports.push(Import::Enum(def));
+    }
+
+    /// Imports the object or enum types a property refers to
+    fn import_dependencies(&mut self, property: &PropertyDefinition) -> Result<()> {
+        if let Some(object) = &property.object {
+            self.import_object(&object.r#type)
+        } else if let Some(r#enum) = &property.r#enum {
+            self.import_enum(&r#enum.r#type)
+        } else if let Some(array) = &property.array {
+            self.import_array_dependencies(array)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn import_array_dependencies(&mut self, array: &ArrayDefinition) -> Result<()> {
+        if let Some(object) = &array.object {
+            self.import_object(&object.r#type)
+        } else if let Some(r#enum) = &array.r#enum {
+            self.import_enum(&r#enum.r#type)
+        } else if let Some(array) = &array.array {
+            self.import_array_dependencies(array)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn import_object(&mut self, r#type: &str) -> Result<()> {
+        let namespaced_type = append_namespace(self.namespace, r#type);
+        if self.is_imported(&namespaced_type) {
+            return Ok(());
+        }
+
+        let ext = self.ext_type_info;
+        let properties = ext
+            .object_types
+            .iter()
+            .find(|def| def.r#type == r#type)
+            .map(|def| &def.properties)
+            .or_else(|| {
+                ext.imported_object_types
+                    .iter()
+                    .find(|def| def.r#type == r#type)
+                    .map(|def| &def.properties)
+            })
+            .ok_or_else(|| self.dependency_not_found(r#type))?;
+        self.add_object(namespaced_type, r#type, properties)
+    }
+
+    fn import_enum(&mut self, r#type: &str) -> Result<()> {
+        let namespaced_type = append_namespace(self.namespace, r#type);
+        if self.is_imported(&namespaced_type) {
+            return Ok(());
+        }
+
+        let ext = self.ext_type_info;
+        let constants = ext
+            .enum_types
+            .iter()
+            .find(|def| def.r#type == r#type)
+            .map(|def| &def.constants)
+            .or_else(|| {
+                ext.imported_enum_types
+                    .iter()
+                    .find(|def| def.r#type == r#type)
+                    .map(|def| &def.constants)
+            })
+            .ok_or_else(|| self.dependency_not_found(r#type))?;
+        self.add_enum(namespaced_type, r#type, constants);
+        Ok(())
+    }
+
+    fn dependency_not_found(&self, r#type: &str) -> SchemaError {
+        SchemaError::new(
+            format!(
+                "Cannot find the dependent type \"{}\" in the schema at {}",
+                r#type, self.uri
+            ),
+            None,
+        )
+    }
+}
+
+/// Namespaces the object and enum types the property refers to
+fn namespace_property(property: &mut PropertyDefinition, namespace: &str) {
+    if let Some(object) = &mut property.object {
+        object.r#type = append_namespace(namespace, &object.r#type);
+        property.r#type = object.r#type.clone();
+    } else if let Some(r#enum) = &mut property.r#enum {
+        r#enum.r#type = append_namespace(namespace, &r#enum.r#type);
+        property.r#type = r#enum.r#type.clone();
+    } else if let Some(array) = &mut property.array {
+        namespace_array(array, namespace);
+        property.r#type = array.r#type.clone();
+    }
+}
+
+fn namespace_array(array: &mut ArrayDefinition, namespace: &str) {
+    let item_type = if let Some(object) = &mut array.object {
+        object.r#type = append_namespace(namespace, &object.r#type);
+        &object.r#type
+    } else if let Some(r#enum) = &mut array.r#enum {
+        r#enum.r#type = append_namespace(namespace, &r#enum.r#type);
+        &r#enum.r#type
+    } else if let Some(item) = &mut array.array {
+        namespace_array(item, namespace);
+        &item.r#type
+    } else {
+        return;
+    };
+    array.r#type = format!("[{}]", item_type);
+}
+
+/// A type imported from a local schema
+#[derive(Clone, Copy)]
+enum LocalType<'l> {
+    Object(&'l ObjectDefinition),
+    Enum(&'l EnumDefinition),
+}
+
+impl LocalType<'_> {
+    fn r#type(&self) -> &str {
+        match self {
+            Self::Object(def) => &def.r#type,
+            Self::Enum(def) => &def.r#type,
+        }
+    }
+}
+
+fn resolve_local_imports(
+    imports_to_resolve: &[LocalImport],
+    resolver: &dyn SchemaResolver,
+    type_info: &mut TypeInfo,
+) -> std::result::Result<(), SchemaErrors> {
+    for import in imports_to_resolve {
+        let path = import.path.to_string_lossy();
+        let mut schema = resolve(resolver, &path)?;
+
+        // Make sure the schema has the Web3API header
+        if !schema.contains("### Web3API Header START ###") {
+            schema = format!("{}\n{}\n", HEADER, schema);
+        }
+        let local_type_info = parse_schema(&schema)?;
+
+        // Keep track of all imported types, in the order found
+        let mut types_to_import = vec![];
+
+        for imported_type in &import.imported_types {
+            if imported_type == "Query" || imported_type == "Mutation" {
+                return Err(SchemaError::new(
+                    format!(
+                        "Importing query types from local schemas is prohibited. \
+                         Tried to import from {}.",
+                        path
+                    ),
+                    None,
+                )
+                .into());
+            }
+
+            let local_type = local_type(&local_type_info, imported_type).ok_or_else(|| {
+                let found: Vec<_> = local_type_info
+                    .object_types
+                    .iter()
+                    .map(|def| format!("{} ", def.r#type))
+                    .collect();
+                SchemaError::new(
+                    format!(
+                        "Cannot find type \"{}\" in the schema at {}.\nFound: [ {}]",
+                        imported_type,
+                        path,
+                        found.join(",")
+                    ),
+                    None,
+                )
+            })?;
+            import_local_type(local_type, &local_type_info, &mut types_to_import)?;
+        }
+
+        // Add all imported types into the aggregate TypeInfo
+        for local_type in types_to_import {
+            match local_type {
+                LocalType::Object(def) => {
+                    if !type_info
+                        .object_types
+                        .iter()
+                        .any(|d| d.r#type == def.r#type)
+                    {
+                        type_info.object_types.push(def.clone());
+                    }
+                }
+                LocalType::Enum(def) => {
+                    if !type_info.enum_types.iter().any(|d| d.r#type == def.r#type) {
+                        type_info.enum_types.push(def.clone());
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn local_type<'l>(local_type_info: &'l TypeInfo, r#type: &str) -> Option<LocalType<'l>> {
+    let object = local_type_info
+        .object_types
+        .iter()
+        .find(|def| def.r#type == r#type)
+        .map(LocalType::Object);
+    object.or_else(|| {
+        local_type_info
+            .enum_types
+            .iter()
+            .find(|def| def.r#type == r#type)
+            .map(LocalType::Enum)
+    })
+}
+
+/// Imports the type, and the object and enum types it depends on
+fn import_local_type<'l>(
+    local_type: LocalType<'l>,
+    local_type_info: &'l TypeInfo,
+    types_to_import: &mut Vec<LocalType<'l>>,
+) -> Result<()> {
+    if types_to_import
+        .iter()
+        .any(|imported| imported.r#type() == local_type.r#type())
+    {
+        return Ok(());
+    }
+    types_to_import.push(local_type);
+
+    if let LocalType::Object(object) = local_type {
+        for property in &object.properties {
+            let r#type = match referred_type(property) {
+                Some(r#type) => r#type,
+                None => continue,
+            };
+            let dependency = self::local_type(local_type_info, r#type).ok_or_else(|| {
+                SchemaError::new(
+                    format!(
+                        "Cannot find the requested type within the TypeInfo.\nType: {}",
+                        r#type
+                    ),
+                    None,
+                )
+            })?;
+            import_local_type(dependency, local_type_info, types_to_import)?;
+        }
+    }
+    Ok(())
+}
+
+/// The object or enum type a property refers to, if any
+fn referred_type(property: &PropertyDefinition) -> Option<&str> {
+    if let Some(object) = &property.object {
+        return Some(&object.r#type);
+    }
+    if let Some(r#enum) = &property.r#enum {
+        return Some(&r#enum.r#type);
+    }
+    let mut array = property.array.as_deref()?;
+    loop {
+        if let Some(object) = &array.object {
+            return Some(&object.r#type);
+        }
+        if let Some(r#enum) = &array.r#enum {
+            return Some(&r#enum.r#type);
+        }
+        array = array.array.as_deref()?;
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Fetches the schemas imported by a schema being composed,
+/// given the URI of an external import or the path of a local one
+pub trait SchemaResolver {
+    fn resolve(&self, uri_or_path: &str) -> io::Result<String>;
+}
+
+impl<F> SchemaResolver for F
+where
+    F: Fn(&str) -> io::Result<String>,
+{
+    fn resolve(&self, uri_or_path: &str) -> io::Result<String> {
+        self(uri_or_path)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct SchemaResolvers<'r> {
+    pub external: &'r dyn SchemaResolver,
+    pub local: &'r dyn SchemaResolver,
+}
+
+/// Reads schemas from files, at paths relative to `root`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileSystemResolver {
+    pub root: PathBuf,
+}
+
+impl FileSystemResolver {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl SchemaResolver for FileSystemResolver {
+    fn resolve(&self, uri_or_path: &str) -> io::Result<String> {
+        fs::read_to_string(self.root.join(uri_or_path))
+    }
+}
+
+/// Schemas by their URI or path, e.g. for tests
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InMemoryResolver {
+    schemas: HashMap<String, String>,
+}
+
+impl InMemoryResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(
+        &mut self,
+        uri_or_path: impl Into<String>,
+        schema: impl Into<String>,
+    ) -> &mut Self {
+        self.schemas.insert(uri_or_path.into(), schema.into());
+        self
+    }
+}
+
+impl SchemaResolver for InMemoryResolver {
+    fn resolve(&self, uri_or_path: &str) -> io::Result<String> {
+        self.schemas.get(uri_or_path).cloned().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no schema for {}", uri_or_path),
+            )
+        })
+    }
+}
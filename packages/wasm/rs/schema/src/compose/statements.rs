@@ -0,0 +1,313 @@
+//! Finds a schema's `import` statements, and checks them as
+//! `@web3api/schema-compose` does with its regular expressions
+
+use crate::error::{Result, SchemaError};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+pub(super) struct ExternalImport {
+    pub imported_types: Vec<String>,
+    pub namespace: String,
+    pub uri: String,
+}
+
+pub(super) struct LocalImport {
+    pub imported_types: Vec<String>,
+    pub path: PathBuf,
+}
+
+/// An import statement, e.g. `#import { A, B } into Ns from "uri"`
+pub(super) struct Statement<'s> {
+    /// From its leading `#` to its closing quote
+    pub range: Range<usize>,
+    pub types: &'s str,
+    /// Set for external imports only
+    pub namespace: Option<&'s str>,
+    pub from: &'s str,
+}
+
+/// The statements found, and the number of lines starting with an
+/// `import` keyword, which differs if any of them is malformed
+pub(super) fn find_statements(schema: &str) -> (Vec<Statement<'_>>, usize) {
+    let mut statements = vec![];
+    let mut keywords = 0;
+    let mut searched = 0;
+
+    while let Some(offset) = schema[searched..].find("import") {
+        let keyword = searched + offset;
+        let after = keyword + "import".len();
+
+        let line_start = schema[..keyword].rfind('\n').map_or(0, |i| i + 1);
+        let prefix = schema[line_start..keyword].trim_start_matches('#');
+        let at_line_start = prefix.chars().all(|c| "\"{3}".contains(c));
+        let spaced = schema[after..].starts_with(|c| " \n\t".contains(c));
+        if at_line_start && spaced {
+            keywords += 1;
+        }
+
+        match statement(schema, searched, keyword) {
+            Some(statement) => {
+                searched = statement.range.end;
+                statements.push(statement);
+            }
+            None => searched = after,
+        }
+    }
+    (statements, keywords)
+}
+
+fn statement(schema: &str, searched: usize, keyword: usize) -> Option<Statement<'_>> {
+    let before = schema[searched..keyword].trim_end_matches(|c| "\"{3}".contains(c));
+    let start = searched + before.trim_end_matches('#').len();
+
+    let mut scanner = Scanner {
+        schema,
+        position: keyword + "import".len(),
+    };
+    scanner.whitespace();
+    scanner.expect("{")?;
+    let types = scanner.take(|c| c.is_ascii_alphanumeric() || "_, \n\t".contains(c))?;
+    scanner.expect("}")?;
+    scanner.whitespace();
+
+    let namespace = if scanner.expect("into").is_some() {
+        scanner.whitespace();
+        let namespace = scanner.take(|c| c.is_ascii_alphanumeric() || c == '_')?;
+        scanner.whitespace();
+        Some(namespace)
+    } else {
+        None
+    };
+
+    scanner.expect("from")?;
+    scanner.whitespace();
+    scanner.quote()?;
+    let from = match namespace {
+        Some(_) => scanner.take(|c| c.is_ascii_alphanumeric() || "_~.:/".contains(c))?,
+        None => scanner.take(|c| c.is_ascii_alphanumeric() || "_~-:./".contains(c))?,
+    };
+    scanner.quote()?;
+
+    Some(Statement {
+        range: start..scanner.position,
+        types,
+        namespace,
+        from,
+    })
+}
+
+struct Scanner<'s> {
+    schema: &'s str,
+    position: usize,
+}
+
+impl<'s> Scanner<'s> {
+    fn rest(&self) -> &'s str {
+        &self.schema[self.position..]
+    }
+
+    fn whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start_matches(|c| " \n\t".contains(c)).len();
+    }
+
+    fn expect(&mut self, token: &str) -> Option<()> {
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn quote(&mut self) -> Option<()> {
+        let quote = self.rest().chars().next().filter(|c| "\"'`".contains(*c))?;
+        self.position += quote.len_utf8();
+        Some(())
+    }
+
+    /// At least one character matching `pattern`
+    fn take(&mut self, pattern: impl Fn(char) -> bool) -> Option<&'s str> {
+        let rest = self.rest();
+        let len = rest.len() - rest.trim_start_matches(pattern).len();
+        if len == 0 {
+            return None;
+        }
+        self.position += len;
+        Some(&rest[..len])
+    }
+}
+
+fn imported_types(types: &str) -> Vec<String> {
+    types
+        .split(',')
+        .map(|r#type| r#type.split_whitespace().collect::<String>())
+        .filter(|r#type| !r#type.is_empty())
+        .collect()
+}
+
+/// The values found more than once, in the order first found
+fn duplicates<'a>(values: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut counts: Vec<(&str, usize)> = vec![];
+    for value in values {
+        match counts.iter_mut().find(|(counted, _)| *counted == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(value, _)| value)
+        .collect()
+}
+
+pub(super) fn parse_external_imports(
+    statements: &[&Statement],
+    mutation: bool,
+) -> Result<Vec<ExternalImport>> {
+    let mut external_imports = vec![];
+
+    for statement in statements {
+        let imported_types = imported_types(statement.types);
+        let namespace = statement.namespace.unwrap_or_default();
+        let uri = statement.from;
+
+        // Make sure the developer does not import the same dependency more than once
+        let duplicate_types = duplicates(imported_types.iter().map(String::as_str));
+        if !duplicate_types.is_empty() {
+            return Err(SchemaError::new(
+                format!(
+                    "Duplicate type found: {} \nIn import: {}",
+                    duplicate_types.join(","),
+                    uri
+                ),
+                None,
+            ));
+        }
+
+        // Make sure the developer does not try to import a dependencies dependency
+        if imported_types.iter().any(|r#type| r#type.contains('_')) {
+            return Err(SchemaError::new(
+                "Importing a dependency's imported type is forbidden. \
+                 Only import types that do not have an '_' in the typename.",
+                None,
+            ));
+        }
+
+        if !mutation && imported_types.iter().any(|r#type| r#type == "Mutation") {
+            return Err(SchemaError::new(
+                format!(
+                    "Query modules cannot import Mutations, write operations are prohibited.\n\
+                     See import statement for namespace \"{}\" at uri \"{}\"",
+                    namespace, uri
+                ),
+                None,
+            ));
+        }
+
+        external_imports.push(ExternalImport {
+            imported_types,
+            namespace: namespace.to_string(),
+            uri: uri.to_string(),
+        });
+    }
+
+    // Make sure namespaces are unique
+    let duplicate_namespaces = duplicates(
+        external_imports
+            .iter()
+            .map(|import| import.namespace.as_str()),
+    );
+    if !duplicate_namespaces.is_empty() {
+        return Err(SchemaError::new(
+            format!(
+                "Duplicate namespaces found: {}",
+                duplicate_namespaces.join(",")
+            ),
+            None,
+        ));
+    }
+
+    // Make sure all uris have the same namespace
+    let mut uri_to_namespace = HashMap::new();
+    for import in &external_imports {
+        let namespace = uri_to_namespace
+            .entry(import.uri.as_str())
+            .or_insert(import.namespace.as_str());
+        if *namespace != import.namespace {
+            return Err(SchemaError::new(
+                format!(
+                    "Imports from a single URI must be imported into the same namespace.\n\
+                     URI: {}\nNamespace 1: {}\nNamespace 2: {}",
+                    import.uri, import.namespace, namespace
+                ),
+                None,
+            ));
+        }
+    }
+
+    Ok(external_imports)
+}
+
+pub(super) fn parse_local_imports(
+    statements: &[&Statement],
+    schema_path: &Path,
+) -> Result<Vec<LocalImport>> {
+    let mut local_imports = vec![];
+
+    for statement in statements {
+        let imported_types = imported_types(statement.types);
+        let directory = schema_path.parent().unwrap_or_else(|| Path::new(""));
+        let path = normalize(&directory.join(statement.from));
+
+        // Make sure the developer does not try to import a dependencies dependency
+        if imported_types.iter().any(|r#type| r#type.contains('_')) {
+            return Err(SchemaError::new(
+                "User defined types with '_' in their name are forbidden. \
+                 This is used for Web3API import namespacing.",
+                None,
+            ));
+        }
+
+        local_imports.push(LocalImport {
+            imported_types,
+            path,
+        });
+    }
+
+    // Make sure types are unique
+    let duplicate_types = duplicates(
+        local_imports
+            .iter()
+            .flat_map(|import| import.imported_types.iter().map(String::as_str)),
+    );
+    if !duplicate_types.is_empty() {
+        return Err(SchemaError::new(
+            format!("Duplicate type found: {}", duplicate_types.join(",")),
+            None,
+        ));
+    }
+
+    Ok(local_imports)
+}
+
+/// Resolves the path's `.` and `..` components, as `path.join` does
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
@@ -0,0 +1,83 @@
+use graphql_parser::Pos;
+use std::error;
+use std::fmt;
+
+/// A position in the schema's source, starting at 1:1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<Pos> for Location {
+    fn from(pos: Pos) -> Self {
+        Self {
+            line: pos.line,
+            column: pos.column,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// An invalid schema, located at the definition at fault
+/// when there is one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaError {
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+impl SchemaError {
+    pub fn new(message: impl Into<String>, location: Option<Location>) -> Self {
+        Self {
+            message: message.into(),
+            location,
+        }
+    }
+
+    pub fn at(message: impl Into<String>, pos: Pos) -> Self {
+        Self::new(message, Some(pos.into()))
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some(location) => write!(f, "{} ({})", self.message, location),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl error::Error for SchemaError {}
+
+/// The problems `parse_schema` found, one per failing validator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaErrors(pub Vec<SchemaError>);
+
+impl From<SchemaError> for SchemaErrors {
+    fn from(error: SchemaError) -> Self {
+        Self(vec![error])
+    }
+}
+
+impl fmt::Display for SchemaErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for SchemaErrors {}
+
+pub type Result<T> = std::result::Result<T, SchemaError>;
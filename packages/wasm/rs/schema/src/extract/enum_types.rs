@@ -0,0 +1,60 @@
+use super::imported::{has_imported_directive, imported_definition};
+use super::CustomTypes;
+use crate::error::Result;
+use crate::type_info::{EnumDefinition, ImportedEnumDefinition, TypeInfo};
+use graphql_parser::schema::{Definition, Document, EnumType, TypeDefinition};
+
+fn enum_types<'d, 'a>(
+    document: &'d Document<'a, String>,
+) -> impl Iterator<Item = &'d EnumType<'a, String>> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::TypeDefinition(TypeDefinition::Enum(r#enum)) => Some(r#enum),
+            _ => None,
+        })
+}
+
+fn constants(r#enum: &EnumType<String>) -> Vec<String> {
+    r#enum
+        .values
+        .iter()
+        .map(|value| value.name.clone())
+        .collect()
+}
+
+pub(super) fn extract_enum_types(
+    document: &Document<String>,
+    type_info: &mut TypeInfo,
+    _: &CustomTypes,
+) -> Result<()> {
+    for r#enum in enum_types(document) {
+        if has_imported_directive(&r#enum.directives) {
+            continue;
+        }
+        type_info
+            .enum_types
+            .push(EnumDefinition::new(&r#enum.name, constants(r#enum)));
+    }
+    Ok(())
+}
+
+pub(super) fn extract_imported_enum_types(
+    document: &Document<String>,
+    type_info: &mut TypeInfo,
+    _: &CustomTypes,
+) -> Result<()> {
+    for r#enum in enum_types(document) {
+        if let Some(imported) = imported_definition(&r#enum.name, &r#enum.directives, false) {
+            type_info
+                .imported_enum_types
+                .push(ImportedEnumDefinition::new(
+                    &r#enum.name,
+                    constants(r#enum),
+                    imported,
+                ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::type_info::ImportedDefinition;
+use graphql_parser::schema::{Directive, Value};
+
+/// The `@imported` directive's arguments, if `directives` has it.
+/// Imported query types are named `<Namespace>_Query` or
+/// `<Namespace>_Mutation`, and only extracted if `query_types`
+/// is set; other imported types only if it is not.
+pub(super) fn imported_definition(
+    type_name: &str,
+    directives: &[Directive<String>],
+    query_types: bool,
+) -> Option<ImportedDefinition> {
+    let directive = directives.iter().find(|dir| dir.name == "imported")?;
+
+    let is_query_type = type_name.ends_with("_Query") || type_name.ends_with("_Mutation");
+    if is_query_type != query_types {
+        return None;
+    }
+
+    let argument = |key: &str| {
+        directive
+            .arguments
+            .iter()
+            .find_map(|(name, value)| match value {
+                Value::String(value) if name == key => Some(value.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    };
+
+    Some(ImportedDefinition {
+        uri: argument("uri"),
+        namespace: argument("namespace"),
+        native_type: argument("nativeType"),
+    })
+}
+
+pub(super) fn has_imported_directive(directives: &[Directive<String>]) -> bool {
+    directives.iter().any(|dir| dir.name == "imported")
+}
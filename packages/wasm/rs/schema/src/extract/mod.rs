@@ -0,0 +1,65 @@
+//! Builds the `TypeInfo` of a parsed schema, one extractor
+//! per kind of definition, as `@web3api/schema-parse` does
+
+mod enum_types;
+mod imported;
+mod object_types;
+mod property;
+mod query_types;
+
+use crate::error::Result;
+use crate::type_info::TypeInfo;
+use graphql_parser::schema::{Definition, Document, TypeDefinition};
+use std::collections::HashMap;
+
+type Extractor = fn(&Document<String>, &mut TypeInfo, &CustomTypes) -> Result<()>;
+
+const EXTRACTORS: &[Extractor] = &[
+    enum_types::extract_enum_types,
+    enum_types::extract_imported_enum_types,
+    object_types::extract_object_types,
+    object_types::extract_imported_object_types,
+    query_types::extract_query_types,
+    query_types::extract_imported_query_types,
+];
+
+pub(crate) fn extract(document: &Document<String>) -> Result<TypeInfo> {
+    let custom_types = CustomTypes::new(document);
+    let mut type_info = TypeInfo::default();
+    for extract in EXTRACTORS {
+        extract(document, &mut type_info, &custom_types)?;
+    }
+    Ok(type_info)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CustomType {
+    Object,
+    Enum,
+}
+
+/// The object and enum types defined in the schema,
+/// which properties may refer to
+struct CustomTypes(HashMap<String, CustomType>);
+
+impl CustomTypes {
+    fn new(document: &Document<String>) -> Self {
+        let mut types = HashMap::new();
+        for definition in &document.definitions {
+            match definition {
+                Definition::TypeDefinition(TypeDefinition::Object(object)) => {
+                    types.insert(object.name.clone(), CustomType::Object);
+                }
+                Definition::TypeDefinition(TypeDefinition::Enum(r#enum)) => {
+                    types.insert(r#enum.name.clone(), CustomType::Enum);
+                }
+                _ => {}
+            }
+        }
+        Self(types)
+    }
+
+    fn get(&self, name: &str) -> Option<CustomType> {
+        self.0.get(name).copied()
+    }
+}
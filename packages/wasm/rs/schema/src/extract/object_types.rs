@@ -0,0 +1,73 @@
+use super::imported::{has_imported_directive, imported_definition};
+use super::property::property;
+use super::CustomTypes;
+use crate::error::{Result, SchemaError};
+use crate::type_info::{
+    is_query_type, ImportedObjectDefinition, ObjectDefinition, PropertyDefinition, TypeInfo,
+};
+use graphql_parser::schema::{Definition, Document, ObjectType, TypeDefinition};
+
+pub(super) fn object_types<'d, 'a>(
+    document: &'d Document<'a, String>,
+) -> impl Iterator<Item = &'d ObjectType<'a, String>> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::TypeDefinition(TypeDefinition::Object(object)) => Some(object),
+            _ => None,
+        })
+}
+
+fn properties(
+    object: &ObjectType<String>,
+    custom_types: &CustomTypes,
+) -> Result<Vec<PropertyDefinition>> {
+    object
+        .fields
+        .iter()
+        .map(|field| {
+            if !field.arguments.is_empty() {
+                return Err(SchemaError::at(
+                    format!(
+                        "Imported types cannot have methods. See type \"{}\"",
+                        object.name
+                    ),
+                    field.position,
+                ));
+            }
+            property(&field.name, &field.field_type, field.position, custom_types)
+        })
+        .collect()
+}
+
+pub(super) fn extract_object_types(
+    document: &Document<String>,
+    type_info: &mut TypeInfo,
+    custom_types: &CustomTypes,
+) -> Result<()> {
+    for object in object_types(document) {
+        if is_query_type(&object.name) || has_imported_directive(&object.directives) {
+            continue;
+        }
+        let mut object_type = ObjectDefinition::new(&object.name);
+        object_type.properties = properties(object, custom_types)?;
+        type_info.object_types.push(object_type);
+    }
+    Ok(())
+}
+
+pub(super) fn extract_imported_object_types(
+    document: &Document<String>,
+    type_info: &mut TypeInfo,
+    custom_types: &CustomTypes,
+) -> Result<()> {
+    for object in object_types(document) {
+        if let Some(imported) = imported_definition(&object.name, &object.directives, false) {
+            let mut object_type = ImportedObjectDefinition::new(&object.name, imported);
+            object_type.properties = properties(object, custom_types)?;
+            type_info.imported_object_types.push(object_type);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use super::{CustomType, CustomTypes};
+use crate::error::{Result, SchemaError};
+use crate::type_info::{
+    ArrayDefinition, EnumDefinition, ObjectDefinition, PropertyDefinition, ScalarDefinition,
+    TypeDefinition,
+};
+use graphql_parser::schema::Type;
+use graphql_parser::Pos;
+
+/// The property `name` of type `r#type`, declared at `pos`
+pub(super) fn property(
+    name: &str,
+    r#type: &Type<String>,
+    pos: Pos,
+    custom_types: &CustomTypes,
+) -> Result<PropertyDefinition> {
+    let value = type_definition(name, r#type, false, pos, custom_types)?;
+    Ok(PropertyDefinition::property(name, value))
+}
+
+fn type_definition(
+    name: &str,
+    r#type: &Type<String>,
+    required: bool,
+    pos: Pos,
+    custom_types: &CustomTypes,
+) -> Result<TypeDefinition> {
+    match r#type {
+        Type::NonNullType(r#type) => type_definition(name, r#type, true, pos, custom_types),
+        Type::ListType(item) => {
+            let item = type_definition(name, item, false, pos, custom_types)?;
+            Ok(TypeDefinition::Array(ArrayDefinition::new(
+                name, required, item,
+            )))
+        }
+        Type::NamedType(r#type) => {
+            if let Some(scalar) = ScalarDefinition::new(r#type, name, required) {
+                return Ok(TypeDefinition::Scalar(scalar));
+            }
+            match custom_types.get(r#type) {
+                Some(CustomType::Enum) => Ok(TypeDefinition::Enum(EnumDefinition::reference(
+                    r#type, name, required,
+                ))),
+                Some(CustomType::Object) => Ok(TypeDefinition::Object(
+                    ObjectDefinition::reference(r#type, name, required),
+                )),
+                None => Err(SchemaError::at(format!("Unsupported type {}", r#type), pos)),
+            }
+        }
+    }
+}
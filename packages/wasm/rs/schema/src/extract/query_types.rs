@@ -0,0 +1,138 @@
+use super::imported::imported_definition;
+use super::object_types::object_types;
+use super::property::property;
+use super::CustomTypes;
+use crate::error::{Result, SchemaError};
+use crate::type_info::{
+    ImportedQueryDefinition, ImportedType, MethodDefinition, QueryDefinition, TypeInfo,
+};
+use graphql_parser::schema::{Document, Field, ObjectType, Value};
+
+fn method(
+    query_type: &str,
+    field: &Field<String>,
+    custom_types: &CustomTypes,
+) -> Result<MethodDefinition> {
+    let r#return = property(&field.name, &field.field_type, field.position, custom_types)?;
+    let mut method = MethodDefinition::new(query_type, &field.name, r#return).ok_or_else(|| {
+        SchemaError::at(
+            format!("Unrecognized operation type \"{}\"", query_type),
+            field.position,
+        )
+    })?;
+    for argument in &field.arguments {
+        method.arguments.push(property(
+            &argument.name,
+            &argument.value_type,
+            argument.position,
+            custom_types,
+        )?);
+    }
+    Ok(method)
+}
+
+/// The types listed by the query type's `@imports` directive
+fn imports(query: &ObjectType<String>) -> Result<Vec<ImportedType>> {
+    let directive = match query.directives.iter().find(|dir| dir.name == "imports") {
+        Some(directive) => directive,
+        None => return Ok(vec![]),
+    };
+    let error = |message: &str| {
+        SchemaError::at(
+            format!("{}. See type {}.", message, query.name),
+            directive.position,
+        )
+    };
+
+    if directive.arguments.is_empty() {
+        return Err(error("@imports directive is incomplete, missing arguments"));
+    }
+    let types = match directive.arguments.iter().find(|(name, _)| name == "types") {
+        Some((_, value)) => value,
+        None => {
+            return Err(error(
+                "@imports directive missing required argument \"types\"",
+            ))
+        }
+    };
+    let types = match types {
+        Value::List(types) => types,
+        _ => {
+            return Err(error(
+                "@imports directive's types argument must be a List type",
+            ))
+        }
+    };
+
+    types
+        .iter()
+        .map(|value| match value {
+            Value::String(r#type) => Ok(ImportedType {
+                r#type: r#type.clone(),
+            }),
+            _ => Err(error(
+                "@imports directive's types list must only contain strings",
+            )),
+        })
+        .collect()
+}
+
+pub(super) fn extract_query_types(
+    document: &Document<String>,
+    type_info: &mut TypeInfo,
+    custom_types: &CustomTypes,
+) -> Result<()> {
+    for object in object_types(document) {
+        let mut query = match QueryDefinition::new(&object.name, vec![]) {
+            Some(query) => query,
+            None => continue,
+        };
+        query.imports = imports(object)?;
+        for field in &object.fields {
+            query
+                .methods
+                .push(method(&object.name, field, custom_types)?);
+        }
+        type_info.query_types.push(query);
+    }
+    Ok(())
+}
+
+pub(super) fn extract_imported_query_types(
+    document: &Document<String>,
+    type_info: &mut TypeInfo,
+    custom_types: &CustomTypes,
+) -> Result<()> {
+    for object in object_types(document) {
+        let imported = match imported_definition(&object.name, &object.directives, true) {
+            Some(imported) => imported,
+            None => continue,
+        };
+        let native_type = imported.native_type.clone();
+        let mut query = ImportedQueryDefinition::new(&object.name, imported).ok_or_else(|| {
+            SchemaError::at(
+                format!(
+                    "Unrecognized query type \"{}\". See type {}.",
+                    native_type, object.name
+                ),
+                object.position,
+            )
+        })?;
+        for field in &object.fields {
+            if field.arguments.is_empty() {
+                return Err(SchemaError::at(
+                    format!(
+                        "Imported Query types must only have methods. See property: {}",
+                        field.name
+                    ),
+                    field.position,
+                ));
+            }
+            query
+                .methods
+                .push(method(&native_type, field, custom_types)?);
+        }
+        type_info.imported_query_types.push(query);
+    }
+    Ok(())
+}
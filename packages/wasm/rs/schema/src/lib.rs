@@ -0,0 +1,78 @@
+//! Parses Web3API schemas into the `TypeInfo` model of
+//! `@web3api/schema-parse`, for Rust tooling
+
+pub mod compose;
+mod error;
+mod extract;
+mod header;
+pub mod type_info;
+pub mod validate;
+
+pub use error::{Location, Result, SchemaError, SchemaErrors};
+pub use header::HEADER;
+pub use type_info::TypeInfo;
+
+use graphql_parser::schema::ParseError;
+use validate::{SchemaValidator, VALIDATORS};
+
+/// How `parse_schema_with` parses a schema
+#[derive(Clone, Debug)]
+pub struct ParserOptions {
+    /// The rules the schema must follow, all of them by default.
+    /// Leave empty to skip validation.
+    pub validators: Vec<SchemaValidator>,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            validators: VALIDATORS.to_vec(),
+        }
+    }
+}
+
+/// Parses a schema, e.g. a module's `schema.graphql`. The Web3API
+/// scalars need not be declared, and `#import` statements are
+/// comments to the parser: imported types must be composed into
+/// the schema, as `@imported` types, for properties to use them.
+pub fn parse_schema(schema: &str) -> std::result::Result<TypeInfo, SchemaErrors> {
+    parse_schema_with(schema, &ParserOptions::default())
+}
+
+/// Parses a schema as `parse_schema` does, validating it against
+/// the given rules, each of which reports its own error
+pub fn parse_schema_with(
+    schema: &str,
+    options: &ParserOptions,
+) -> std::result::Result<TypeInfo, SchemaErrors> {
+    let document = graphql_parser::parse_schema::<String>(schema).map_err(syntax_error)?;
+
+    let errors: Vec<_> = options
+        .validators
+        .iter()
+        .filter_map(|validate| validate(&document).err())
+        .collect();
+    if !errors.is_empty() {
+        return Err(SchemaErrors(errors));
+    }
+
+    Ok(extract::extract(&document)?)
+}
+
+/// The parser only locates its errors in their message,
+/// e.g. `schema parse error: Parse error at 2:10`
+fn syntax_error(error: ParseError) -> SchemaError {
+    let message = error.to_string();
+    let location = message
+        .split("Parse error at ")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|position| {
+            let (line, column) = position.split_once(':')?;
+            Some(Location {
+                line: line.parse().ok()?,
+                column: column.parse().ok()?,
+            })
+        });
+    SchemaError::new(message, location)
+}
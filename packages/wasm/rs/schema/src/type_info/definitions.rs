@@ -0,0 +1,451 @@
+//! The definitions making up a `TypeInfo`, mirroring
+//! `@web3api/schema-parse`. They serialize to the same
+//! JSON as their TypeScript counterparts.
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+
+/// The kind of a definition, as bit flags: an `ARRAY` or a
+/// `PROPERTY` is also `ANY`, an `IMPORTED_OBJECT` also `OBJECT`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DefinitionKind(pub u32);
+
+impl DefinitionKind {
+    pub const GENERIC: Self = Self(0);
+    pub const OBJECT: Self = Self(1 << 0);
+    pub const ANY: Self = Self(1 << 1);
+    pub const SCALAR: Self = Self(1 << 2);
+    pub const ENUM: Self = Self(1 << 3);
+    pub const ARRAY: Self = Self((1 << 4) | Self::ANY.0);
+    pub const PROPERTY: Self = Self((1 << 5) | Self::ANY.0);
+    pub const METHOD: Self = Self(1 << 6);
+    pub const QUERY: Self = Self(1 << 7);
+    pub const IMPORTED_QUERY: Self = Self(1 << 8);
+    pub const IMPORTED_ENUM: Self = Self(1 << 9);
+    pub const IMPORTED_OBJECT: Self = Self((1 << 10) | Self::OBJECT.0);
+
+    pub fn is_kind(self, kind: DefinitionKind) -> bool {
+        self.0 & kind.0 == kind.0
+    }
+}
+
+/// `required` is `null` rather than `false` in the TypeScript
+/// model, so optional definitions serialize it as such
+mod required {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(required: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        if *required {
+            serializer.serialize_bool(true)
+        } else {
+            serializer.serialize_none()
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+        Ok(Option::<bool>::deserialize(deserializer)?.unwrap_or_default())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ObjectDefinition {
+    pub r#type: String,
+    pub name: Option<String>,
+    #[serde(with = "required")]
+    pub required: bool,
+    pub kind: DefinitionKind,
+    pub properties: Vec<PropertyDefinition>,
+}
+
+impl ObjectDefinition {
+    pub fn new(r#type: &str) -> Self {
+        Self {
+            r#type: r#type.to_string(),
+            name: None,
+            required: false,
+            kind: DefinitionKind::OBJECT,
+            properties: vec![],
+        }
+    }
+
+    /// A reference to the object type, from a property named `name`
+    pub fn reference(r#type: &str, name: &str, required: bool) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            required,
+            ..Self::new(r#type)
+        }
+    }
+}
+
+/// A value of any type: one of `array`, `scalar`, `object`
+/// and `enum` is set, and `type` is its GraphQL type name
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnyDefinition {
+    pub r#type: String,
+    pub name: Option<String>,
+    #[serde(with = "required")]
+    pub required: bool,
+    pub kind: DefinitionKind,
+    pub array: Option<Box<ArrayDefinition>>,
+    pub scalar: Option<ScalarDefinition>,
+    pub object: Option<ObjectDefinition>,
+    pub r#enum: Option<EnumDefinition>,
+}
+
+pub type PropertyDefinition = AnyDefinition;
+
+impl AnyDefinition {
+    /// A property named `name`, taking its type from `value`
+    pub fn property(name: &str, value: TypeDefinition) -> Self {
+        let (r#type, required) = value.type_and_required();
+        let mut property = Self {
+            r#type,
+            name: Some(name.to_string()),
+            required,
+            kind: DefinitionKind::PROPERTY,
+            array: None,
+            scalar: None,
+            object: None,
+            r#enum: None,
+        };
+        match value {
+            TypeDefinition::Array(array) => property.array = Some(Box::new(array)),
+            TypeDefinition::Scalar(scalar) => property.scalar = Some(scalar),
+            TypeDefinition::Object(object) => property.object = Some(object),
+            TypeDefinition::Enum(r#enum) => property.r#enum = Some(r#enum),
+        }
+        property
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScalarDefinition {
+    pub r#type: String,
+    pub name: Option<String>,
+    #[serde(with = "required")]
+    pub required: bool,
+    pub kind: DefinitionKind,
+}
+
+impl ScalarDefinition {
+    /// Returns `None` if `type` is not one of `SCALAR_TYPES`
+    pub fn new(r#type: &str, name: &str, required: bool) -> Option<Self> {
+        if !super::is_scalar_type(r#type) {
+            return None;
+        }
+        Some(Self {
+            r#type: r#type.to_string(),
+            name: Some(name.to_string()),
+            required,
+            kind: DefinitionKind::SCALAR,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnumDefinition {
+    pub r#type: String,
+    pub name: Option<String>,
+    #[serde(with = "required")]
+    pub required: bool,
+    pub kind: DefinitionKind,
+    pub constants: Vec<String>,
+}
+
+impl EnumDefinition {
+    pub fn new(r#type: &str, constants: Vec<String>) -> Self {
+        Self {
+            r#type: r#type.to_string(),
+            name: None,
+            required: false,
+            kind: DefinitionKind::ENUM,
+            constants,
+        }
+    }
+
+    /// A reference to the enum type, from a property named `name`
+    pub fn reference(r#type: &str, name: &str, required: bool) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            required,
+            ..Self::new(r#type, vec![])
+        }
+    }
+}
+
+/// An array of `item`s, which is the one of `array`, `scalar`,
+/// `object` and `enum` that is set. Its `type` is `[<item type>]`.
+/// The serialized `item` is ignored when deserializing.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ArrayDefinition {
+    pub r#type: String,
+    pub name: Option<String>,
+    #[serde(with = "required")]
+    pub required: bool,
+    pub kind: DefinitionKind,
+    pub array: Option<Box<ArrayDefinition>>,
+    pub scalar: Option<ScalarDefinition>,
+    pub object: Option<ObjectDefinition>,
+    pub r#enum: Option<EnumDefinition>,
+}
+
+impl ArrayDefinition {
+    pub fn new(name: &str, required: bool, item: TypeDefinition) -> Self {
+        let (item_type, _) = item.type_and_required();
+        let mut array = Self {
+            r#type: format!("[{}]", item_type),
+            name: Some(name.to_string()),
+            required,
+            kind: DefinitionKind::ARRAY,
+            array: None,
+            scalar: None,
+            object: None,
+            r#enum: None,
+        };
+        match item {
+            TypeDefinition::Array(item) => array.array = Some(Box::new(item)),
+            TypeDefinition::Scalar(item) => array.scalar = Some(item),
+            TypeDefinition::Object(item) => array.object = Some(item),
+            TypeDefinition::Enum(item) => array.r#enum = Some(item),
+        }
+        array
+    }
+
+    pub fn item(&self) -> Option<ArrayItem<'_>> {
+        if let Some(array) = &self.array {
+            Some(ArrayItem::Array(array))
+        } else if let Some(scalar) = &self.scalar {
+            Some(ArrayItem::Scalar(scalar))
+        } else if let Some(object) = &self.object {
+            Some(ArrayItem::Object(object))
+        } else {
+            self.r#enum.as_ref().map(ArrayItem::Enum)
+        }
+    }
+}
+
+struct Required(bool);
+
+impl Serialize for Required {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        required::serialize(&self.0, serializer)
+    }
+}
+
+// Written by hand for the `item` field, which duplicates
+// whichever of the others is set
+impl Serialize for ArrayDefinition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ArrayDefinition", 9)?;
+        state.serialize_field("type", &self.r#type)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("required", &Required(self.required))?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("array", &self.array)?;
+        state.serialize_field("scalar", &self.scalar)?;
+        state.serialize_field("object", &self.object)?;
+        state.serialize_field("enum", &self.r#enum)?;
+        state.serialize_field("item", &self.item())?;
+        state.end()
+    }
+}
+
+/// The item of an `ArrayDefinition`
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ArrayItem<'a> {
+    Array(&'a ArrayDefinition),
+    Scalar(&'a ScalarDefinition),
+    Object(&'a ObjectDefinition),
+    Enum(&'a EnumDefinition),
+}
+
+/// The type of a property, argument or array item,
+/// used to build the definition holding it
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeDefinition {
+    Array(ArrayDefinition),
+    Scalar(ScalarDefinition),
+    Object(ObjectDefinition),
+    Enum(EnumDefinition),
+}
+
+impl TypeDefinition {
+    fn type_and_required(&self) -> (String, bool) {
+        match self {
+            Self::Array(def) => (def.r#type.clone(), def.required),
+            Self::Scalar(def) => (def.r#type.clone(), def.required),
+            Self::Object(def) => (def.r#type.clone(), def.required),
+            Self::Enum(def) => (def.r#type.clone(), def.required),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MethodDefinition {
+    /// The operation, `query` or `mutation`
+    pub r#type: String,
+    pub name: Option<String>,
+    #[serde(with = "required")]
+    pub required: bool,
+    pub kind: DefinitionKind,
+    pub arguments: Vec<PropertyDefinition>,
+    pub r#return: PropertyDefinition,
+}
+
+impl MethodDefinition {
+    /// Returns `None` if `query_type` is not one of `QUERY_TYPES`
+    pub fn new(query_type: &str, name: &str, r#return: PropertyDefinition) -> Option<Self> {
+        let operation = query_type.to_lowercase();
+        if !super::is_operation_type(&operation) {
+            return None;
+        }
+        Some(Self {
+            r#type: operation,
+            name: Some(name.to_string()),
+            required: true,
+            kind: DefinitionKind::METHOD,
+            arguments: vec![],
+            r#return,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImportedType {
+    pub r#type: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueryDefinition {
+    /// `Query` or `Mutation`
+    pub r#type: String,
+    pub name: Option<String>,
+    #[serde(with = "required")]
+    pub required: bool,
+    pub kind: DefinitionKind,
+    pub methods: Vec<MethodDefinition>,
+    /// The types listed by the `@imports` directive
+    pub imports: Vec<ImportedType>,
+}
+
+impl QueryDefinition {
+    /// Returns `None` if `type` is not one of `QUERY_TYPES`
+    pub fn new(r#type: &str, imports: Vec<ImportedType>) -> Option<Self> {
+        if !super::is_query_type(r#type) {
+            return None;
+        }
+        Some(Self {
+            r#type: r#type.to_string(),
+            name: None,
+            required: false,
+            kind: DefinitionKind::QUERY,
+            methods: vec![],
+            imports,
+        })
+    }
+}
+
+/// Where an imported type comes from, as given
+/// by its `@imported` directive
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedDefinition {
+    pub uri: String,
+    pub namespace: String,
+    pub native_type: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedEnumDefinition {
+    pub r#type: String,
+    pub name: Option<String>,
+    #[serde(with = "required")]
+    pub required: bool,
+    pub kind: DefinitionKind,
+    pub constants: Vec<String>,
+    pub uri: String,
+    pub namespace: String,
+    pub native_type: String,
+}
+
+impl ImportedEnumDefinition {
+    pub fn new(r#type: &str, constants: Vec<String>, imported: ImportedDefinition) -> Self {
+        Self {
+            r#type: r#type.to_string(),
+            name: None,
+            required: false,
+            kind: DefinitionKind::IMPORTED_ENUM,
+            constants,
+            uri: imported.uri,
+            namespace: imported.namespace,
+            native_type: imported.native_type,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedQueryDefinition {
+    pub r#type: String,
+    pub name: Option<String>,
+    #[serde(with = "required")]
+    pub required: bool,
+    pub kind: DefinitionKind,
+    pub methods: Vec<MethodDefinition>,
+    pub uri: String,
+    pub namespace: String,
+    /// `Query` or `Mutation`
+    pub native_type: String,
+}
+
+impl ImportedQueryDefinition {
+    /// Returns `None` if the native type is not one of `QUERY_TYPES`
+    pub fn new(r#type: &str, imported: ImportedDefinition) -> Option<Self> {
+        if !super::is_query_type(&imported.native_type) {
+            return None;
+        }
+        Some(Self {
+            r#type: r#type.to_string(),
+            name: None,
+            required: false,
+            kind: DefinitionKind::IMPORTED_QUERY,
+            methods: vec![],
+            uri: imported.uri,
+            namespace: imported.namespace,
+            native_type: imported.native_type,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedObjectDefinition {
+    pub r#type: String,
+    pub name: Option<String>,
+    #[serde(with = "required")]
+    pub required: bool,
+    pub kind: DefinitionKind,
+    pub properties: Vec<PropertyDefinition>,
+    pub uri: String,
+    pub namespace: String,
+    pub native_type: String,
+}
+
+impl ImportedObjectDefinition {
+    pub fn new(r#type: &str, imported: ImportedDefinition) -> Self {
+        Self {
+            r#type: r#type.to_string(),
+            name: None,
+            required: false,
+            kind: DefinitionKind::IMPORTED_OBJECT,
+            properties: vec![],
+            uri: imported.uri,
+            namespace: imported.namespace,
+            native_type: imported.native_type,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! The model of a schema produced by `parse_schema`
+
+mod definitions;
+
+pub use definitions::*;
+
+use crate::error::{Result, SchemaError};
+use serde::{Deserialize, Serialize};
+
+/// The schema's scalars, GraphQL's and Web3API's
+pub const SCALAR_TYPES: &[&str] = &[
+    "UInt", "UInt8", "UInt16", "UInt32", "UInt64", "Int", "Int8", "Int16", "Int32", "Int64",
+    "String", "Boolean", "Bytes", "BigInt",
+];
+
+/// The root types of the query and mutation modules
+pub const QUERY_TYPES: &[&str] = &["Mutation", "Query"];
+
+/// The operations of methods, the query types in lowercase
+pub const OPERATION_TYPES: &[&str] = &["mutation", "query"];
+
+pub fn is_scalar_type(r#type: &str) -> bool {
+    SCALAR_TYPES.contains(&r#type)
+}
+
+pub fn is_query_type(r#type: &str) -> bool {
+    QUERY_TYPES.contains(&r#type)
+}
+
+pub fn is_operation_type(r#type: &str) -> bool {
+    OPERATION_TYPES.contains(&r#type)
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeInfo {
+    pub object_types: Vec<ObjectDefinition>,
+    pub query_types: Vec<QueryDefinition>,
+    pub enum_types: Vec<EnumDefinition>,
+    pub imported_object_types: Vec<ImportedObjectDefinition>,
+    pub imported_query_types: Vec<ImportedQueryDefinition>,
+    pub imported_enum_types: Vec<ImportedEnumDefinition>,
+}
+
+/// Merges the `TypeInfo`s of a module's query and mutation schemas.
+/// A type defined by several of them must be defined the same way:
+/// imported object and query types are the same type if imported
+/// from the same URI and native type, others if of the same name.
+pub fn combine_type_info(type_infos: &[TypeInfo]) -> Result<TypeInfo> {
+    let mut combined = TypeInfo::default();
+
+    for type_info in type_infos {
+        for def in &type_info.enum_types {
+            try_insert(&mut combined.enum_types, def, &def.r#type, |a| {
+                a.r#type == def.r#type
+            })?;
+        }
+        for def in &type_info.object_types {
+            try_insert(&mut combined.object_types, def, &def.r#type, |a| {
+                a.r#type == def.r#type
+            })?;
+        }
+        for def in &type_info.query_types {
+            try_insert(&mut combined.query_types, def, &def.r#type, |a| {
+                a.r#type == def.r#type
+            })?;
+        }
+        for def in &type_info.imported_object_types {
+            try_insert(&mut combined.imported_object_types, def, &def.r#type, |a| {
+                a.uri == def.uri && a.native_type == def.native_type
+            })?;
+        }
+        for def in &type_info.imported_query_types {
+            try_insert(&mut combined.imported_query_types, def, &def.r#type, |a| {
+                a.uri == def.uri && a.native_type == def.native_type
+            })?;
+        }
+        for def in &type_info.imported_enum_types {
+            try_insert(&mut combined.imported_enum_types, def, &def.r#type, |a| {
+                a.r#type == def.r#type
+            })?;
+        }
+    }
+    Ok(combined)
+}
+
+fn try_insert<T: Clone + PartialEq>(
+    dest: &mut Vec<T>,
+    value: &T,
+    r#type: &str,
+    same_type: impl Fn(&T) -> bool,
+) -> Result<()> {
+    match dest.iter().find(|def| same_type(def)) {
+        Some(def) if def != value => Err(SchemaError::new(
+            format!(
+                "combineTypeInfo found two types by the same type that are not equivalent.\n\
+                 Type: \"{}\"",
+                r#type
+            ),
+            None,
+        )),
+        Some(_) => Ok(()),
+        None => {
+            dest.push(value.clone());
+            Ok(())
+        }
+    }
+}
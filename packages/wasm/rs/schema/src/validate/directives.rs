@@ -0,0 +1,173 @@
+use super::{directive_usages, DirectiveTarget};
+use crate::error::{Result, SchemaError};
+use crate::type_info::is_query_type;
+use graphql_parser::schema::{Document, Value};
+
+const SUPPORTED_DIRECTIVES: &[&str] = &["imported", "imports"];
+
+/// The `@imported` directive's arguments, those of `ImportedDefinition`
+const IMPORTED_ARGUMENTS: &[&str] = &["uri", "namespace", "nativeType"];
+
+/// Values' kinds, as GraphQL.js names them
+fn value_kind(value: &Value<String>) -> &'static str {
+    match value {
+        Value::Variable(_) => "Variable",
+        Value::Int(_) => "IntValue",
+        Value::Float(_) => "FloatValue",
+        Value::String(_) => "StringValue",
+        Value::Boolean(_) => "BooleanValue",
+        Value::Null => "NullValue",
+        Value::Enum(_) => "EnumValue",
+        Value::List(_) => "ListValue",
+        Value::Object(_) => "ObjectValue",
+    }
+}
+
+pub fn supported_directives(document: &Document<String>) -> Result<()> {
+    let unsupported: Vec<_> = directive_usages(document)
+        .into_iter()
+        .filter(|usage| !SUPPORTED_DIRECTIVES.contains(&usage.directive.name.as_str()))
+        .collect();
+
+    match unsupported.first() {
+        Some(first) => {
+            let usages: Vec<_> = unsupported
+                .iter()
+                .map(|usage| format!("\n@{}", usage.directive.name))
+                .collect();
+            Err(SchemaError::at(
+                format!(
+                    "Found the following usages of unsupported directives:{}",
+                    usages.join(",")
+                ),
+                first.directive.position,
+            ))
+        }
+        None => Ok(()),
+    }
+}
+
+pub fn imports_directive(document: &Document<String>) -> Result<()> {
+    for usage in directive_usages(document) {
+        let directive = usage.directive;
+        if directive.name != "imports" {
+            continue;
+        }
+        let error = |message: String| Err(SchemaError::at(message, directive.position));
+
+        match usage.target {
+            DirectiveTarget::ObjectType(name) if !is_query_type(name) => {
+                return error(format!(
+                    "@imports directive should only be used on QUERY or MUTATION type \
+                     definitions, but it is being used on the following \
+                     ObjectTypeDefinitions:\n{}",
+                    name
+                ));
+            }
+            DirectiveTarget::ObjectType(_) => {}
+            _ => {
+                return error(format!(
+                    "@imports directive should only be used on QUERY or MUTATION type \
+                     definitions, but it is being used in the following location: {}",
+                    usage.path
+                ));
+            }
+        }
+
+        let types = match directive.arguments.iter().find(|(name, _)| name == "types") {
+            Some((_, types)) => types,
+            None => {
+                return error(
+                    "@imports directive requires argument 'types' of type [String!]!".to_string(),
+                )
+            }
+        };
+
+        if directive.arguments.len() > 1 {
+            let others: Vec<_> = directive
+                .arguments
+                .iter()
+                .filter(|(name, _)| name != "types")
+                .map(|(name, _)| format!("\n- {}", name))
+                .collect();
+            return error(format!(
+                "@imports directive takes only one argument 'types', but found: {}",
+                others.join(",")
+            ));
+        }
+
+        if let Value::List(values) = types {
+            if values.is_empty() {
+                return error(
+                    "@imports directive's 'types' argument of type [String!]! \
+                     requires at least one value"
+                        .to_string(),
+                );
+            }
+
+            let non_strings: Vec<_> = values
+                .iter()
+                .filter(|value| !matches!(value, Value::String(_)))
+                .map(|value| format!("\n -{}", value_kind(value)))
+                .collect();
+            if !non_strings.is_empty() {
+                return error(format!(
+                    "@imports directive's 'types' List values must be of type String, \
+                     but found: \n{}",
+                    non_strings.join(",")
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn imported_directive(document: &Document<String>) -> Result<()> {
+    for usage in directive_usages(document) {
+        let directive = usage.directive;
+        if directive.name != "imported" {
+            continue;
+        }
+        let error = |message: String| Err(SchemaError::at(message, directive.position));
+
+        if usage.target == DirectiveTarget::Other {
+            return error(format!(
+                "@imported directive should only be used on object or enum type definitions, \
+                 but it is being used in the following location: {}",
+                usage.path
+            ));
+        }
+
+        let actual: Vec<_> = directive
+            .arguments
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+
+        let missing: Vec<_> = IMPORTED_ARGUMENTS
+            .iter()
+            .filter(|expected| !actual.contains(expected))
+            .map(|expected| format!("\n- {}", expected))
+            .collect();
+        if !missing.is_empty() {
+            return error(format!(
+                "@imported directive is missing the following arguments:{}",
+                missing.join(",")
+            ));
+        }
+
+        let extra: Vec<_> = actual
+            .iter()
+            .filter(|actual| !IMPORTED_ARGUMENTS.contains(actual))
+            .map(|actual| format!("\n- {}", actual))
+            .collect();
+        if !extra.is_empty() {
+            return error(format!(
+                "@imported directive takes only 3 arguments: {}. But found:{}",
+                IMPORTED_ARGUMENTS.join(", "),
+                extra.join(",")
+            ));
+        }
+    }
+    Ok(())
+}
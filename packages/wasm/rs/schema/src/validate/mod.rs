@@ -0,0 +1,148 @@
+//! The rules `parse_schema` checks schemas against, ported from
+//! `@web3api/schema-parse`. Each validator reports the first
+//! problem it finds, located in the schema's source.
+
+pub mod directives;
+pub mod types;
+
+use crate::error::Result;
+use graphql_parser::schema::{
+    Definition, Directive, Document, EnumValue, Field, InputValue, TypeDefinition, TypeExtension,
+};
+
+pub type SchemaValidator = for<'a> fn(&Document<'a, String>) -> Result<()>;
+
+pub const VALIDATORS: &[SchemaValidator] = &[
+    directives::supported_directives,
+    directives::imported_directive,
+    directives::imports_directive,
+    types::type_definitions,
+    types::property_types,
+    types::circular_definitions,
+];
+
+/// What a directive is applied to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DirectiveTarget<'d> {
+    ObjectType(&'d str),
+    EnumType,
+    Other,
+}
+
+/// A directive, with its path in the document as GraphQL.js
+/// gives it, e.g. `definitions -> 0 -> fields -> 1 -> directives -> 0`
+struct DirectiveUsage<'d, 'a> {
+    directive: &'d Directive<'a, String>,
+    target: DirectiveTarget<'d>,
+    path: String,
+}
+
+/// Every directive applied in the document, in order
+fn directive_usages<'d, 'a>(document: &'d Document<'a, String>) -> Vec<DirectiveUsage<'d, 'a>> {
+    let mut usages = vec![];
+    let mut add = |directives: &'d [Directive<'a, String>], target, path: String| {
+        for (i, directive) in directives.iter().enumerate() {
+            usages.push(DirectiveUsage {
+                directive,
+                target,
+                path: format!("{} -> directives -> {}", path, i),
+            });
+        }
+    };
+
+    for (i, definition) in document.definitions.iter().enumerate() {
+        let path = format!("definitions -> {}", i);
+        let other = DirectiveTarget::Other;
+        match definition {
+            Definition::SchemaDefinition(schema) => add(&schema.directives, other, path),
+            Definition::TypeDefinition(TypeDefinition::Object(object)) => {
+                let target = DirectiveTarget::ObjectType(&object.name);
+                add(&object.directives, target, path.clone());
+                fields(&mut add, &object.fields, &path);
+            }
+            Definition::TypeDefinition(TypeDefinition::Interface(interface)) => {
+                add(&interface.directives, other, path.clone());
+                fields(&mut add, &interface.fields, &path);
+            }
+            Definition::TypeDefinition(TypeDefinition::Enum(r#enum)) => {
+                add(&r#enum.directives, DirectiveTarget::EnumType, path.clone());
+                values(&mut add, &r#enum.values, &path);
+            }
+            Definition::TypeDefinition(TypeDefinition::InputObject(input)) => {
+                add(&input.directives, other, path.clone());
+                input_values(&mut add, &input.fields, &format!("{} -> fields", path));
+            }
+            Definition::TypeDefinition(TypeDefinition::Scalar(scalar)) => {
+                add(&scalar.directives, other, path)
+            }
+            Definition::TypeDefinition(TypeDefinition::Union(union)) => {
+                add(&union.directives, other, path)
+            }
+            Definition::TypeExtension(TypeExtension::Object(object)) => {
+                add(&object.directives, other, path.clone());
+                fields(&mut add, &object.fields, &path);
+            }
+            Definition::TypeExtension(TypeExtension::Interface(interface)) => {
+                add(&interface.directives, other, path.clone());
+                fields(&mut add, &interface.fields, &path);
+            }
+            Definition::TypeExtension(TypeExtension::Enum(r#enum)) => {
+                add(&r#enum.directives, other, path.clone());
+                values(&mut add, &r#enum.values, &path);
+            }
+            Definition::TypeExtension(TypeExtension::InputObject(input)) => {
+                add(&input.directives, other, path.clone());
+                input_values(&mut add, &input.fields, &format!("{} -> fields", path));
+            }
+            Definition::TypeExtension(TypeExtension::Scalar(scalar)) => {
+                add(&scalar.directives, other, path)
+            }
+            Definition::TypeExtension(TypeExtension::Union(union)) => {
+                add(&union.directives, other, path)
+            }
+            Definition::DirectiveDefinition(directive) => input_values(
+                &mut add,
+                &directive.arguments,
+                &format!("{} -> arguments", path),
+            ),
+        }
+    }
+    usages
+}
+
+fn fields<'d, 'a, F>(add: &mut F, fields: &'d [Field<'a, String>], path: &str)
+where
+    F: FnMut(&'d [Directive<'a, String>], DirectiveTarget<'d>, String),
+{
+    for (i, field) in fields.iter().enumerate() {
+        let path = format!("{} -> fields -> {}", path, i);
+        add(&field.directives, DirectiveTarget::Other, path.clone());
+        input_values(add, &field.arguments, &format!("{} -> arguments", path));
+    }
+}
+
+fn input_values<'d, 'a, F>(add: &mut F, values: &'d [InputValue<'a, String>], path: &str)
+where
+    F: FnMut(&'d [Directive<'a, String>], DirectiveTarget<'d>, String),
+{
+    for (i, value) in values.iter().enumerate() {
+        add(
+            &value.directives,
+            DirectiveTarget::Other,
+            format!("{} -> {}", path, i),
+        );
+    }
+}
+
+fn values<'d, 'a, F>(add: &mut F, values: &'d [EnumValue<'a, String>], path: &str)
+where
+    F: FnMut(&'d [Directive<'a, String>], DirectiveTarget<'d>, String),
+{
+    for (i, value) in values.iter().enumerate() {
+        add(
+            &value.directives,
+            DirectiveTarget::Other,
+            format!("{} -> values -> {}", path, i),
+        );
+    }
+}
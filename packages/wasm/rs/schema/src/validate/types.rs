@@ -0,0 +1,259 @@
+use crate::error::{Result, SchemaError};
+use crate::type_info::{is_query_type, is_scalar_type, QUERY_TYPES, SCALAR_TYPES};
+use graphql_parser::schema::{
+    Definition, Document, Field, ObjectType, Type, TypeDefinition, Value,
+};
+use graphql_parser::Pos;
+use std::collections::HashSet;
+
+/// Types ignored by `circular_definitions`, along with their imports
+const OPERATION_TYPE_NAMES: &[&str] = &["Mutation", "Subscription", "Query"];
+
+fn object_types<'d, 'a>(
+    document: &'d Document<'a, String>,
+) -> impl Iterator<Item = &'d ObjectType<'a, String>> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::TypeDefinition(TypeDefinition::Object(object)) => Some(object),
+            _ => None,
+        })
+}
+
+fn named_type<'d>(r#type: &'d Type<String>) -> &'d str {
+    match r#type {
+        Type::NamedType(name) => name,
+        Type::ListType(item) | Type::NonNullType(item) => named_type(item),
+    }
+}
+
+pub fn type_definitions(document: &Document<String>) -> Result<()> {
+    let mut object_types = HashSet::new();
+
+    for definition in &document.definitions {
+        let definition = match definition {
+            Definition::TypeDefinition(definition) => definition,
+            _ => continue,
+        };
+        match definition {
+            TypeDefinition::Interface(interface) => {
+                return Err(SchemaError::at(
+                    format!(
+                        "Interface type definitions are not supported.\n\
+                         Found: interface {0} {{ ... }}\n\
+                         Please Use: type {0} {{ ... }}",
+                        interface.name
+                    ),
+                    interface.position,
+                ));
+            }
+            TypeDefinition::InputObject(input) => {
+                return Err(SchemaError::at(
+                    format!(
+                        "Input type definitions are not supported.\n\
+                         Found: input {0} {{ ... }}\n\
+                         Please Use: type {0} {{ ... }}",
+                        input.name
+                    ),
+                    input.position,
+                ));
+            }
+            TypeDefinition::Object(object) => {
+                if object.name == "Subscription" {
+                    return Err(SchemaError::at(
+                        "Subscriptions are not yet supported. Please use Query or Mutation.",
+                        object.position,
+                    ));
+                }
+                if !object_types.insert(object.name.as_str()) {
+                    return Err(SchemaError::at(
+                        format!("Duplicate object type definition found: {}", object.name),
+                        object.position,
+                    ));
+                }
+            }
+            TypeDefinition::Scalar(scalar) if !is_scalar_type(&scalar.name) => {
+                return Err(SchemaError::at(
+                    format!(
+                        "Custom scalar types are not supported. Supported scalars: {}",
+                        SCALAR_TYPES.join(",")
+                    ),
+                    scalar.position,
+                ));
+            }
+            TypeDefinition::Union(union) => {
+                return Err(SchemaError::at(
+                    format!(
+                        "Union type definitions are not supported.\nFound: union {}",
+                        union.name
+                    ),
+                    union.position,
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+pub fn property_types(document: &Document<String>) -> Result<()> {
+    let mut custom_types = HashSet::new();
+    for definition in &document.definitions {
+        match definition {
+            Definition::TypeDefinition(TypeDefinition::Object(object)) => {
+                custom_types.insert(object.name.as_str());
+            }
+            Definition::TypeDefinition(TypeDefinition::Enum(r#enum)) => {
+                custom_types.insert(r#enum.name.as_str());
+            }
+            _ => {}
+        }
+    }
+
+    // Arguments make methods, which only query types may have
+    for object in object_types(document) {
+        let native_type = object
+            .directives
+            .iter()
+            .filter(|directive| directive.name == "imported")
+            .flat_map(|directive| &directive.arguments)
+            .find_map(|(name, value)| match value {
+                Value::String(native_type) if name == "nativeType" => Some(native_type),
+                _ => None,
+            });
+        let type_name = native_type.unwrap_or(&object.name);
+        if is_query_type(type_name) {
+            continue;
+        }
+        for field in &object.fields {
+            if let Some(argument) = field.arguments.first() {
+                return Err(SchemaError::at(
+                    format!(
+                        "Methods can only be defined on query types ({}).\n\
+                         Found: type {} {{ {}({}) }}",
+                        QUERY_TYPES.join(", "),
+                        type_name,
+                        field.name,
+                        argument.name
+                    ),
+                    argument.position,
+                ));
+            }
+        }
+    }
+
+    // Properties, methods and their arguments must be of a
+    // supported scalar, or of an object or enum type
+    for object in object_types(document) {
+        for field in &object.fields {
+            let types = std::iter::once(&field.field_type)
+                .chain(field.arguments.iter().map(|argument| &argument.value_type));
+            for r#type in types {
+                let r#type = named_type(r#type);
+                if !is_scalar_type(r#type) && !custom_types.contains(r#type) {
+                    return Err(SchemaError::at(
+                        format!(
+                            "Unknown property type found: type {} {{ {}: {} }}",
+                            object.name, field.name, r#type
+                        ),
+                        field.position,
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A required property, referring to another object type
+struct Edge<'d> {
+    property: &'d str,
+    target: usize,
+    position: Pos,
+}
+
+pub fn circular_definitions(document: &Document<String>) -> Result<()> {
+    let is_operation_type = |name: &str| {
+        OPERATION_TYPE_NAMES
+            .iter()
+            .any(|operation| name == *operation || name.ends_with(&format!("_{}", operation)))
+    };
+    let mut nodes: Vec<&ObjectType<String>> = vec![];
+    for object in object_types(document) {
+        let known = nodes.iter().any(|node| node.name == object.name);
+        if !known && !is_operation_type(&object.name) {
+            nodes.push(object);
+        }
+    }
+
+    // Nullable properties may end a cycle, so only required ones count
+    let edges: Vec<Vec<Edge>> = nodes
+        .iter()
+        .map(|node| {
+            node.fields
+                .iter()
+                .filter(|field| matches!(field.field_type, Type::NonNullType(_)))
+                .filter_map(|field: &Field<String>| {
+                    let r#type = named_type(&field.field_type);
+                    let target = nodes.iter().position(|node| node.name == r#type)?;
+                    Some(Edge {
+                        property: &field.name,
+                        target,
+                        position: field.position,
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    // Every elementary cycle, searched for from the last type
+    // defined, each time through the types defined before it
+    let mut cycles = vec![];
+    for start in (0..nodes.len()).rev() {
+        let mut path = vec![];
+        find_cycles(start, start, &edges, &mut path, &mut cycles);
+    }
+
+    let position = match cycles.first() {
+        Some(cycle) => cycle[0].position,
+        None => return Ok(()),
+    };
+    let cycles: Vec<_> = cycles
+        .iter()
+        .map(|cycle| {
+            let mut string = format!("\n- {{ {}", nodes[cycle.last().unwrap().target].name);
+            for edge in cycle {
+                string += &format!(" -[{}]-> {}", edge.property, nodes[edge.target].name);
+            }
+            string + " }"
+        })
+        .collect();
+    Err(SchemaError::at(
+        format!(
+            "Graphql cycles are not supported. \nFound: {}",
+            cycles.join(",")
+        ),
+        position,
+    ))
+}
+
+fn find_cycles<'e, 'd>(
+    start: usize,
+    node: usize,
+    edges: &'e [Vec<Edge<'d>>],
+    path: &mut Vec<&'e Edge<'d>>,
+    cycles: &mut Vec<Vec<&'e Edge<'d>>>,
+) {
+    for edge in &edges[node] {
+        if edge.target == start {
+            path.push(edge);
+            cycles.push(path.clone());
+            path.pop();
+        } else if edge.target < start && !path.iter().any(|step| step.target == edge.target) {
+            path.push(edge);
+            find_cycles(start, edge.target, edges, path, cycles);
+            path.pop();
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use web3api_schema::compose::{
+    compose_schema, ComposerOptions, ComposerOutput, FileSystemResolver, InMemoryResolver,
+    SchemaFile, SchemaInfo, SchemaResolvers,
+};
+use web3api_schema::{parse_schema, TypeInfo};
+
+fn compose_case(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../../test-cases/cases/compose")
+        .join(name)
+}
+
+fn schema_file(case: &Path, name: &str) -> Option<SchemaFile> {
+    let absolute_path = case.join("input").join(name);
+    let schema = fs::read_to_string(&absolute_path).ok()?;
+    Some(SchemaFile {
+        schema,
+        absolute_path,
+    })
+}
+
+fn compose(case: &Path) -> ComposerOutput {
+    let imports_ext = case.join("imports-ext");
+    let external =
+        move |uri: &str| fs::read_to_string(imports_ext.join(uri).join("schema.graphql"));
+    let local = FileSystemResolver::default();
+
+    let options = ComposerOptions {
+        query: schema_file(case, "query.graphql"),
+        mutation: schema_file(case, "mutation.graphql"),
+        resolvers: SchemaResolvers {
+            external: &external,
+            local: &local,
+        },
+    };
+    compose_schema(&options).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// `<name>.json` is the case's `<name>.ts`, as JSON, written by
+/// `test-cases/scripts/typeinfo-json.ts`
+fn assert_output(case: &Path, name: &str, output: Option<SchemaInfo>) {
+    let output = output.unwrap_or_else(|| panic!("no {} output", name));
+    let expected =
+        fs::read_to_string(case.join("output").join(format!("{}.graphql", name))).unwrap();
+    assert_eq!(output.schema, expected, "{}", name);
+
+    let json = fs::read_to_string(case.join("output").join(format!("{}.json", name))).unwrap();
+    let expected: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        serde_json::to_value(&output.type_info).unwrap(),
+        expected,
+        "{}",
+        name
+    );
+}
+
+fn assert_case(name: &str) {
+    let case = compose_case(name);
+    let output = compose(&case);
+    assert_output(&case, "query", output.query);
+    assert_output(&case, "mutation", output.mutation);
+    assert_output(&case, "schema", output.combined);
+}
+
+#[test]
+fn composes_the_sanity_case() {
+    assert_case("sanity");
+}
+
+#[test]
+fn composes_the_local_imports_case() {
+    assert_case("local-imports");
+}
+
+fn compose_in_memory(query: &str, resolver: &InMemoryResolver) -> Result<TypeInfo, String> {
+    let options = ComposerOptions {
+        query: Some(SchemaFile {
+            schema: query.to_string(),
+            absolute_path: PathBuf::from("/module/query.graphql"),
+        }),
+        mutation: None,
+        resolvers: SchemaResolvers {
+            external: resolver,
+            local: resolver,
+        },
+    };
+    compose_schema(&options)
+        .map(|output| output.combined.unwrap().type_info)
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn resolves_imports_in_memory() {
+    let mut resolver = InMemoryResolver::new();
+    resolver
+        .insert(
+            "ens/object.eth",
+            "type Object { nested: Nested! }\ntype Nested { prop: [Enum!] }\nenum Enum { ONE }",
+        )
+        .insert("/common/types.graphql", "type Common { prop: String }");
+
+    let query = r#"#import { Object } into Ext from "ens/object.eth"
+#import { Common } from "../common/types.graphql"
+
+type Query {
+  method(arg: Ext_Object!): Common
+}
+"#;
+    let type_info = compose_in_memory(query, &resolver).unwrap();
+
+    let imported: Vec<_> = type_info
+        .imported_object_types
+        .iter()
+        .map(|def| (def.r#type.as_str(), def.native_type.as_str()))
+        .collect();
+    assert_eq!(
+        imported,
+        [("Ext_Object", "Object"), ("Ext_Nested", "Nested")]
+    );
+    assert_eq!(type_info.imported_enum_types[0].r#type, "Ext_Enum");
+    assert_eq!(
+        type_info.imported_object_types[1].properties[0].r#type,
+        "[Ext_Enum]"
+    );
+    assert_eq!(type_info.object_types[0].r#type, "Common");
+
+    let imports: Vec<_> = type_info.query_types[0]
+        .imports
+        .iter()
+        .map(|import| import.r#type.as_str())
+        .collect();
+    assert_eq!(imports, ["Ext_Object", "Ext_Nested", "Ext_Enum"]);
+}
+
+#[test]
+fn rejects_invalid_imports() {
+    let resolver = InMemoryResolver::new();
+    let error = |query: &str| compose_in_memory(query, &resolver).unwrap_err();
+
+    assert!(
+        error("#import { Mutation } into Ext from \"ext.eth\"\ntype Query { m: String }")
+            .starts_with("Query modules cannot import Mutations")
+    );
+    assert!(
+        error("#import { A_B } into Ext from \"ext.eth\"\ntype Query { m: String }")
+            .starts_with("Importing a dependency's imported type is forbidden.")
+    );
+    assert!(
+        error("#import { A, A } into Ext from \"ext.eth\"\ntype Query { m: String }")
+            .starts_with("Duplicate type found: A \nIn import: ext.eth")
+    );
+    assert!(
+        error("#import A from \"ext.eth\"\ntype Query { m: String }")
+            .starts_with("Invalid import statement found in file /module/query.graphql.")
+    );
+    assert_eq!(
+        error("#import { A } into Ext from \"ext.eth\"\ntype Query { m: String }"),
+        "Unable to resolve schema at \"ext.eth\": no schema for ext.eth"
+    );
+}
+
+#[test]
+fn composed_schemas_parse_to_their_type_info() {
+    let case = compose_case("sanity");
+    let combined = compose(&case).combined.unwrap();
+    assert_eq!(parse_schema(&combined.schema).unwrap(), combined.type_info);
+}
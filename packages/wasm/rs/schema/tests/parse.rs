@@ -0,0 +1,91 @@
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use web3api_schema::{
+    parse_schema, parse_schema_with, Location, ParserOptions, SchemaError, TypeInfo,
+};
+
+fn parse_unvalidated(schema: &str) -> SchemaError {
+    let options = ParserOptions { validators: vec![] };
+    let mut errors = parse_schema_with(schema, &options).unwrap_err().0;
+    assert_eq!(errors.len(), 1);
+    errors.remove(0)
+}
+
+fn parse_case(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../../test-cases/cases/parse")
+        .join(name)
+}
+
+#[test]
+fn parses_the_sanity_case() {
+    // `typeinfo.json` is the case's `output.ts`, as JSON, written by
+    // `test-cases/scripts/typeinfo-json.ts`
+    let case = parse_case("sanity");
+    let schema = fs::read_to_string(case.join("input.graphql")).unwrap();
+    let expected: Value =
+        serde_json::from_str(&fs::read_to_string(case.join("typeinfo.json")).unwrap()).unwrap();
+
+    let type_info = parse_schema(&schema).unwrap();
+    assert_eq!(serde_json::to_value(&type_info).unwrap(), expected);
+}
+
+#[test]
+fn deserializes_serialized_type_info() {
+    let schema = fs::read_to_string(parse_case("sanity").join("input.graphql")).unwrap();
+    let type_info = parse_schema(&schema).unwrap();
+
+    let json = serde_json::to_string(&type_info).unwrap();
+    assert_eq!(serde_json::from_str::<TypeInfo>(&json).unwrap(), type_info);
+}
+
+#[test]
+fn builds_array_types_from_their_items() {
+    let type_info = parse_schema("type Object { prop: [[Int!]]! }").unwrap();
+    let property = &type_info.object_types[0].properties[0];
+    assert_eq!(property.r#type, "[[Int]]");
+    assert!(property.required);
+
+    let outer = property.array.as_ref().unwrap();
+    assert_eq!((outer.r#type.as_str(), outer.required), ("[[Int]]", true));
+    let inner = outer.array.as_ref().unwrap();
+    assert_eq!((inner.r#type.as_str(), inner.required), ("[Int]", false));
+    let scalar = inner.scalar.as_ref().unwrap();
+    assert_eq!((scalar.r#type.as_str(), scalar.required), ("Int", true));
+}
+
+#[test]
+fn locates_unsupported_types() {
+    // Validation reports unknown types before the extractors do
+    let error = parse_unvalidated("type Query {\n  method(arg: Unknown!): String\n}");
+    assert_eq!(error.message, "Unsupported type Unknown");
+    assert_eq!(
+        error.location,
+        Some(Location {
+            line: 2,
+            column: 10
+        })
+    );
+}
+
+#[test]
+fn rejects_imported_query_properties() {
+    let schema = r#"
+type Namespace_Query @imported(uri: "uri", namespace: "Namespace", nativeType: "Query") {
+  prop: String
+}
+"#;
+    let errors = parse_schema(schema).unwrap_err();
+    assert_eq!(
+        errors.0[0].message,
+        "Imported Query types must only have methods. See property: prop"
+    );
+}
+
+#[test]
+fn reports_syntax_errors() {
+    let error = parse_unvalidated("type Query {");
+    assert!(error.message.contains("Parse error"), "{}", error);
+    assert!(error.location.is_some(), "{}", error);
+}
@@ -0,0 +1,411 @@
+//! The `validate-directives` and `validate-types` specs of
+//! `@web3api/schema-parse`, which match on the messages only
+
+use web3api_schema::validate::{directives, types, SchemaValidator};
+use web3api_schema::{parse_schema, parse_schema_with, Location, ParserOptions, SchemaError};
+
+fn validate(validator: SchemaValidator, schema: &str) -> Option<SchemaError> {
+    let options = ParserOptions {
+        validators: vec![validator],
+    };
+    parse_schema_with(schema, &options)
+        .err()
+        .map(|mut errors| errors.0.remove(0))
+}
+
+fn assert_error(
+    validator: SchemaValidator,
+    schema: &str,
+    message: &str,
+    (line, column): (usize, usize),
+) {
+    let error = validate(validator, schema).expect("the schema should be invalid");
+    assert_eq!(error.message, message);
+    assert_eq!(
+        error.location,
+        Some(Location { line, column }),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn supported_directives() {
+    let schema = r#"
+type Query @imports(
+  types: ["Hey"]
+) {
+  func(
+    prop: String!
+  ): String!
+}
+
+type Namespace_Object @imported(
+  uri: "uri",
+  namespace: "Namespace",
+  nativeType: "Object"
+) {
+  prop: String!
+}
+
+type Foo @unknown {
+  prop: Boolean! @anotherUnknown
+}
+"#;
+    assert_error(
+        directives::supported_directives,
+        schema,
+        "Found the following usages of unsupported directives:\n@unknown,\n@anotherUnknown",
+        (18, 10),
+    );
+}
+
+#[test]
+fn imports_directive() {
+    let query_only = r#"
+type Object @imports(
+  types: ["Hey"]
+) {
+  prop: String!
+}
+"#;
+    assert_error(
+        directives::imports_directive,
+        query_only,
+        "@imports directive should only be used on QUERY or MUTATION type definitions, \
+         but it is being used on the following ObjectTypeDefinitions:\nObject",
+        (2, 13),
+    );
+
+    let improper_placement = r#"
+type Object {
+  prop: String! @imports(
+    types: ["Hey"]
+  )
+}
+"#;
+    assert_error(
+        directives::imports_directive,
+        improper_placement,
+        "@imports directive should only be used on QUERY or MUTATION type definitions, \
+         but it is being used in the following location: \
+         definitions -> 0 -> fields -> 0 -> directives -> 0",
+        (3, 17),
+    );
+
+    let incorrect_arguments = r#"
+type Query @imports(
+  typees: ["Hey"]
+) {
+  prop: String!
+}
+"#;
+    assert_error(
+        directives::imports_directive,
+        incorrect_arguments,
+        "@imports directive requires argument 'types' of type [String!]!",
+        (2, 12),
+    );
+
+    let non_strings = "type Query @imports(types: [\"Hey\", 1, true]) { prop: String! }";
+    assert_error(
+        directives::imports_directive,
+        non_strings,
+        "@imports directive's 'types' List values must be of type String, \
+         but found: \n\n -IntValue,\n -BooleanValue",
+        (1, 12),
+    );
+}
+
+#[test]
+fn imported_directive() {
+    let incorrect_arguments = r#"
+type Namespace_Object @imported(
+  urri: "uri",
+  namespace: "Namespace",
+  nativeType: "Object"
+) {
+  prop: String!
+}
+"#;
+    assert_error(
+        directives::imported_directive,
+        incorrect_arguments,
+        "@imported directive is missing the following arguments:\n- uri",
+        (2, 23),
+    );
+
+    let improper_placement = r#"
+type Namespace_Object {
+  prop: String! @imported(
+    uri: "uri",
+    namespace: "Namespace",
+    nativeType: "Object"
+  )
+}
+"#;
+    assert_error(
+        directives::imported_directive,
+        improper_placement,
+        "@imported directive should only be used on object or enum type definitions, \
+         but it is being used in the following location: \
+         definitions -> 0 -> fields -> 0 -> directives -> 0",
+        (3, 17),
+    );
+}
+
+#[test]
+fn type_definitions() {
+    let validator = types::type_definitions;
+    assert_error(
+        validator,
+        "type Subscription {\n  prop: String!\n}",
+        "Subscriptions are not yet supported. Please use Query or Mutation.",
+        (1, 1),
+    );
+    assert_error(
+        validator,
+        "input Custom {\n  prop: String!\n}",
+        "Input type definitions are not supported.\n\
+         Found: input Custom { ... }\n\
+         Please Use: type Custom { ... }",
+        (1, 1),
+    );
+    assert_error(
+        validator,
+        "interface Custom {\n  prop: String!\n}",
+        "Interface type definitions are not supported.\n\
+         Found: interface Custom { ... }\n\
+         Please Use: type Custom { ... }",
+        (1, 1),
+    );
+    assert_error(
+        validator,
+        "type Bar {\n  prop: String!\n}\n\ntype Foo {\n  foo: String\n}\n\nunion FooBar = Bar | Foo",
+        "Union type definitions are not supported.\nFound: union FooBar",
+        (9, 1),
+    );
+    assert_error(
+        validator,
+        "type Bar {\n  prop: String!\n}\n\ntype Bar {\n  other: String!\n}",
+        "Duplicate object type definition found: Bar",
+        (5, 1),
+    );
+    assert_error(
+        validator,
+        "scalar Custom",
+        "Custom scalar types are not supported. Supported scalars: \
+         UInt,UInt8,UInt16,UInt32,UInt64,Int,Int8,Int16,Int32,Int64,String,Boolean,Bytes,BigInt",
+        (1, 1),
+    );
+}
+
+#[test]
+fn property_types() {
+    let validator = types::property_types;
+    let unknown = |object: &str, field: &str, r#type: &str| {
+        format!(
+            "Unknown property type found: type {} {{ {}: {} }}",
+            object, field, r#type
+        )
+    };
+
+    let schema = "type Custom {\n  prop: String!\n  other: Stringg!\n}";
+    assert_error(
+        validator,
+        schema,
+        &unknown("Custom", "other", "Stringg"),
+        (3, 3),
+    );
+
+    let schema = "type Custom {\n  prop: Bar!\n  other: Barr!\n}\n\ntype Bar {\n  prop: Int!\n}";
+    assert_error(
+        validator,
+        schema,
+        &unknown("Custom", "other", "Barr"),
+        (3, 3),
+    );
+
+    let schema =
+        "type Custom {\n  prop: [[Bar]]!\n  other: [[Barr]]!\n}\n\ntype Bar {\n  prop: Int!\n}";
+    assert_error(
+        validator,
+        schema,
+        &unknown("Custom", "other", "Barr"),
+        (3, 3),
+    );
+
+    let schema = "type Custom {\n  prop: Bar!\n}\n\ntype Bar {\n  prop: Intt!\n}";
+    assert_error(validator, schema, &unknown("Bar", "prop", "Intt"), (6, 3));
+
+    let schema = "type Query {\n  method(\n    prop: Bar!\n    other: Barr!\n  ): String!\n}\n\n\
+                  type Bar {\n  prop: Int!\n}";
+    assert_error(
+        validator,
+        schema,
+        &unknown("Query", "method", "Barr"),
+        (2, 3),
+    );
+
+    let schema =
+        "type Query {\n  method(\n    prop: Bar!\n  ): Barr!\n}\n\ntype Bar {\n  prop: Int!\n}";
+    assert_error(
+        validator,
+        schema,
+        &unknown("Query", "method", "Barr"),
+        (2, 3),
+    );
+
+    let schema = "type Queryy {\n  method(\n    prop: Bar!\n  ): String!\n}\n\n\
+                  type Bar {\n  prop: Int!\n}";
+    assert_error(
+        validator,
+        schema,
+        "Methods can only be defined on query types (Mutation, Query).\n\
+         Found: type Queryy { method(prop) }",
+        (3, 5),
+    );
+}
+
+#[test]
+fn imported_query_types_may_have_methods() {
+    let schema = r#"
+type TestImport_Query @imported(
+  uri: "testimport.uri.eth",
+  namespace: "TestImport",
+  nativeType: "Query"
+) {
+  importedMethod(str: String!): String!
+}
+"#;
+    assert_eq!(validate(types::property_types, schema), None);
+}
+
+#[test]
+fn circular_definitions() {
+    let validator = types::circular_definitions;
+    let cycles = |cycles: &[&str]| {
+        let cycles: Vec<_> = cycles
+            .iter()
+            .map(|cycle| format!("\n- {{ {} }}", cycle))
+            .collect();
+        format!(
+            "Graphql cycles are not supported. \nFound: {}",
+            cycles.join(",")
+        )
+    };
+
+    let schema = "type A {\n  prop: B!\n}\n\ntype B {\n  prop: A!\n}";
+    assert_error(
+        validator,
+        schema,
+        &cycles(&["B -[prop]-> A -[prop]-> B"]),
+        (6, 3),
+    );
+
+    let schema = "type A {\n  prop: B!\n}\n\ntype B {\n  prop: C!\n}\n\ntype C {\n  prop: A!\n}";
+    assert_error(
+        validator,
+        schema,
+        &cycles(&["C -[prop]-> A -[prop]-> B -[prop]-> C"]),
+        (10, 3),
+    );
+
+    let schema = r#"
+type A {
+  prop: B!
+  root: D!
+}
+
+type B {
+  prop: C!
+}
+
+type C {
+  prop: A!
+  root: D!
+}
+
+type D {
+  prop: B!
+  root: A!
+}
+"#;
+    assert_error(
+        validator,
+        schema,
+        &cycles(&[
+            "D -[prop]-> B -[prop]-> C -[prop]-> A -[root]-> D",
+            "D -[prop]-> B -[prop]-> C -[root]-> D",
+            "D -[root]-> A -[prop]-> B -[prop]-> C -[root]-> D",
+            "D -[root]-> A -[root]-> D",
+            "C -[prop]-> A -[prop]-> B -[prop]-> C",
+        ]),
+        (17, 3),
+    );
+
+    let schema = r#"
+type TestImport_Object @imported(
+  uri: "testimport.uri.eth",
+  namespace: "TestImport",
+  nativeType: "Object"
+) {
+  prop: String!
+  nested: TestImport_NestedObject!
+}
+
+type TestImport_NestedObject @imported(
+  uri: "testimport.uri.eth",
+  namespace: "TestImport",
+  nativeType: "NestedObject"
+) {
+  foo: [String!]!
+  circular: TestImport_Object!
+}
+"#;
+    assert_error(
+        validator,
+        schema,
+        &cycles(&["TestImport_NestedObject -[circular]-> TestImport_Object \
+             -[nested]-> TestImport_NestedObject"]),
+        (17, 3),
+    );
+}
+
+#[test]
+fn allows_cycles_through_operations_and_nullable_properties() {
+    let validator = types::circular_definitions;
+
+    let schema = r#"
+type Query {
+  method(arg: String!): Query!
+}
+
+type TestImport_Query @imported(
+  uri: "testimport.uri.eth",
+  namespace: "TestImport",
+  nativeType: "Query"
+) {
+  importedMethod(str: String!): TestImport_Query!
+}
+"#;
+    assert_eq!(validate(validator, schema), None);
+
+    let schema = "type A {\n  prop: B!\n}\n\ntype B {\n  prop: A\n}";
+    assert_eq!(validate(validator, schema), None);
+}
+
+#[test]
+fn reports_each_failing_validator() {
+    let schema = "type Foo @unknown {\n  prop: Barr!\n}\n\nunion FooBar = Foo";
+    let errors = parse_schema(schema).unwrap_err().0;
+    let messages: Vec<_> = errors.iter().map(|error| error.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "Found the following usages of unsupported directives:\n@unknown",
+            "Union type definitions are not supported.\nFound: union FooBar",
+            "Unknown property type found: type Foo { prop: Barr }",
+        ]
+    );
+}
@@ -1 +1,63 @@
+//! Abort API: reports a fatal error, along with its
+//! source location, to the host
 
+use std::panic::{self, PanicHookInfo};
+use std::sync::Once;
+
+#[cfg(target_arch = "wasm32")]
+#[link(wasm_import_module = "w3")]
+extern "C" {
+    // Abort
+    pub fn __w3_abort(
+        msg_ptr: *const u8,
+        msg_len: u32,
+        file_ptr: *const u8,
+        file_len: u32,
+        line: u32,
+        column: u32,
+    );
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::mock::__w3_abort;
+
+/// Helper for aborting
+pub fn w3_abort(msg: &str, file: &str, line: u32, column: u32) {
+    unsafe {
+        __w3_abort(
+            msg.as_ptr(),
+            msg.len() as u32,
+            file.as_ptr(),
+            file.len() as u32,
+            line,
+            column,
+        );
+    }
+}
+
+static SET_PANIC_HOOK: Once = Once::new();
+
+/// Installs `abort_hook` as the panic hook. Calling
+/// this more than once has no effect.
+pub fn set_panic_hook() {
+    SET_PANIC_HOOK.call_once(|| panic::set_hook(Box::new(abort_hook)));
+}
+
+/// Panic hook that forwards the panic message and
+/// location to `__w3_abort`, so a panicking module shows
+/// up in the client as an abort instead of an opaque trap
+pub fn abort_hook(info: &PanicHookInfo) {
+    let payload = info.payload();
+    let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.as_str()
+    } else {
+        "panic occurred"
+    };
+
+    match info.location() {
+        Some(location) => w3_abort(msg, location.file(), location.line(), location.column()),
+        None => w3_abort(msg, "unknown", 0, 0),
+    }
+}
@@ -1 +1,221 @@
+//! Invoke API: dispatches `_w3_invoke` calls from the host
+//! to the module's registered query/mutation methods
 
+#[cfg(target_arch = "wasm32")]
+use crate::abort::set_panic_hook;
+use crate::{encode_with_context, Context, ReadDecoder, W3Read, W3Write};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+#[cfg(target_arch = "wasm32")]
+#[link(wasm_import_module = "w3")]
+extern "C" {
+    // Get Invoke Arguments
+    pub fn __w3_invoke_args(method_ptr: *mut u8, args_ptr: *mut u8);
+
+    // Set Invoke Result
+    pub fn __w3_invoke_result(ptr: *const u8, len: u32);
+
+    // Set Invoke Error
+    pub fn __w3_invoke_error(ptr: *const u8, len: u32);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::mock::{__w3_invoke_error, __w3_invoke_result, read_invoke_args};
+
+/// An invokable function. Any error it returns is reported
+/// to the host through `__w3_invoke_error`. Since the registry
+/// is per thread, it need not be `Send` or `Sync`.
+pub type InvokeFunction = Rc<dyn Fn(&[u8]) -> Result<Vec<u8>, String>>;
+
+thread_local! {
+    // Keep track of all invokable functions. A wasm module has a
+    // single thread; natively, each test's `MockHost` has its own.
+    static INVOKES: RefCell<HashMap<String, InvokeFunction>> = RefCell::new(HashMap::new());
+}
+
+pub fn w3_add_invoke<F, E>(method: &str, func: F)
+where
+    F: Fn(&[u8]) -> Result<Vec<u8>, E> + 'static,
+    E: Display,
+{
+    let invoke: InvokeFunction = Rc::new(move |args: &[u8]| func(args).map_err(|e| e.to_string()));
+    INVOKES.with(|invokes| invokes.borrow_mut().insert(method.to_string(), invoke));
+}
+
+/// Forgets every registered invoke, for a new `MockHost`
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn clear_invokes() {
+    INVOKES.with(|invokes| invokes.borrow_mut().clear());
+}
+
+/// Reads the invoked method and its args, into buffers of
+/// the sizes the host passed to `_w3_invoke`
+#[cfg(target_arch = "wasm32")]
+fn read_invoke_args(method_buf: &mut [u8], args_buf: &mut [u8]) {
+    unsafe { __w3_invoke_args(method_buf.as_mut_ptr(), args_buf.as_mut_ptr()) };
+}
+
+/// Helper for handling `_w3_invoke`. Returns `false` if the
+/// method is unknown or its handler returned an error.
+pub fn w3_invoke(method_size: u32, args_size: u32) -> bool {
+    // Natively, the `MockHost` reports panics itself
+    #[cfg(target_arch = "wasm32")]
+    set_panic_hook();
+
+    let mut method_buf = vec![0u8; method_size as usize];
+    let mut args_buf = vec![0u8; args_size as usize];
+    read_invoke_args(&mut method_buf, &mut args_buf);
+
+    let method = String::from_utf8_lossy(&method_buf);
+    // Release the registry before calling the handler,
+    // so that it may register further invokes itself
+    let func = INVOKES.with(|invokes| invokes.borrow().get(method.as_ref()).cloned());
+
+    let result = match func {
+        Some(func) => func(&args_buf),
+        None => Err(format!("Could not find invoke function \"{}\"", method)),
+    };
+
+    match result {
+        Ok(result) => {
+            unsafe { __w3_invoke_result(result.as_ptr(), result.len() as u32) };
+            true
+        }
+        Err(message) => {
+            unsafe { __w3_invoke_error(message.as_ptr(), message.len() as u32) };
+            false
+        }
+    }
+}
+
+/// Deserializes `args`, calls `func` with them and serializes its
+/// result. `module_type` is "query" or "mutation". This is the body
+/// of every wrapped method generated by `#[w3_module]`.
+pub fn wrap_invoke<In, Out, E, F>(
+    module_type: &str,
+    method: &str,
+    args: &[u8],
+    func: F,
+) -> Result<Vec<u8>, String>
+where
+    In: W3Read,
+    Out: W3Write,
+    E: Display,
+    F: FnOnce(In) -> Result<Out, E>,
+{
+    let context =
+        Context::with_description(&format!("Deserializing {}-type: {}", module_type, method));
+    let mut reader = ReadDecoder::with_context(args, context);
+    let input = In::read(&mut reader).map_err(|e| e.to_string())?;
+
+    let result = func(input).map_err(|e| e.to_string())?;
+
+    let context =
+        Context::with_description(&format!("Serializing {}-type: {}", module_type, method));
+    Ok(encode_with_context(&result, context))
+}
+
+// A crate may hold both modules; each is built on its own
+// with the runtime's `query` or `mutation` feature enabled.
+#[cfg(all(feature = "query", feature = "mutation"))]
+compile_error!(
+    "the `query` and `mutation` features select which module's wasm \
+     is being built and cannot be enabled together"
+);
+
+/// Exports a module's `_w3_init`/`_w3_invoke` under their own names.
+/// Without a `query` or `mutation` feature, only wasm builds export
+/// them, so that a crate holding both modules still builds natively,
+/// e.g. to be tested with the `MockHost`; either way, they stay
+/// callable from Rust.
+#[doc(hidden)]
+#[cfg(any(feature = "query", feature = "mutation"))]
+#[macro_export]
+macro_rules! __w3_exports {
+    ($($item:item)*) => { $(#[no_mangle] $item)* };
+}
+
+#[doc(hidden)]
+#[cfg(not(any(feature = "query", feature = "mutation")))]
+#[macro_export]
+macro_rules! __w3_exports {
+    ($($item:item)*) => { $(#[cfg_attr(target_arch = "wasm32", no_mangle)] $item)* };
+}
+
+/// Emits the `_w3_init`/`_w3_invoke` exports of a query module,
+/// unless the `mutation` module is the one being built.
+#[doc(hidden)]
+#[cfg(not(feature = "mutation"))]
+#[macro_export]
+macro_rules! __w3_query_exports {
+    ($($item:item)*) => { $crate::__w3_exports! { $($item)* } };
+}
+
+#[doc(hidden)]
+#[cfg(feature = "mutation")]
+#[macro_export]
+macro_rules! __w3_query_exports {
+    ($($item:item)*) => {};
+}
+
+/// Emits the `_w3_init`/`_w3_invoke` exports of a mutation module,
+/// unless the `query` module is the one being built.
+#[doc(hidden)]
+#[cfg(not(feature = "query"))]
+#[macro_export]
+macro_rules! __w3_mutation_exports {
+    ($($item:item)*) => { $crate::__w3_exports! { $($item)* } };
+}
+
+#[doc(hidden)]
+#[cfg(feature = "query")]
+#[macro_export]
+macro_rules! __w3_mutation_exports {
+    ($($item:item)*) => {};
+}
+
+/// Exports a query module's `_w3_init`, calling `$init` to register
+/// its methods, and `_w3_invoke`, as `#[w3_module(query)]` does. For
+/// schema bindings, `$init` is their entry's `w3_init` for the type
+/// implementing the module's methods:
+///
+/// ```ignore
+/// web3api_wasm_rs::w3_query_exports!(query::entry::w3_init::<Module>);
+/// ```
+///
+/// Nothing is exported when the `mutation` feature is enabled.
+#[macro_export]
+macro_rules! w3_query_exports {
+    ($init:expr) => {
+        $crate::__w3_query_exports! {
+            pub extern "C" fn _w3_init() {
+                $init()
+            }
+
+            pub extern "C" fn _w3_invoke(method_size: u32, args_size: u32) -> bool {
+                $crate::w3_invoke(method_size, args_size)
+            }
+        }
+    };
+}
+
+/// Exports a mutation module's `_w3_init` and `_w3_invoke`, as
+/// `w3_query_exports!` does for a query module. Nothing is
+/// exported when the `query` feature is enabled.
+#[macro_export]
+macro_rules! w3_mutation_exports {
+    ($init:expr) => {
+        $crate::__w3_mutation_exports! {
+            pub extern "C" fn _w3_init() {
+                $init()
+            }
+
+            pub extern "C" fn _w3_invoke(method_size: u32, args_size: u32) -> bool {
+                $crate::w3_invoke(method_size, args_size)
+            }
+        }
+    };
+}
@@ -1,11 +1,36 @@
 pub mod abort;
 pub mod invoke;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod mock;
 pub mod msgpack;
 pub mod subinvoke;
 
+pub use abort::w3_abort;
+pub use invoke::{w3_add_invoke, w3_invoke, wrap_invoke};
+pub use subinvoke::{subinvoke, w3_subinvoke};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use mock::MockHost;
+
 pub use msgpack::{
-    context::Context, read::Read, read_decoder::ReadDecoder, write::Write,
-    write_encoder::WriteEncoder, write_sizer::WriteSizer,
+    bytes::Bytes,
+    context::{Context, Contextual},
+    enums::{read_enum, write_enum, EnumError, InvalidEnum, W3Enum},
+    object::{read_object, UnknownFieldPolicy, W3Object},
+    object_view::ObjectView,
+    read::{Read, W3Read},
+    read_decoder::ReadDecoder,
+    required_fields::{FieldKind, RequiredFields},
+    value::Value,
+    write::{W3Write, Write},
+    write_encoder::{encode, encode_with_context, WriteEncoder},
+    write_sizer::{W3Size, WriteSizer},
 };
 
+pub use web3api_wasm_rs_macros::{w3_import, w3_module, W3Enum};
+
+// The `BigInt` scalar, for generated bindings and modules
+// that do not depend on `num-bigint` themselves
+pub use num_bigint::BigInt;
+
 pub type Result = std::result::Result<(), failure::Error>;
@@ -0,0 +1,508 @@
+//! MockHost: an in-process stand-in for the Web3API client,
+//! used in place of the `w3` imports on non-wasm targets so that
+//! modules can be unit-tested with `cargo test`
+
+use crate::abort::abort_hook;
+use crate::invoke::clear_invokes;
+use crate::{encode, Context, Read, ReadDecoder, W3Read, W3Write, Write};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::io::{Error, Result as IoResult};
+use std::path::Path;
+use std::sync::Once;
+use std::{fs, panic, slice};
+
+/// A subinvocation made by the module, and the response it got
+#[derive(Clone, Debug, PartialEq)]
+pub struct Subinvocation {
+    pub uri: String,
+    pub module: String,
+    pub method: String,
+    pub input: Vec<u8>,
+    pub result: Result<Vec<u8>, String>,
+}
+
+/// An abort reported by the module through `__w3_abort`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Abort {
+    pub msg: String,
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl std::fmt::Display for Abort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "__w3_abort: {}\nFile: {}\nLocation: [{},{}]",
+            self.msg, self.file, self.line, self.column
+        )
+    }
+}
+
+impl W3Write for Subinvocation {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_map_length(5);
+        writer.write_string("uri");
+        writer.write_string(&self.uri);
+        writer.write_string("module");
+        writer.write_string(&self.module);
+        writer.write_string("method");
+        writer.write_string(&self.method);
+        writer.write_string("input");
+        let _ = writer.write_bytes(&self.input);
+        match &self.result {
+            Ok(result) => {
+                writer.write_string("result");
+                let _ = writer.write_bytes(result);
+            }
+            Err(error) => {
+                writer.write_string("error");
+                writer.write_string(error);
+            }
+        }
+    }
+}
+
+impl W3Read for Subinvocation {
+    fn read(reader: &mut ReadDecoder) -> IoResult<Self> {
+        let mut subinvocation = Subinvocation {
+            uri: String::new(),
+            module: String::new(),
+            method: String::new(),
+            input: vec![],
+            result: Ok(vec![]),
+        };
+        let num_of_fields = reader.read_map_length()?;
+        for _ in 0..num_of_fields {
+            let field = reader.read_string()?;
+            match field.as_str() {
+                "uri" => subinvocation.uri = reader.read_string()?,
+                "module" => subinvocation.module = reader.read_string()?,
+                "method" => subinvocation.method = reader.read_string()?,
+                "input" => subinvocation.input = reader.read_bytes()?,
+                "result" => subinvocation.result = Ok(reader.read_bytes()?),
+                "error" => subinvocation.result = Err(reader.read_string()?),
+                _ => {
+                    return Err(Error::other(format!(
+                        "Unknown subinvocation fixture field: {}",
+                        field
+                    )))
+                }
+            }
+        }
+        Ok(subinvocation)
+    }
+}
+
+type SubinvokeKey = (String, String, String);
+
+/// Forwards a subinvocation (uri, module, method, input)
+/// to a live host, for recording
+pub type Responder = Box<dyn FnMut(&str, &str, &str, &[u8]) -> Result<Vec<u8>, String>>;
+
+/// How the host answers subinvocations
+#[derive(Default)]
+enum Mode {
+    /// Answer with the responses registered by `on_subinvoke`
+    #[default]
+    Mock,
+    /// Answer with the recording responder
+    Record,
+    /// Answer with the remaining fixture entries, in order
+    Replay(VecDeque<Subinvocation>),
+}
+
+#[derive(Default)]
+struct HostState {
+    method: String,
+    args: Vec<u8>,
+    invoke_result: Option<Vec<u8>>,
+    invoke_error: Option<String>,
+    responses: HashMap<SubinvokeKey, Result<Vec<u8>, String>>,
+    subinvocations: Vec<Subinvocation>,
+    subinvoke_result: Vec<u8>,
+    subinvoke_error: String,
+    abort: Option<Abort>,
+    mode: Mode,
+    responder: Option<Responder>,
+}
+
+thread_local! {
+    static HOST: RefCell<HostState> = RefCell::new(HostState::default());
+    // The live `MockHost` handles on this thread
+    static HANDLES: Cell<usize> = const { Cell::new(0) };
+}
+
+static SET_PANIC_HOOK: Once = Once::new();
+
+/// Reports panics on threads with a live `MockHost` through `__w3_abort`,
+/// as the runtime's panic hook does in wasm. Every panic then goes on
+/// to the previous hook, so that other tests' panics are unaffected.
+fn set_mock_panic_hook() {
+    SET_PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if HANDLES.try_with(Cell::get).unwrap_or(0) > 0 {
+                abort_hook(info);
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// Handle to the current thread's mock host. Each test runs on
+/// its own thread, so tests don't observe each other's state.
+#[derive(Debug)]
+pub struct MockHost {
+    _private: (),
+}
+
+impl MockHost {
+    /// Resets the host state and the invoke registry,
+    /// and returns a handle to them
+    pub fn new() -> Self {
+        HOST.with(|host| *host.borrow_mut() = HostState::default());
+        clear_invokes();
+        HANDLES.with(|handles| handles.set(handles.get() + 1));
+        set_mock_panic_hook();
+        Self { _private: () }
+    }
+
+    /// Sets the method name and msgpack arguments that
+    /// `__w3_invoke_args` hands to the module
+    pub fn set_invoke(&self, method: &str, args: &[u8]) {
+        HOST.with(|host| {
+            let mut host = host.borrow_mut();
+            host.method = method.to_string();
+            host.args = args.to_vec();
+            host.invoke_result = None;
+            host.invoke_error = None;
+        });
+    }
+
+    /// Calls `w3_invoke` the way the client would, with the
+    /// method and arguments given to `set_invoke`
+    pub fn invoke(&self) -> bool {
+        let (method_size, args_size) = HOST.with(|host| {
+            let host = host.borrow();
+            (host.method.len() as u32, host.args.len() as u32)
+        });
+        crate::invoke::w3_invoke(method_size, args_size)
+    }
+
+    /// The payload passed to `__w3_invoke_result`, if any
+    pub fn invoke_result(&self) -> Option<Vec<u8>> {
+        HOST.with(|host| host.borrow().invoke_result.clone())
+    }
+
+    /// The message passed to `__w3_invoke_error`, if any
+    pub fn invoke_error(&self) -> Option<String> {
+        HOST.with(|host| host.borrow().invoke_error.clone())
+    }
+
+    /// Registers the response for subinvocations of `uri`/`module`/`method`
+    pub fn on_subinvoke(
+        &self,
+        uri: &str,
+        module: &str,
+        method: &str,
+        response: Result<Vec<u8>, String>,
+    ) {
+        HOST.with(|host| {
+            host.borrow_mut().responses.insert(
+                (uri.to_string(), module.to_string(), method.to_string()),
+                response,
+            );
+        });
+    }
+
+    /// Every subinvocation made so far, in call order
+    pub fn subinvocations(&self) -> Vec<Subinvocation> {
+        HOST.with(|host| host.borrow().subinvocations.clone())
+    }
+
+    /// The abort reported by the module, if any
+    pub fn abort(&self) -> Option<Abort> {
+        HOST.with(|host| host.borrow().abort.clone())
+    }
+
+    /// Forwards every subinvocation to `responder`, which is
+    /// expected to call a live host (e.g. the test-env), and
+    /// records it for `save_recording`. Subinvocations that the
+    /// responder makes through this host fail, and are not recorded.
+    pub fn record<F>(&self, responder: F)
+    where
+        F: FnMut(&str, &str, &str, &[u8]) -> Result<Vec<u8>, String> + 'static,
+    {
+        HOST.with(|host| {
+            let mut host = host.borrow_mut();
+            host.mode = Mode::Record;
+            host.responder = Some(Box::new(responder));
+        });
+    }
+
+    /// Writes every subinvocation made so far to a msgpack fixture file
+    pub fn save_recording<P: AsRef<Path>>(&self, path: P) -> IoResult<()> {
+        // An array of the subinvocations' maps
+        fs::write(path, encode(&self.subinvocations()))
+    }
+
+    /// Answers subinvocations from a fixture written by `save_recording`.
+    /// Subinvocations must arrive in the recorded order, with the recorded
+    /// input; any other subinvocation panics.
+    pub fn replay<P: AsRef<Path>>(&self, path: P) -> IoResult<()> {
+        let buffer = fs::read(path)?;
+        let context = Context::with_description("Deserializing subinvoke fixture");
+        let mut reader = ReadDecoder::with_context(&buffer, context);
+        let length = reader.read_array_length()?;
+        let mut fixture = VecDeque::with_capacity(reader.capacity_for(length));
+        for _ in 0..length {
+            fixture.push_back(Subinvocation::read(&mut reader)?);
+        }
+        HOST.with(|host| host.borrow_mut().mode = Mode::Replay(fixture));
+        Ok(())
+    }
+
+    /// Panics if the replayed fixture has subinvocations left
+    pub fn assert_replayed(&self) {
+        let remaining = HOST.with(|host| match &host.borrow().mode {
+            Mode::Replay(fixture) => fixture
+                .front()
+                .map(|next| (fixture.len(), next.uri.clone(), next.method.clone())),
+            _ => None,
+        });
+        if let Some((len, uri, method)) = remaining {
+            panic!(
+                "MockHost: {} recorded subinvocation(s) were not replayed, next: {}/{}",
+                len, uri, method
+            );
+        }
+    }
+}
+
+impl Default for MockHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MockHost {
+    fn drop(&mut self) {
+        let _ = HANDLES.try_with(|handles| handles.set(handles.get().saturating_sub(1)));
+    }
+}
+
+// Invoke
+
+/// `__w3_invoke_args`, into the buffers `w3_invoke` sized from the
+/// `_w3_invoke` arguments. A real host writes exactly the sizes it
+/// passed to `_w3_invoke`, so any others are a bug in the caller.
+pub(crate) fn read_invoke_args(method_buf: &mut [u8], args_buf: &mut [u8]) {
+    let (method, args) = HOST.with(|host| {
+        let host = host.borrow();
+        (host.method.clone(), host.args.clone())
+    });
+    check_size("_w3_invoke method_size", method_buf.len(), method.len());
+    check_size("_w3_invoke args_size", args_buf.len(), args.len());
+    method_buf.copy_from_slice(method.as_bytes());
+    args_buf.copy_from_slice(&args);
+}
+
+/// Panics unless a buffer of `requested` bytes fits
+/// the `actual` bytes the host has to write
+fn check_size(what: &str, requested: usize, actual: usize) {
+    assert!(
+        requested == actual,
+        "MockHost: {} is {} bytes, but the host has {} to write",
+        what,
+        requested,
+        actual
+    );
+}
+
+pub(crate) unsafe fn __w3_invoke_result(ptr: *const u8, len: u32) {
+    let result = slice::from_raw_parts(ptr, len as usize).to_vec();
+    HOST.with(|host| host.borrow_mut().invoke_result = Some(result));
+}
+
+pub(crate) unsafe fn __w3_invoke_error(ptr: *const u8, len: u32) {
+    let error = String::from_utf8_lossy(slice::from_raw_parts(ptr, len as usize)).into_owned();
+    HOST.with(|host| host.borrow_mut().invoke_error = Some(error));
+}
+
+// Subinvoke
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) unsafe fn __w3_subinvoke(
+    uri_ptr: *const u8,
+    uri_len: u32,
+    module_ptr: *const u8,
+    module_len: u32,
+    method_ptr: *const u8,
+    method_len: u32,
+    input_ptr: *const u8,
+    input_len: u32,
+) -> bool {
+    let to_string =
+        |ptr, len| String::from_utf8_lossy(slice::from_raw_parts(ptr, len as usize)).into_owned();
+    let uri = to_string(uri_ptr, uri_len);
+    let module = to_string(module_ptr, module_len);
+    let method = to_string(method_ptr, method_len);
+    let input = slice::from_raw_parts(input_ptr, input_len as usize).to_vec();
+
+    let (result, recorded) = respond(&uri, &module, &method, &input);
+    HOST.with(|host| {
+        let mut host = host.borrow_mut();
+        match &result {
+            Ok(bytes) => host.subinvoke_result = bytes.clone(),
+            Err(message) => host.subinvoke_error = message.clone(),
+        }
+        if recorded {
+            host.subinvocations.push(Subinvocation {
+                uri,
+                module,
+                method,
+                input,
+                result: result.clone(),
+            });
+        }
+        result.is_ok()
+    })
+}
+
+/// The response to a subinvocation, and whether to record it
+fn respond(uri: &str, module: &str, method: &str, input: &[u8]) -> (Result<Vec<u8>, String>, bool) {
+    let mut responder = None;
+    let mut unexpected = None;
+    let mut nested = false;
+    let result = HOST.with(|host| {
+        let mut host = host.borrow_mut();
+        match &mut host.mode {
+            Mode::Mock => {
+                let key: SubinvokeKey = (uri.to_string(), module.to_string(), method.to_string());
+                host.responses.get(&key).cloned().unwrap_or_else(|| {
+                    Err(format!(
+                        "MockHost: no response registered for {}/{}/{}",
+                        uri, module, method
+                    ))
+                })
+            }
+            Mode::Record => {
+                // Taken out of the host while it runs, so
+                // that it's free to use the host itself
+                responder = host.responder.take();
+                // It is only missing while it runs, i.e. when it
+                // subinvokes through this host instead of the live one.
+                // That call is rejected and left out of the recording,
+                // which replay could not reproduce.
+                nested = responder.is_none();
+                if nested {
+                    Err(format!(
+                        "MockHost: subinvocation of {}/{}/{} from the recording responder",
+                        uri, module, method
+                    ))
+                } else {
+                    Ok(vec![])
+                }
+            }
+            Mode::Replay(fixture) => match fixture.pop_front() {
+                Some(expected)
+                    if expected.uri == uri
+                        && expected.module == module
+                        && expected.method == method
+                        && expected.input == input =>
+                {
+                    expected.result
+                }
+                Some(expected) => {
+                    unexpected = Some(format!(
+                        "expected {}/{}/{} with the recorded input",
+                        expected.uri, expected.module, expected.method
+                    ));
+                    Ok(vec![])
+                }
+                None => {
+                    unexpected = Some("the replay fixture is exhausted".to_string());
+                    Ok(vec![])
+                }
+            },
+        }
+    });
+
+    // Panic only once the host is released, since the
+    // panic hook reports the abort through the host
+    if let Some(reason) = unexpected {
+        panic!(
+            "MockHost: unexpected subinvocation of {}/{}/{}, {}",
+            uri, module, method, reason
+        );
+    }
+
+    match responder {
+        Some(mut responder) => {
+            let result = responder(uri, module, method, input);
+            HOST.with(|host| host.borrow_mut().responder = Some(responder));
+            (result, true)
+        }
+        None => (result, !nested),
+    }
+}
+
+pub(crate) unsafe fn __w3_subinvoke_result_len() -> u32 {
+    HOST.with(|host| host.borrow().subinvoke_result.len() as u32)
+}
+
+/// `__w3_subinvoke_result`, into a buffer sized from
+/// `__w3_subinvoke_result_len`
+pub(crate) fn read_subinvoke_result(buf: &mut [u8]) {
+    let result = HOST.with(|host| host.borrow().subinvoke_result.clone());
+    check_size("the subinvoke result buffer", buf.len(), result.len());
+    buf.copy_from_slice(&result);
+}
+
+pub(crate) unsafe fn __w3_subinvoke_error_len() -> u32 {
+    HOST.with(|host| host.borrow().subinvoke_error.len() as u32)
+}
+
+/// `__w3_subinvoke_error`, into a buffer sized from
+/// `__w3_subinvoke_error_len`
+pub(crate) fn read_subinvoke_error(buf: &mut [u8]) {
+    let error = HOST.with(|host| host.borrow().subinvoke_error.clone());
+    check_size("the subinvoke error buffer", buf.len(), error.len());
+    buf.copy_from_slice(error.as_bytes());
+}
+
+// Abort
+
+pub(crate) unsafe fn __w3_abort(
+    msg_ptr: *const u8,
+    msg_len: u32,
+    file_ptr: *const u8,
+    file_len: u32,
+    line: u32,
+    column: u32,
+) {
+    let abort = Abort {
+        msg: String::from_utf8_lossy(slice::from_raw_parts(msg_ptr, msg_len as usize)).into_owned(),
+        file: String::from_utf8_lossy(slice::from_raw_parts(file_ptr, file_len as usize))
+            .into_owned(),
+        line,
+        column,
+    };
+    let message = abort.to_string();
+    HOST.with(|host| {
+        // The panic hook may run while the host is in use
+        if let Ok(mut host) = host.try_borrow_mut() {
+            host.abort = Some(abort);
+        }
+    });
+
+    // A real host never returns from an abort. Unless we're
+    // already unwinding (i.e. called from the panic hook),
+    // stop the module the same way, bypassing the hook.
+    if !std::thread::panicking() {
+        std::panic::resume_unwind(Box::new(message));
+    }
+}
@@ -0,0 +1,55 @@
+//! `Bytes` is a msgpack `bin` value. A plain `Vec<u8>`
+//! is written as an array of integers instead.
+
+use super::read::{Read, W3Read};
+use super::read_decoder::ReadDecoder;
+use super::write::{W3Write, Write};
+use std::io::Result;
+use std::ops::{Deref, DerefMut};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Bytes {
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(buf: Vec<u8>) -> Self {
+        Self(buf)
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(buf: &[u8]) -> Self {
+        Self(buf.to_vec())
+    }
+}
+
+impl Deref for Bytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+
+impl DerefMut for Bytes {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+}
+
+impl W3Read for Bytes {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        reader.read_bytes().map(Self)
+    }
+}
+
+impl W3Write for Bytes {
+    fn write<W: Write>(&self, writer: &mut W) {
+        let _ = writer.write_bytes(&self.0);
+    }
+}
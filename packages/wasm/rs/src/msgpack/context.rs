@@ -1,8 +1,9 @@
 //! Context stores debug information in a stack, and
 //! prints it in a clear format
 
-use std::io::{Error, ErrorKind, Result};
-use std::str::FromStr;
+use std::fmt;
+use std::io::{Error, Result};
+use std::ops::{Deref, DerefMut};
 
 #[derive(Clone, Debug)]
 pub struct Context {
@@ -12,14 +13,18 @@ pub struct Context {
 
 impl Context {
     pub fn new() -> Self {
+        Self::with_description("context description not set")
+    }
+
+    pub fn with_description(description: &str) -> Self {
         Self {
-            description: String::from_str("context description not set").unwrap(),
+            description: description.to_string(),
             nodes: vec![],
         }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.nodes.len() == 0
+        self.nodes.is_empty()
     }
 
     pub fn get_length(&self) -> i32 {
@@ -36,61 +41,138 @@ impl Context {
     }
 
     pub fn pop(&mut self) -> Result<String> {
-        if self.is_empty() {
-            return Err(Error::new(
-                ErrorKind::Other,
+        match self.nodes.pop() {
+            Some(node) => Ok(node.to_string()),
+            None => Err(Error::other(
                 "Null pointer exception: tried to pop an item from an empty Context stack",
-            ));
+            )),
         }
-        let node = self.nodes.pop().unwrap_or_default();
-        Ok(format!(
-            "{} : {} >> {}",
-            node.node_item, node.node_type, node.node_info
-        ))
     }
 
-    pub fn to_string(&self) -> String {
-        self.print_with_tabs(0, 2)
+    /// A JSON-pointer-like path to the current position, built
+    /// from the node items, e.g. `/objectArray/3/circular/optStr`.
+    /// Consecutive pushes for the same property count once, and
+    /// `array[i]` and `map[i]` items become `i`.
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        let mut previous: Option<&str> = None;
+        for node in &self.nodes {
+            let item = node.node_item.as_str();
+            let index = ["array[", "map["]
+                .iter()
+                .find_map(|prefix| item.strip_prefix(prefix)?.strip_suffix(']'));
+            if index.is_none() && previous == Some(item) {
+                continue;
+            }
+            previous = Some(item);
+            let segment = index.unwrap_or(item);
+            path.push('/');
+            path.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+        }
+        if path.is_empty() {
+            path.push('/');
+        }
+        path
     }
 
     pub fn print_with_context(&self, message: &str) -> String {
-        format!("{} \n {}", message.to_string(), self.print_with_tabs(1, 0))
+        format!("{}\n{}", message, self.print_with_tabs(1, 2))
     }
 
-    fn print_with_tabs(&self, tabs: i32, size: i32) -> String {
-        let width = (tabs + 1) * size;
-        let pad_start = format!("{:width$}", " ", width = width as usize);
-        let pad_end = format!("\n{:width$}", " ", width = (width + 1) as usize);
-
-        let mut result = String::new();
-        result.push_str(&pad_start);
-
-        let ctx = format!("Context: {}", self.description);
-        result.push_str(&ctx);
+    fn print_with_tabs(&self, tabs: usize, size: usize) -> String {
+        let mut result = format!("{:width$}", "", width = size * tabs);
+        result.push_str(&format!("Context: {}", self.description));
 
         if self.is_empty() {
-            result.push_str(&pad_end);
+            let pad = format!("\n{:width$}", "", width = size * (tabs + 1));
+            result.push_str(&pad);
             result.push_str("context stack is empty");
             return result;
         }
-        let len = self.get_length() as usize;
-        for i in (0..len).rev() {
-            let node = &self.nodes[i];
-            result.push_str(&pad_end);
-            let msg = format!(
-                "at {} : {} {}",
-                node.node_item, node.node_type, node.node_info
-            );
-            result.push_str(&msg);
+        for (depth, node) in self.nodes.iter().rev().enumerate() {
+            let pad = format!("\n{:width$}", "", width = size * (tabs + 1 + depth));
+            result.push_str(&pad);
+            result.push_str(&format!("at {}", node));
         }
         result
     }
 }
 
-#[allow(dead_code)]
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.print_with_tabs(0, 2))
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Node {
     node_item: String,
     node_type: String,
     node_info: String,
 }
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.node_item, self.node_type)?;
+        if !self.node_info.is_empty() {
+            write!(f, " >> {}", self.node_info)?;
+        }
+        Ok(())
+    }
+}
+
+/// Implemented by everything that carries a `Context`
+/// (`Context` itself, `ReadDecoder`, `WriteEncoder`)
+pub trait Contextual: Sized {
+    fn context_mut(&mut self) -> &mut Context;
+
+    /// Pushes a node that is popped again when the returned
+    /// guard is dropped, including on an early `?` return.
+    /// The guard derefs to `self`.
+    fn push_scoped(
+        &mut self,
+        node_item: &str,
+        node_type: &str,
+        node_info: &str,
+    ) -> ContextGuard<'_, Self> {
+        self.context_mut().push(node_item, node_type, node_info);
+        ContextGuard { inner: self }
+    }
+}
+
+impl Contextual for Context {
+    fn context_mut(&mut self) -> &mut Context {
+        self
+    }
+}
+
+/// Returned by `Contextual::push_scoped`, pops its node on drop
+pub struct ContextGuard<'a, T: Contextual> {
+    inner: &'a mut T,
+}
+
+impl<T: Contextual> Deref for ContextGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.inner
+    }
+}
+
+impl<T: Contextual> DerefMut for ContextGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner
+    }
+}
+
+impl<T: Contextual> Drop for ContextGuard<'_, T> {
+    fn drop(&mut self) {
+        let _ = self.inner.context_mut().pop();
+    }
+}
@@ -1,317 +1,261 @@
 use super::context::Context;
-use super::utils::throw_index_out_of_range;
+use super::utils::{describe_location, throw_index_out_of_range};
 use super::{BLOCK_MAX_SIZE, E_INVALID_LENGTH};
-use num::FromPrimitive;
 use std::io::{Error, ErrorKind, Result};
-use std::sync::atomic::{AtomicPtr, Ordering};
 
 #[derive(Clone, Debug)]
 pub struct DataView {
-    data_start: u32,
     buffer: Vec<u8>,
     byte_length: i32,
     byte_offset: i32,
+    lead_offset: i32,
     context: Context,
+    // Why the buffer was rejected, for views that fail every access
+    invalid: Option<String>,
 }
 
 impl DataView {
     pub fn new(buf: &[u8]) -> Result<Self> {
-        let byte_offset = 0;
-        let byte_length = buf.len() as i32;
-        let context = Context::new();
+        Self::with_context(buf, Context::new())
+    }
+
+    pub fn with_context(buf: &[u8], context: Context) -> Result<Self> {
+        Self::at_offset(buf, 0, context)
+    }
 
-        if byte_length > BLOCK_MAX_SIZE as i32 || byte_offset + byte_length > buf.len() as i32 {
-            let msg = format!("DataView::new(): {} [byte_length: {} BLOCK_MAX_SIZE: {} byte_offset: {} buffer.byte_length: {} ]",
-            E_INVALID_LENGTH, byte_length.to_string(), BLOCK_MAX_SIZE.to_string(), byte_offset.to_string(), byte_length.to_string());
+    /// A view over all of `buf`, positioned at `byte_offset`,
+    /// so that offsets in errors are relative to `buf`
+    pub fn at_offset(buf: &[u8], byte_offset: usize, context: Context) -> Result<Self> {
+        if buf.len() > BLOCK_MAX_SIZE || byte_offset > buf.len() {
+            let msg = format!(
+                "DataView::new(): {} [byte_length: {} BLOCK_MAX_SIZE: {} byte_offset: {} buffer.byte_length: {} ]",
+                E_INVALID_LENGTH,
+                buf.len(),
+                BLOCK_MAX_SIZE,
+                byte_offset,
+                buf.len()
+            );
             let ctx = context.print_with_context(&msg);
             return Err(Error::new(ErrorKind::Interrupted, ctx));
         }
-        let data_start = buf.iter().fold(0, |result, &bit| (result << 1) ^ bit) as u32;
+        let byte_offset = byte_offset as i32;
         Ok(Self {
-            data_start,
             buffer: buf.to_vec(),
-            byte_length,
+            byte_length: buf.len() as i32,
             byte_offset,
+            lead_offset: byte_offset,
             context,
+            invalid: None,
         })
     }
 
-    pub fn get_bytes(&mut self, length: i32) -> Result<Vec<u8>> {
-        if let Err(error) = self.check_index_in_range("get_bytes", length) {
-            return Err(Error::from(error));
+    /// An empty view whose every access fails with `error`, for
+    /// readers and writers whose buffer was rejected, so that
+    /// the error reaches the caller along with its context
+    pub fn invalid(error: &Error, context: Context) -> Self {
+        Self {
+            buffer: vec![],
+            byte_length: 0,
+            byte_offset: 0,
+            lead_offset: 0,
+            context,
+            invalid: Some(error.to_string()),
         }
-        let buf = self.buffer.as_slice();
+    }
+
+    /// Why the buffer was rejected, if it was
+    pub fn get_error(&self) -> Option<Error> {
+        self.invalid
+            .as_ref()
+            .map(|msg| Error::new(ErrorKind::Interrupted, msg.clone()))
+    }
+
+    pub fn get_context(&self) -> &Context {
+        &self.context
+    }
+
+    pub fn context_mut(&mut self) -> &mut Context {
+        &mut self.context
+    }
+
+    pub fn get_byte_offset(&self) -> i32 {
+        self.byte_offset
+    }
+
+    /// The number of bytes after the current offset
+    pub fn remaining_bytes(&self) -> usize {
+        (self.byte_length - self.byte_offset) as usize
+    }
+
+    /// Reads the lead (format) byte of the next value, remembering
+    /// its offset so that errors can point at it
+    pub fn get_lead_u8(&mut self) -> Result<u8> {
+        self.lead_offset = self.byte_offset;
+        self.get_u8()
+    }
+
+    /// Builds an error for the value whose lead byte was read last,
+    /// with its location and the context stack
+    pub fn error(&self, message: &str) -> Error {
+        let location = describe_location(&self.context, &self.buffer, self.lead_offset);
+        Error::other(
+            self.context
+                .print_with_context(&format!("{} {}", message, location)),
+        )
+    }
+
+    /// The underlying buffer, including any bytes
+    /// that have not been written to yet
+    pub fn get_buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    pub fn into_buffer(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    pub fn get_bytes(&mut self, length: i32) -> Result<Vec<u8>> {
+        self.check_index_in_range("get_bytes", length)?;
         let (b_off, b_len) = (
             self.byte_offset as usize,
             (self.byte_offset + length) as usize,
         );
-        let result = &buf[b_off..b_len];
+        let result = self.buffer[b_off..b_len].to_vec();
         self.byte_offset += length;
-        Ok(result.to_vec())
+        Ok(result)
     }
 
-    pub fn peek_u8(&mut self) -> Result<u8> {
-        if let Err(error) = self.check_index_in_range("peek_u8", 0) {
-            return Err(Error::from(error));
-        }
-        let p = (self.data_start + self.byte_offset as u32) as u8;
-        let result = self.load_from_memory(p);
-        Ok(result.swap_bytes())
+    pub fn peek_u8(&self) -> Result<u8> {
+        self.check_index_in_range("peek_u8", 1)?;
+        Ok(self.buffer[self.byte_offset as usize])
     }
 
     pub fn discard(&mut self, length: i32) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("discard", length) {
-            return Err(Error::from(error));
-        }
+        self.check_index_in_range("discard", length)?;
         self.byte_offset += length;
         Ok(())
     }
 
     pub fn get_f32(&mut self) -> Result<f32> {
-        if let Err(error) = self.check_index_in_range("get_f32", 4) {
-            return Err(Error::from(error));
-        }
-        let p = self.data_start + self.byte_offset as u32;
-        let result = self.load_from_memory(p).swap_bytes();
-
-        self.byte_offset += 4;
-        Ok(result as f32)
+        Ok(f32::from_be_bytes(self.load("get_f32")?))
     }
 
     pub fn get_f64(&mut self) -> Result<f64> {
-        if let Err(error) = self.check_index_in_range("get_f64", 8) {
-            return Err(Error::from(error));
-        }
-        let p = (self.data_start + self.byte_offset as u32) as u64;
-        let result = self.load_from_memory(p).swap_bytes();
-
-        self.byte_offset += 8;
-        Ok(result as f64)
+        Ok(f64::from_be_bytes(self.load("get_f64")?))
     }
 
     pub fn get_i8(&mut self) -> Result<i8> {
-        if let Err(error) = self.check_index_in_range("get_i8", 1) {
-            return Err(Error::from(error));
-        }
-        let p = (self.data_start + self.byte_offset as u32) as i8;
-        let result = self.load_from_memory(p);
-        self.byte_offset += 1;
-        Ok(result.swap_bytes())
+        Ok(i8::from_be_bytes(self.load("get_i8")?))
     }
 
     pub fn get_i16(&mut self) -> Result<i16> {
-        if let Err(error) = self.check_index_in_range("get_i16", 2) {
-            return Err(Error::from(error));
-        }
-        let p = (self.data_start + self.byte_offset as u32) as i16;
-        let result = self.load_from_memory(p);
-        self.byte_offset += 2;
-        Ok(result.swap_bytes())
+        Ok(i16::from_be_bytes(self.load("get_i16")?))
     }
 
     pub fn get_i32(&mut self) -> Result<i32> {
-        if let Err(error) = self.check_index_in_range("get_i32", 4) {
-            return Err(Error::from(error));
-        }
-        let p = (self.data_start + self.byte_offset as u32) as i32;
-        let result = self.load_from_memory(p);
-        self.byte_offset += 4;
-        Ok(result.swap_bytes())
+        Ok(i32::from_be_bytes(self.load("get_i32")?))
     }
 
     pub fn get_i64(&mut self) -> Result<i64> {
-        if let Err(error) = self.check_index_in_range("get_i64", 8) {
-            return Err(Error::from(error));
-        }
-        let p = (self.data_start + self.byte_offset as u32) as i64;
-        let result = self.load_from_memory(p);
-        self.byte_offset += 8;
-        Ok(result.swap_bytes())
+        Ok(i64::from_be_bytes(self.load("get_i64")?))
     }
 
     pub fn get_u8(&mut self) -> Result<u8> {
-        if let Err(error) = self.check_index_in_range("get_u8", 1) {
-            return Err(Error::from(error));
-        }
-        let p = (self.data_start + self.byte_offset as u32) as u8;
-        let result = self.load_from_memory(p);
-        self.byte_offset += 1;
-        Ok(result.swap_bytes())
+        Ok(u8::from_be_bytes(self.load("get_u8")?))
     }
 
     pub fn get_u16(&mut self) -> Result<u16> {
-        if let Err(error) = self.check_index_in_range("get_u16", 2) {
-            return Err(Error::from(error));
-        }
-        let p = (self.data_start + self.byte_offset as u32) as u16;
-        let result = self.load_from_memory(p);
-        self.byte_offset += 2;
-        Ok(result.swap_bytes())
+        Ok(u16::from_be_bytes(self.load("get_u16")?))
     }
 
     pub fn get_u32(&mut self) -> Result<u32> {
-        if let Err(error) = self.check_index_in_range("get_u32", 4) {
-            return Err(Error::from(error));
-        }
-        let p = (self.data_start + self.byte_offset as u32) as u32;
-        let result = self.load_from_memory(p);
-        self.byte_offset += 4;
-        Ok(result.swap_bytes())
+        Ok(u32::from_be_bytes(self.load("get_u32")?))
     }
 
     pub fn get_u64(&mut self) -> Result<u64> {
-        if let Err(error) = self.check_index_in_range("get_u64", 8) {
-            return Err(Error::from(error));
-        }
-        let p = (self.data_start + self.byte_offset as u32) as u64;
-        let result = self.load_from_memory(p);
-        self.byte_offset += 8;
-        Ok(result.swap_bytes())
+        Ok(u64::from_be_bytes(self.load("get_u64")?))
     }
 
     pub fn set_bytes(&mut self, buf: &[u8]) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_bytes", buf.len() as i32) {
-            return Err(Error::from(error));
-        }
-        let src_ptr = buf.iter().fold(0, |result, &bit| (result << 1) ^ bit) as *const i32;
-        let dst_ptr = (self.data_start as i32 + self.byte_offset) as *mut i32;
-        unsafe {
-            std::ptr::copy(src_ptr, dst_ptr, buf.len());
-        }
-        self.byte_offset += buf.len() as i32;
-        Ok(())
+        self.store("set_bytes", buf)
     }
 
     pub fn set_f32(&mut self, value: f32) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_f32", 4) {
-            return Err(Error::from(error));
-        }
-        let ptr = self.data_start + self.byte_offset as u32;
-        let val = value as u32;
-        self.store_to_memory(ptr, val.swap_bytes());
-        self.byte_offset += 4;
-        Ok(())
+        self.store("set_f32", &value.to_be_bytes())
     }
 
     pub fn set_f64(&mut self, value: f64) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_f64", 8) {
-            return Err(Error::from(error));
-        }
-        let ptr = (self.data_start + self.byte_offset as u32) as u64;
-        let val = value as u64;
-        self.store_to_memory(ptr, val.swap_bytes());
-        self.byte_offset += 8;
-        Ok(())
+        self.store("set_f64", &value.to_be_bytes())
     }
 
     pub fn set_i8(&mut self, value: i8) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_i8", 1) {
-            return Err(Error::from(error));
-        }
-        let ptr = (self.data_start + self.byte_offset as u32) as i8;
-        self.store_to_memory(ptr, value.swap_bytes());
-        self.byte_offset += 1;
-        Ok(())
+        self.store("set_i8", &value.to_be_bytes())
     }
 
     pub fn set_i16(&mut self, value: i16) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_i16", 2) {
-            return Err(Error::from(error));
-        }
-        let ptr = (self.data_start + self.byte_offset as u32) as i16;
-        self.store_to_memory(ptr, value.swap_bytes());
-        self.byte_offset += 2;
-        Ok(())
+        self.store("set_i16", &value.to_be_bytes())
     }
 
     pub fn set_i32(&mut self, value: i32) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_i32", 4) {
-            return Err(Error::from(error));
-        }
-        let ptr = (self.data_start + self.byte_offset as u32) as i32;
-        self.store_to_memory(ptr, value.swap_bytes());
-        self.byte_offset += 4;
-        Ok(())
+        self.store("set_i32", &value.to_be_bytes())
     }
 
     pub fn set_i64(&mut self, value: i64) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_i64", 8) {
-            return Err(Error::from(error));
-        }
-        let ptr = (self.data_start + self.byte_offset as u32) as i64;
-        self.store_to_memory(ptr, value.swap_bytes());
-        self.byte_offset += 8;
-        Ok(())
+        self.store("set_i64", &value.to_be_bytes())
     }
 
     pub fn set_u8(&mut self, value: u8) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_u8", 1) {
-            return Err(Error::from(error));
-        }
-        let ptr = (self.data_start + self.byte_offset as u32) as u8;
-        self.store_to_memory(ptr, value.swap_bytes());
-        self.byte_offset += 1;
-        Ok(())
+        self.store("set_u8", &value.to_be_bytes())
     }
 
     pub fn set_u16(&mut self, value: u16) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_u16", 2) {
-            return Err(Error::from(error));
-        }
-        let ptr = (self.data_start + self.byte_offset as u32) as u16;
-        self.store_to_memory(ptr, value.swap_bytes());
-        self.byte_offset += 2;
-        Ok(())
+        self.store("set_u16", &value.to_be_bytes())
     }
 
     pub fn set_u32(&mut self, value: u32) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_u32", 4) {
-            return Err(Error::from(error));
-        }
-        let ptr = self.data_start + self.byte_offset as u32;
-        self.store_to_memory(ptr, value.swap_bytes());
-        self.byte_offset += 4;
-        Ok(())
+        self.store("set_u32", &value.to_be_bytes())
     }
 
     pub fn set_u64(&mut self, value: u64) -> Result<()> {
-        if let Err(error) = self.check_index_in_range("set_u64", 8) {
-            return Err(Error::from(error));
-        }
-        let ptr = (self.data_start + self.byte_offset as u32) as u64;
-        self.store_to_memory(ptr, value.swap_bytes());
-        self.byte_offset += 8;
-        Ok(())
+        self.store("set_u64", &value.to_be_bytes())
     }
 
     pub fn to_string() -> String {
-        format!("[object DataView")
+        "[object DataView]".to_string()
     }
 
-    fn load_from_memory<T: FromPrimitive + Copy>(&mut self, mut p: T) -> T {
-        let val_ptr = AtomicPtr::new(&mut p);
-        let value = val_ptr.load(Ordering::Relaxed);
-        let result = unsafe { value.as_ref().unwrap() };
-        *result
+    /// Reads the next `N` bytes, as stored (big-endian)
+    fn load<const N: usize>(&mut self, method: &str) -> Result<[u8; N]> {
+        self.check_index_in_range(method, N as i32)?;
+        let start = self.byte_offset as usize;
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(&self.buffer[start..start + N]);
+        self.byte_offset += N as i32;
+        Ok(bytes)
     }
 
-    fn store_to_memory<T: FromPrimitive + Copy>(&mut self, mut p: T, mut value: T) {
-        let p = &mut p;
-        let ptr = AtomicPtr::new(p);
-        let value = &mut value;
-        ptr.store(value, Ordering::Relaxed);
+    fn store(&mut self, method: &str, bytes: &[u8]) -> Result<()> {
+        self.check_index_in_range(method, bytes.len() as i32)?;
+        let start = self.byte_offset as usize;
+        self.buffer[start..start + bytes.len()].copy_from_slice(bytes);
+        self.byte_offset += bytes.len() as i32;
+        Ok(())
     }
 
     fn check_index_in_range(&self, method: &str, length: i32) -> Result<()> {
-        if self.byte_offset + length > self.byte_length {
-            let custom = throw_index_out_of_range(
-                self.context.clone(),
+        if let Some(error) = self.get_error() {
+            return Err(error);
+        }
+        if length < 0 || length > self.byte_length - self.byte_offset {
+            let location = describe_location(&self.context, &self.buffer, self.lead_offset);
+            return Err(throw_index_out_of_range(
+                &self.context,
                 method,
                 length,
                 self.byte_offset,
                 self.byte_length,
-            );
-            return Err(Error::new(ErrorKind::Interrupted, custom));
+                &location,
+            ));
         }
         Ok(())
     }
@@ -0,0 +1,92 @@
+//! Enums are written as their int32 value, and read
+//! from either that value or the variant name
+
+use super::read::Read;
+use super::read_decoder::ReadDecoder;
+use super::write::Write;
+use std::error;
+use std::fmt;
+use std::io::{Error, ErrorKind, Result};
+
+/// A schema enum. Values are the variants' positions in
+/// the schema, starting at 0. Usually derived with
+/// `#[derive(W3Enum)]`, which also implements
+/// `W3Read`/`W3Write` through `read_enum`/`write_enum`.
+pub trait W3Enum: Sized + Copy {
+    /// The enum's name in the schema
+    const NAME: &'static str;
+
+    fn from_value(value: i32) -> Option<Self>;
+    fn from_key(key: &str) -> Option<Self>;
+    fn value(self) -> i32;
+    fn key(self) -> &'static str;
+
+    /// Checks that `value` belongs to the enum
+    fn sanitize(value: i32) -> std::result::Result<Self, EnumError> {
+        Self::from_value(value).ok_or_else(|| EnumError::invalid_value(Self::NAME, value))
+    }
+
+    fn get_value(key: &str) -> std::result::Result<Self, EnumError> {
+        Self::from_key(key).ok_or_else(|| EnumError::invalid_key(Self::NAME, key))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvalidEnum {
+    Value(i32),
+    Key(String),
+}
+
+/// An int or name that is not part of the enum. Decoders return
+/// it inside an `io::Error` of kind `InvalidData`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumError {
+    pub enum_name: &'static str,
+    pub invalid: InvalidEnum,
+    message: String,
+}
+
+impl EnumError {
+    pub fn invalid_value(enum_name: &'static str, value: i32) -> Self {
+        Self {
+            enum_name,
+            invalid: InvalidEnum::Value(value),
+            message: format!("Invalid value for enum '{}': {}", enum_name, value),
+        }
+    }
+
+    pub fn invalid_key(enum_name: &'static str, key: &str) -> Self {
+        Self {
+            enum_name,
+            invalid: InvalidEnum::Key(key.to_string()),
+            message: format!("Invalid key for enum '{}': {}", enum_name, key),
+        }
+    }
+
+    /// Adds the reader's location and context to the message
+    fn located(mut self, reader: &ReadDecoder) -> Error {
+        self.message = reader.error(&self.message).to_string();
+        Error::new(ErrorKind::InvalidData, self)
+    }
+}
+
+impl fmt::Display for EnumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl error::Error for EnumError {}
+
+pub fn read_enum<T: W3Enum>(reader: &mut ReadDecoder) -> Result<T> {
+    if reader.is_next_string() {
+        let key = reader.read_string()?;
+        return T::get_value(&key).map_err(|e| e.located(reader));
+    }
+    let value = reader.read_i32()?;
+    T::sanitize(value).map_err(|e| e.located(reader))
+}
+
+pub fn write_enum<T: W3Enum, W: Write>(value: T, writer: &mut W) {
+    writer.write_i32(value.value());
+}
@@ -1,9 +1,15 @@
+pub mod bytes;
 pub mod context;
 pub mod data_view;
+pub mod enums;
 pub mod format;
+pub mod object;
+pub mod object_view;
 pub mod read;
 pub mod read_decoder;
+pub mod required_fields;
 pub mod utils;
+pub mod value;
 pub mod write;
 pub mod write_encoder;
 pub mod write_sizer;
@@ -0,0 +1,84 @@
+//! Object decoding that tolerates schema changes: fields
+//! missing from the input keep their `Default` value, and
+//! each type chooses what happens to fields it does not know
+
+use super::context::Contextual;
+use super::read::{Read, W3Read};
+use super::read_decoder::ReadDecoder;
+use super::required_fields::RequiredFields;
+use super::value::Value;
+use std::io::Result;
+
+/// What `read_object` does with a field the type does not know,
+/// e.g. one added by a newer version of the module
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnknownFieldPolicy {
+    /// Skip the value
+    Ignore,
+    /// Keep the field and its value in `W3Object::extra_mut`
+    Collect,
+    /// Fail with an error naming the field
+    Reject,
+}
+
+/// An object type, read from a msgpack map field by field
+pub trait W3Object: Default {
+    /// The type name used in errors
+    const TYPE_NAME: &'static str;
+
+    const UNKNOWN_FIELDS: UnknownFieldPolicy = UnknownFieldPolicy::Ignore;
+
+    /// Fields that must be present, with their GraphQL types
+    const REQUIRED_FIELDS: &'static [(&'static str, &'static str)] = &[];
+
+    /// Reads the value of `field` into `self`. Returns `Ok(false)`,
+    /// without reading anything, if the field is unknown.
+    fn read_field(&mut self, field: &str, reader: &mut ReadDecoder) -> Result<bool>;
+
+    /// Where unknown fields go under `UnknownFieldPolicy::Collect`
+    fn extra_mut(&mut self) -> Option<&mut Vec<(String, Value)>> {
+        None
+    }
+}
+
+/// Reads a `W3Object`, starting from its `Default` value. Fails
+/// if any of its `REQUIRED_FIELDS` is missing.
+pub fn read_object<T: W3Object>(reader: &mut ReadDecoder) -> Result<T> {
+    let mut object = T::default();
+    let mut required = RequiredFields::properties(T::REQUIRED_FIELDS);
+    let num_of_fields = reader.read_map_length()?;
+
+    for _ in 0..num_of_fields {
+        let field = reader.read_string()?;
+        let mut reader = reader.push_scoped(&field, "unknown", "searching for property type");
+        if object.read_field(&field, &mut reader)? {
+            required.mark(&field);
+            continue;
+        }
+
+        match T::UNKNOWN_FIELDS {
+            UnknownFieldPolicy::Ignore => reader.skip()?,
+            UnknownFieldPolicy::Collect => {
+                let value = Value::read(&mut reader)?;
+                match object.extra_mut() {
+                    Some(extra) => extra.push((field.clone(), value)),
+                    None => {
+                        let custom_error = format!(
+                            "{} collects unknown fields but has no side map for them",
+                            T::TYPE_NAME
+                        );
+                        return Err(reader.error(&custom_error));
+                    }
+                }
+            }
+            UnknownFieldPolicy::Reject => {
+                let custom_error =
+                    format!("Unknown property: '{}' for type {}", field, T::TYPE_NAME);
+                return Err(reader.error(&custom_error));
+            }
+        }
+    }
+
+    required.check(reader.get_context())?;
+    Ok(object)
+}
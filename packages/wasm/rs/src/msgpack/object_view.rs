@@ -0,0 +1,117 @@
+//! ObjectView indexes the fields of an encoded msgpack
+//! map without decoding their values, so that a handler
+//! can read only the fields it needs
+
+use super::context::Context;
+use super::read::{Read, W3Read};
+use super::read_decoder::ReadDecoder;
+use std::collections::HashMap;
+use std::io::{Error, Result};
+use std::ops::Range;
+
+#[derive(Clone, Debug)]
+pub struct ObjectView<'a> {
+    buffer: &'a [u8],
+    context: Context,
+    fields: Vec<(String, Range<usize>)>,
+    index: HashMap<String, usize>,
+}
+
+impl<'a> ObjectView<'a> {
+    pub fn new(buf: &'a [u8]) -> Result<Self> {
+        Self::with_context(buf, Context::new())
+    }
+
+    /// Indexes the map in `buf` in one pass. Only the keys are
+    /// decoded; values are skipped over and their offsets kept.
+    pub fn with_context(buf: &'a [u8], context: Context) -> Result<Self> {
+        let mut reader = ReadDecoder::with_context(buf, context.clone());
+        let num_of_fields = reader.read_map_length()? as usize;
+
+        // Each field takes at least two bytes, its key and value
+        if num_of_fields > reader.remaining_bytes() / 2 {
+            let custom_error = format!(
+                "Map of {} fields is longer than the {} bytes left",
+                num_of_fields,
+                reader.remaining_bytes()
+            );
+            return Err(reader.error(&custom_error));
+        }
+
+        let mut fields = Vec::with_capacity(num_of_fields);
+        let mut index = HashMap::with_capacity(num_of_fields);
+        for _ in 0..num_of_fields {
+            let field = reader.read_string()?;
+            let start = reader.get_byte_offset() as usize;
+            reader.skip()?;
+            let end = reader.get_byte_offset() as usize;
+            // Like the generated deserializers, the last
+            // occurrence of a repeated key wins
+            index.insert(field.clone(), fields.len());
+            fields.push((field, start..end));
+        }
+
+        Ok(Self {
+            buffer: buf,
+            context,
+            fields,
+            index,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn contains(&self, field: &str) -> bool {
+        self.index.contains_key(field)
+    }
+
+    /// The encoded value of `field`, if present
+    pub fn get_raw(&self, field: &str) -> Option<&'a [u8]> {
+        let position = *self.index.get(field)?;
+        Some(&self.buffer[self.fields[position].1.clone()])
+    }
+
+    /// Decodes `field`, which must be present. `type_name` is
+    /// its schema type, e.g. `[String]`, for error messages.
+    pub fn get<T: W3Read>(&self, field: &str, type_name: &str) -> Result<T> {
+        match self.index.get(field) {
+            Some(&position) => self.decode(position, type_name),
+            None => Err(Error::other(format!(
+                "Missing required property: '{}: {}'",
+                field, type_name
+            ))),
+        }
+    }
+
+    /// Decodes `field`, or returns `None` if it is absent
+    pub fn get_optional<T: W3Read>(&self, field: &str, type_name: &str) -> Result<Option<T>> {
+        match self.index.get(field) {
+            Some(&position) => self.decode(position, type_name).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// The fields in encoded order, with their encoded values
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &'a [u8])> + '_ {
+        let buffer = self.buffer;
+        self.fields
+            .iter()
+            .map(move |(field, range)| (field.as_str(), &buffer[range.clone()]))
+    }
+
+    /// Decodes the field at `position` where it lies in the map,
+    /// so that errors give offsets and bytes within the map
+    fn decode<T: W3Read>(&self, position: usize, type_name: &str) -> Result<T> {
+        let (field, range) = &self.fields[position];
+        let mut context = self.context.clone();
+        context.push(field, type_name, "type found, reading property");
+        let mut reader = ReadDecoder::at_offset(self.buffer, range.start, context);
+        T::read(&mut reader)
+    }
+}
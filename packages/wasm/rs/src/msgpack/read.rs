@@ -1,7 +1,14 @@
+use super::read_decoder::ReadDecoder;
 use num_bigint::BigInt;
 use std::collections::HashMap;
+use std::hash::Hash;
 use std::io::Result;
 
+/// A type that can be deserialized from msgpack
+pub trait W3Read: Sized {
+    fn read(reader: &mut ReadDecoder) -> Result<Self>;
+}
+
 pub trait Read {
     fn read_bool(&mut self) -> Result<bool>;
     fn read_i8(&mut self) -> Result<i8>;
@@ -20,33 +27,94 @@ pub trait Read {
     fn read_bytes(&mut self) -> Result<Vec<u8>>;
     fn read_bigint(&mut self) -> Result<BigInt>;
     fn read_array_length(&mut self) -> Result<u32>;
-    fn read_array<T, F>(&mut self, func: F) -> Result<Vec<T>>
+    fn read_array<T, F>(&mut self, item_reader: F) -> Result<Vec<T>>
     where
-        F: FnMut(u32);
+        F: FnMut(&mut Self) -> Result<T>;
     fn read_map_length(&mut self) -> Result<u32>;
-    fn read_map<F, K, V, W>(&mut self, key_fn: F, value_fn: W) -> HashMap<K, V>
+    fn read_map<K, V, F, G>(&mut self, key_reader: F, value_reader: G) -> Result<HashMap<K, V>>
     where
-        F: FnMut(&K),
-        W: FnMut(&V);
-    fn read_nullable_bool(&mut self) -> Option<bool>;
-    fn read_nullable_i8(&mut self) -> Option<i8>;
-    fn read_nullable_i16(&mut self) -> Option<i16>;
-    fn read_nullable_i32(&mut self) -> Option<i32>;
-    fn read_nullable_i64(&mut self) -> Option<i64>;
-    fn read_nullable_u8(&mut self) -> Option<u8>;
-    fn read_nullable_u16(&mut self) -> Option<u16>;
-    fn read_nullable_u32(&mut self) -> Option<u32>;
-    fn read_nullable_u64(&mut self) -> Option<u64>;
-    fn read_nullable_f32(&mut self) -> Option<f32>;
-    fn read_nullable_f64(&mut self) -> Option<f64>;
-    fn read_nullable_string(&mut self) -> Option<String>;
-    fn read_nullable_bytes(&mut self) -> Option<Vec<u8>>;
-    fn read_nullable_bigint(&mut self) -> Option<BigInt>;
-    fn read_nullable_array<T, F>(&mut self, func: F) -> Option<Vec<T>>
+        K: Eq + Hash,
+        F: FnMut(&mut Self) -> Result<K>,
+        G: FnMut(&mut Self) -> Result<V>;
+    fn read_nullable_bool(&mut self) -> Result<Option<bool>>;
+    fn read_nullable_i8(&mut self) -> Result<Option<i8>>;
+    fn read_nullable_i16(&mut self) -> Result<Option<i16>>;
+    fn read_nullable_i32(&mut self) -> Result<Option<i32>>;
+    fn read_nullable_i64(&mut self) -> Result<Option<i64>>;
+    fn read_nullable_u8(&mut self) -> Result<Option<u8>>;
+    fn read_nullable_u16(&mut self) -> Result<Option<u16>>;
+    fn read_nullable_u32(&mut self) -> Result<Option<u32>>;
+    fn read_nullable_u64(&mut self) -> Result<Option<u64>>;
+    fn read_nullable_f32(&mut self) -> Result<Option<f32>>;
+    fn read_nullable_f64(&mut self) -> Result<Option<f64>>;
+    fn read_nullable_string(&mut self) -> Result<Option<String>>;
+    fn read_nullable_bytes(&mut self) -> Result<Option<Vec<u8>>>;
+    fn read_nullable_bigint(&mut self) -> Result<Option<BigInt>>;
+    fn read_nullable_array<T, F>(&mut self, item_reader: F) -> Result<Option<Vec<T>>>
     where
-        F: FnMut(T);
-    fn read_nullable_map<F, K, V, W>(&mut self, key_fn: F, value_fn: W) -> Option<HashMap<K, V>>
+        F: FnMut(&mut Self) -> Result<T>;
+    fn read_nullable_map<K, V, F, G>(
+        &mut self,
+        key_reader: F,
+        value_reader: G,
+    ) -> Result<Option<HashMap<K, V>>>
     where
-        F: FnMut(&K),
-        W: FnMut(&V);
+        K: Eq + Hash,
+        F: FnMut(&mut Self) -> Result<K>,
+        G: FnMut(&mut Self) -> Result<V>;
+}
+
+macro_rules! impl_w3_read {
+    ($($ty:ty => $read:ident),* $(,)?) => {
+        $(
+            impl W3Read for $ty {
+                fn read(reader: &mut ReadDecoder) -> Result<Self> {
+                    reader.$read()
+                }
+            }
+        )*
+    };
+}
+
+impl_w3_read! {
+    bool => read_bool,
+    i8 => read_i8,
+    i16 => read_i16,
+    i32 => read_i32,
+    i64 => read_i64,
+    u8 => read_u8,
+    u16 => read_u16,
+    u32 => read_u32,
+    u64 => read_u64,
+    f32 => read_f32,
+    f64 => read_f64,
+    String => read_string,
+    BigInt => read_bigint,
+}
+
+impl<T: W3Read> W3Read for Option<T> {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        if reader.is_next_nil() {
+            return Ok(None);
+        }
+        T::read(reader).map(Some)
+    }
+}
+
+impl<T: W3Read> W3Read for Box<T> {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        T::read(reader).map(Box::new)
+    }
+}
+
+impl<T: W3Read> W3Read for Vec<T> {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        reader.read_array(T::read)
+    }
+}
+
+impl<K: W3Read + Eq + Hash, V: W3Read> W3Read for HashMap<K, V> {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        reader.read_map(K::read, V::read)
+    }
 }
@@ -1,31 +1,62 @@
-use super::context::Context;
+use super::context::{Context, Contextual};
 use super::data_view::DataView;
 use super::format::Format;
 use super::read::Read;
+use super::E_INDEX_OUT_OF_RANGE;
 use num_bigint::BigInt;
 
 use std::collections::HashMap;
-use std::io::{Error, ErrorKind, Result};
+use std::convert::TryFrom;
+use std::hash::Hash;
+use std::io::{Error, Result};
 use std::str::FromStr;
 
 #[derive(Clone, Debug)]
 pub struct ReadDecoder {
-    context: Context,
     view: DataView,
 }
 
 impl ReadDecoder {
     #[allow(dead_code)]
     pub fn new(buf: &[u8]) -> Self {
-        Self {
-            context: Context::new(),
-            view: DataView::new(buf).unwrap(),
-        }
+        Self::with_context(buf, Context::new())
+    }
+
+    /// A decoder over `buf`. If `buf` is too large to decode,
+    /// the first read returns the error.
+    pub fn with_context(buf: &[u8], context: Context) -> Self {
+        let view = DataView::with_context(buf, context.clone())
+            .unwrap_or_else(|error| DataView::invalid(&error, context));
+        Self { view }
+    }
+
+    /// A decoder over all of `buf`, reading from `byte_offset`
+    /// on, whose errors locate values within `buf`. If the offset
+    /// is past the end, the first read returns the error.
+    pub fn at_offset(buf: &[u8], byte_offset: usize, context: Context) -> Self {
+        let view = DataView::at_offset(buf, byte_offset, context.clone())
+            .unwrap_or_else(|error| DataView::invalid(&error, context));
+        Self { view }
     }
 
     #[allow(dead_code)]
     pub fn get_context(&self) -> &Context {
-        &self.context
+        self.view.get_context()
+    }
+
+    pub fn get_byte_offset(&self) -> i32 {
+        self.view.get_byte_offset()
+    }
+
+    /// The format byte of the next value, without consuming it
+    pub fn peek_format(&self) -> Result<u8> {
+        self.view.peek_u8()
+    }
+
+    /// Builds an error located at the last value read, for
+    /// errors found above the level of single values
+    pub fn error(&self, message: &str) -> Error {
+        self.view.error(message)
     }
 
     pub fn is_next_nil(&mut self) -> bool {
@@ -37,132 +68,92 @@ impl ReadDecoder {
         false
     }
 
-    #[allow(dead_code)]
     pub fn is_next_string(&mut self) -> bool {
         let format = self.view.peek_u8().unwrap_or_default();
-        return Format::is_fixed_string(format)
+        Format::is_fixed_string(format)
             || format == Format::STR8
             || format == Format::STR16
-            || format == Format::STR32;
+            || format == Format::STR32
     }
 
-    #[allow(dead_code)]
-    fn skip(&mut self) {
-        // get_size handles discarding `msgpack header` info
-        let mut num_of_objects_to_discard = self.get_size().unwrap_or_default();
-        while num_of_objects_to_discard > 0 {
-            let _ = self.get_size().unwrap(); // discard next object
-            num_of_objects_to_discard -= 1;
+    /// Skips the next value, including everything nested in it
+    pub fn skip(&mut self) -> Result<()> {
+        // get_size discards the value's own bytes and returns the
+        // number of nested values that still follow it
+        let mut objects_to_discard: i64 = 1;
+        while objects_to_discard > 0 {
+            objects_to_discard += self.get_size()? - 1;
         }
+        Ok(())
+    }
+
+    /// The exact bytes of the next complete value, which can be
+    /// spliced into other msgpack output with `Write::write_raw`
+    pub fn read_raw_value(&mut self) -> Result<&[u8]> {
+        let start = self.view.get_byte_offset() as usize;
+        self.skip()?;
+        let end = self.view.get_byte_offset() as usize;
+        Ok(&self.view.get_buffer()[start..end])
     }
 
-    fn get_size(&mut self) -> Result<i32> {
-        let lead_byte = self.view.get_u8()?; // will discard one
-        let mut objects_to_discard: i32 = 0;
+    fn get_size(&mut self) -> Result<i64> {
+        let lead_byte = self.view.get_lead_u8()?; // will discard one
+        let mut objects_to_discard = 0;
         // handle for fixed values
-        if Format::is_negative_fixed_int(lead_byte) {
-            // noop, will just discard the leadbyte
-        } else if Format::is_fixed_int(lead_byte) {
+        if Format::is_negative_fixed_int(lead_byte) || Format::is_fixed_int(lead_byte) {
             // noop, will just discard the leadbyte
         } else if Format::is_fixed_string(lead_byte) {
             let str_len = lead_byte & 0x1f;
-            let _ = self.view.discard(str_len as i32)?;
+            self.view.discard(str_len as i32)?;
         } else if Format::is_fixed_array(lead_byte) {
-            objects_to_discard = (lead_byte & Format::FOUR_LEAST_SIG_BITS_IN_BYTE) as i32;
+            objects_to_discard = (lead_byte & Format::FOUR_LEAST_SIG_BITS_IN_BYTE) as i64;
         } else if Format::is_fixed_map(lead_byte) {
-            objects_to_discard = 2 * (lead_byte & Format::FOUR_LEAST_SIG_BITS_IN_BYTE) as i32;
+            objects_to_discard = 2 * (lead_byte & Format::FOUR_LEAST_SIG_BITS_IN_BYTE) as i64;
         } else {
             match lead_byte {
-                Format::NIL => {}
-                Format::TRUE => {}
-                Format::FALSE => {}
-                Format::BIN8 => {
+                Format::NIL | Format::TRUE | Format::FALSE => {}
+                Format::UINT8 | Format::INT8 => self.view.discard(1)?,
+                Format::UINT16 | Format::INT16 => self.view.discard(2)?,
+                Format::FLOAT32 | Format::UINT32 | Format::INT32 => self.view.discard(4)?,
+                Format::FLOAT64 | Format::UINT64 | Format::INT64 => self.view.discard(8)?,
+                // fixext values are a type byte followed by the data
+                Format::FIXEXT1 => self.view.discard(2)?,
+                Format::FIXEXT2 => self.view.discard(3)?,
+                Format::FIXEXT4 => self.view.discard(5)?,
+                Format::FIXEXT8 => self.view.discard(9)?,
+                Format::FIXEXT16 => self.view.discard(17)?,
+                Format::BIN8 | Format::STR8 => {
                     let length = self.view.get_u8()?;
-                    let _ = self.view.discard(length as i32)?;
+                    self.view.discard(length as i32)?;
                 }
-                Format::BIN16 => {
+                Format::BIN16 | Format::STR16 => {
                     let length = self.view.get_u16()?;
-                    let _ = self.view.discard(length as i32)?;
+                    self.view.discard(length as i32)?;
                 }
-                Format::BIN32 => {
+                Format::BIN32 | Format::STR32 => {
                     let length = self.view.get_u32()?;
-                    let _ = self.view.discard(length as i32)?;
-                }
-                Format::FLOAT32 => {
-                    let _ = self.view.discard(4)?;
-                }
-                Format::FLOAT64 => {
-                    let _ = self.view.discard(8)?;
-                }
-                Format::UINT8 => {
-                    let _ = self.view.discard(1)?;
-                }
-                Format::UINT16 => {
-                    let _ = self.view.discard(2)?;
-                }
-                Format::UINT32 => {
-                    let _ = self.view.discard(4)?;
-                }
-                Format::UINT64 => {
-                    let _ = self.view.discard(8)?;
-                }
-                Format::INT8 => {
-                    let _ = self.view.discard(1)?;
-                }
-                Format::INT16 => {
-                    let _ = self.view.discard(2)?;
-                }
-                Format::INT32 => {
-                    let _ = self.view.discard(4)?;
-                }
-                Format::INT64 => {
-                    let _ = self.view.discard(8)?;
-                }
-                Format::FIXEXT1 => {
-                    let _ = self.view.discard(2)?;
-                }
-                Format::FIXEXT2 => {
-                    let _ = self.view.discard(3)?;
-                }
-                Format::FIXEXT4 => {
-                    let _ = self.view.discard(5)?;
-                }
-                Format::FIXEXT8 => {
-                    let _ = self.view.discard(9)?;
+                    self.view.discard(length as i32)?;
                 }
-                Format::FIXEXT16 => {
-                    let _ = self.view.discard(17)?;
+                Format::EXT8 => {
+                    let length = self.view.get_u8()? as u32;
+                    self.discard_ext(length)?;
                 }
-                Format::STR8 => {
-                    let length = self.view.get_u8()?;
-                    let _ = self.view.discard(length as i32)?;
-                }
-                Format::STR16 => {
-                    let length = self.view.get_u16()?;
-                    let _ = self.view.discard(length as i32)?;
+                Format::EXT16 => {
+                    let length = self.view.get_u16()? as u32;
+                    self.discard_ext(length)?;
                 }
-                Format::STR32 => {
+                Format::EXT32 => {
                     let length = self.view.get_u32()?;
-                    let _ = self.view.discard(length as i32)?;
-                }
-                Format::ARRAY16 => {
-                    objects_to_discard = self.view.get_u16()? as i32;
-                }
-                Format::ARRAY32 => {
-                    objects_to_discard = self.view.get_u32()? as i32;
-                }
-                Format::MAP16 => {
-                    objects_to_discard = 2 * (self.view.get_u16()? as i32);
-                }
-                Format::MAP32 => {
-                    objects_to_discard = 2 * (self.view.get_u32()? as i32);
+                    self.discard_ext(length)?;
                 }
+                Format::ARRAY16 => objects_to_discard = self.view.get_u16()? as i64,
+                Format::ARRAY32 => objects_to_discard = self.view.get_u32()? as i64,
+                Format::MAP16 => objects_to_discard = 2 * self.view.get_u16()? as i64,
+                Format::MAP32 => objects_to_discard = 2 * self.view.get_u32()? as i64,
                 _ => {
-                    let custom_error = format!(
-                        "ivalid prefix, bad encoding for val: {}",
-                        lead_byte.to_string()
-                    );
-                    return Err(Error::new(ErrorKind::Other, custom_error));
+                    let custom_error =
+                        format!("invalid prefix, bad encoding for val: {}", lead_byte);
+                    return Err(self.view.error(&custom_error));
                 }
             }
         }
@@ -170,171 +161,125 @@ impl ReadDecoder {
         Ok(objects_to_discard)
     }
 
-    fn get_error_message(lead_byte: u8) -> Result<String> {
-        if Format::is_negative_fixed_int(lead_byte) {
-            return Ok("Found `int`".to_string());
-        } else if Format::is_fixed_int(lead_byte) {
-            return Ok("Found `int`".to_string());
+    /// The number of bytes left to read
+    pub fn remaining_bytes(&self) -> usize {
+        self.view.remaining_bytes()
+    }
+
+    /// The capacity to reserve for `count` values, which the
+    /// remaining bytes bound, as each value takes at least one.
+    /// This keeps a corrupt length from allocating without limit.
+    pub fn capacity_for(&self, count: u32) -> usize {
+        (count as usize).min(self.remaining_bytes())
+    }
+
+    /// Discards the data of an extension value of `length`
+    /// bytes, along with the type byte before it
+    fn discard_ext(&mut self, length: u32) -> Result<()> {
+        match i32::try_from(length).ok().and_then(|l| l.checked_add(1)) {
+            Some(length) => self.view.discard(length),
+            None => {
+                let custom_error =
+                    format!("discard: {} [length: {}]", E_INDEX_OUT_OF_RANGE, length);
+                Err(self.view.error(&custom_error))
+            }
+        }
+    }
+
+    fn get_error_message(lead_byte: u8) -> String {
+        let found = if Format::is_negative_fixed_int(lead_byte) || Format::is_fixed_int(lead_byte) {
+            "int"
         } else if Format::is_fixed_string(lead_byte) {
-            return Ok("Found `string`".to_string());
+            "string"
         } else if Format::is_fixed_array(lead_byte) {
-            return Ok("Found `array`".to_string());
+            "array"
         } else if Format::is_fixed_map(lead_byte) {
-            return Ok("Found `map`".to_string());
+            "map"
         } else {
             match lead_byte {
-                Format::NIL => {
-                    return Ok("Found `nil`".to_string());
-                }
-                Format::TRUE => {
-                    return Ok("Found `bool`".to_string());
-                }
-                Format::FALSE => {
-                    return Ok("Found `bool`".to_string());
-                }
-                Format::BIN8 => {
-                    return Ok("Found `BIN8`".to_string());
-                }
-                Format::BIN16 => {
-                    return Ok("Found `BIN16`".to_string());
-                }
-                Format::BIN32 => {
-                    return Ok("Found `BIN32`".to_string());
-                }
-                Format::FLOAT32 => {
-                    return Ok("Found `float32`".to_string());
-                }
-                Format::FLOAT64 => {
-                    return Ok("Found `float64`".to_string());
-                }
-                Format::UINT8 => {
-                    return Ok("Found `uint8`".to_string());
-                }
-                Format::UINT16 => {
-                    return Ok("Found `uint16`".to_string());
-                }
-                Format::UINT32 => {
-                    return Ok("Found `uint32`".to_string());
-                }
-                Format::UINT64 => {
-                    return Ok("Found `uint64`".to_string());
-                }
-                Format::INT8 => {
-                    return Ok("Found `int8`".to_string());
-                }
-                Format::INT16 => {
-                    return Ok("Found `int16`".to_string());
-                }
-                Format::INT32 => {
-                    return Ok("Found `int32`".to_string());
-                }
-                Format::INT64 => {
-                    return Ok("Found `int64`".to_string());
-                }
-                Format::FIXEXT1 => {
-                    return Ok("Found `FIXEXT1`".to_string());
-                }
-                Format::FIXEXT2 => {
-                    return Ok("Found `FIXEXT2`".to_string());
-                }
-                Format::FIXEXT4 => {
-                    return Ok("Found `FIXEXT4`".to_string());
-                }
-                Format::FIXEXT8 => {
-                    return Ok("Found `FIXEXT8`".to_string());
-                }
-                Format::FIXEXT16 => {
-                    return Ok("Found `FIXEXT16`".to_string());
-                }
-                Format::STR8 => {
-                    return Ok("Found `string`".to_string());
-                }
-                Format::STR16 => {
-                    return Ok("Found `string`".to_string());
-                }
-                Format::STR32 => {
-                    return Ok("Found `string`".to_string());
-                }
-                Format::ARRAY16 => {
-                    return Ok("Found `array`".to_string());
-                }
-                Format::ARRAY32 => {
-                    return Ok("Found `array`".to_string());
-                }
-                Format::MAP16 => {
-                    return Ok("Found `map`".to_string());
-                }
-                Format::MAP32 => {
-                    return Ok("Found `map`".to_string());
-                }
-                _ => {
-                    let custom_error = format!(
-                        "ivalid prefix, bad encoding for val: {}",
-                        lead_byte.to_string()
-                    );
-                    return Err(Error::new(ErrorKind::Other, custom_error));
-                }
+                Format::NIL => "nil",
+                Format::TRUE | Format::FALSE => "bool",
+                Format::BIN8 => "BIN8",
+                Format::BIN16 => "BIN16",
+                Format::BIN32 => "BIN32",
+                Format::FLOAT32 => "float32",
+                Format::FLOAT64 => "float64",
+                Format::UINT8 => "uint8",
+                Format::UINT16 => "uint16",
+                Format::UINT32 => "uint32",
+                Format::UINT64 => "uint64",
+                Format::INT8 => "int8",
+                Format::INT16 => "int16",
+                Format::INT32 => "int32",
+                Format::INT64 => "int64",
+                Format::FIXEXT1 => "FIXEXT1",
+                Format::FIXEXT2 => "FIXEXT2",
+                Format::FIXEXT4 => "FIXEXT4",
+                Format::FIXEXT8 => "FIXEXT8",
+                Format::FIXEXT16 => "FIXEXT16",
+                Format::STR8 | Format::STR16 | Format::STR32 => "string",
+                Format::ARRAY16 | Format::ARRAY32 => "array",
+                Format::MAP16 | Format::MAP32 => "map",
+                _ => return format!("invalid prefix, bad encoding for val: {}", lead_byte),
             }
-        }
+        };
+        format!("Found `{}`.", found)
+    }
+
+    /// Error for a value of the wrong type, e.g.
+    /// "Property must be of type `bool`. Found `int`."
+    fn type_error(&self, expected: &str, lead_byte: u8) -> Error {
+        let custom_error = format!(
+            "Property must be of type `{}`. {}",
+            expected,
+            Self::get_error_message(lead_byte)
+        );
+        self.view.error(&custom_error)
+    }
+}
+
+impl Contextual for ReadDecoder {
+    fn context_mut(&mut self) -> &mut Context {
+        self.view.context_mut()
     }
 }
 
 impl Read for ReadDecoder {
     fn read_bool(&mut self) -> Result<bool> {
-        let value = self.view.get_u8().unwrap_or_default();
-        if value == Format::TRUE {
-            return Ok(true);
-        } else if value == Format::FALSE {
-            return Ok(false);
-        }
-        let mut custom_error = String::new();
-        custom_error.push_str("Property must be of type `bool`");
-        let msg = Self::get_error_message(value).unwrap_or_default();
-        custom_error.push_str(&msg);
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        let value = self.view.get_lead_u8()?;
+        match value {
+            Format::TRUE => Ok(true),
+            Format::FALSE => Ok(false),
+            _ => Err(self.type_error("bool", value)),
+        }
     }
 
     fn read_i8(&mut self) -> Result<i8> {
         let value = self.read_i64()?;
-        if (value <= i8::MAX as i64) && (value >= i8::MIN as i64) {
-            return Ok(value as i8);
-        }
-        let custom_error = format!("integer overflow: value = {}; bits = 8", value.to_string());
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        i8::try_from(value).map_err(|_| {
+            let custom_error = format!("integer overflow: value = {}; bits = 8", value);
+            self.view.error(&custom_error)
+        })
     }
 
     fn read_i16(&mut self) -> Result<i16> {
         let value = self.read_i64()?;
-        if (value <= i16::MAX as i64) && (value >= i16::MIN as i64) {
-            return Ok(value as i16);
-        }
-        let custom_error = format!("integer overflow: value = {}; bits = 16", value.to_string());
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        i16::try_from(value).map_err(|_| {
+            let custom_error = format!("integer overflow: value = {}; bits = 16", value);
+            self.view.error(&custom_error)
+        })
     }
 
     fn read_i32(&mut self) -> Result<i32> {
         let value = self.read_i64()?;
-        if (value <= i32::MAX as i64) && (value >= i32::MIN as i64) {
-            return Ok(value as i32);
-        }
-        let custom_error = format!("integer overflow: value = {}; bits = 32", value.to_string());
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        i32::try_from(value).map_err(|_| {
+            let custom_error = format!("integer overflow: value = {}; bits = 32", value);
+            self.view.error(&custom_error)
+        })
     }
 
     fn read_i64(&mut self) -> Result<i64> {
-        let prefix = self.view.get_u8()?;
+        let prefix = self.view.get_lead_u8()?;
         if Format::is_fixed_int(prefix) {
             return Ok(prefix as i64);
         }
@@ -346,121 +291,69 @@ impl Read for ReadDecoder {
             Format::INT16 => Ok(self.view.get_i16()? as i64),
             Format::INT32 => Ok(self.view.get_i32()? as i64),
             Format::INT64 => Ok(self.view.get_i64()?),
-            _ => {
-                let mut custom_error = String::from("Property must be of type `int`");
-                let msg = Self::get_error_message(prefix).unwrap_or_default();
-                custom_error.push_str(&msg);
-                Err(Error::new(
-                    ErrorKind::Other,
-                    self.context.print_with_context(&custom_error),
-                ))
-            }
+            _ => Err(self.type_error("int", prefix)),
         }
     }
 
     fn read_u8(&mut self) -> Result<u8> {
         let value = self.read_u64()?;
-        if (value <= u8::MAX as u64) && (value >= u8::MIN as u64) {
-            return Ok(value as u8);
-        }
-        let custom_error = format!(
-            "unsigned integer overflow: value = {}; bits = 8",
-            value.to_string()
-        );
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        u8::try_from(value).map_err(|_| {
+            let custom_error = format!("unsigned integer overflow: value = {}; bits = 8", value);
+            self.view.error(&custom_error)
+        })
     }
 
     fn read_u16(&mut self) -> Result<u16> {
         let value = self.read_u64()?;
-        if (value <= u16::MAX as u64) && (value >= u16::MIN as u64) {
-            return Ok(value as u16);
-        }
-        let custom_error = format!(
-            "unsigned integer overflow: value = {}; bits = 16",
-            value.to_string()
-        );
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        u16::try_from(value).map_err(|_| {
+            let custom_error = format!("unsigned integer overflow: value = {}; bits = 16", value);
+            self.view.error(&custom_error)
+        })
     }
 
     fn read_u32(&mut self) -> Result<u32> {
         let value = self.read_u64()?;
-        if (value <= u32::MAX as u64) && (value >= u32::MIN as u64) {
-            return Ok(value as u32);
-        }
-        let custom_error = format!(
-            "unsigned integer overflow: value = {}; bits = 32",
-            value.to_string()
-        );
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        u32::try_from(value).map_err(|_| {
+            let custom_error = format!("unsigned integer overflow: value = {}; bits = 32", value);
+            self.view.error(&custom_error)
+        })
     }
 
     fn read_u64(&mut self) -> Result<u64> {
-        let prefix = self.view.get_u8()?;
+        let prefix = self.view.get_lead_u8()?;
         if Format::is_fixed_int(prefix) {
             return Ok(prefix as u64);
         } else if Format::is_negative_fixed_int(prefix) {
-            let custom_error = format!(
-                "unsigned integer cannot be negative: prefix = {}",
-                prefix.to_string()
-            );
-            return Err(Error::new(ErrorKind::Other, custom_error));
+            let custom_error = format!("unsigned integer cannot be negative: prefix = {}", prefix);
+            return Err(self.view.error(&custom_error));
         }
         match prefix {
             Format::UINT8 => Ok(self.view.get_u8()? as u64),
             Format::UINT16 => Ok(self.view.get_u16()? as u64),
             Format::UINT32 => Ok(self.view.get_u32()? as u64),
             Format::UINT64 => Ok(self.view.get_u64()?),
-            _ => {
-                let mut custom_error = String::from("Property must be of type `uint`");
-                let msg = Self::get_error_message(prefix).unwrap_or_default();
-                custom_error.push_str(&msg);
-                Err(Error::new(
-                    ErrorKind::Other,
-                    self.context.print_with_context(&custom_error),
-                ))
-            }
+            _ => Err(self.type_error("uint", prefix)),
         }
     }
 
     fn read_f32(&mut self) -> Result<f32> {
-        let prefix = self.view.get_u8()?;
+        let prefix = self.view.get_lead_u8()?;
         if Format::is_float_32(prefix) {
-            return Ok(self.view.get_f32()?);
+            return self.view.get_f32();
         }
-        let mut custom_error = String::from("Property must be of type `float32`");
-        let msg = Self::get_error_message(prefix).unwrap_or_default();
-        custom_error.push_str(&msg);
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        Err(self.type_error("float32", prefix))
     }
 
     fn read_f64(&mut self) -> Result<f64> {
-        let prefix = self.view.get_u8()?;
+        let prefix = self.view.get_lead_u8()?;
         if Format::is_float_64(prefix) {
-            return Ok(self.view.get_f64()?);
+            return self.view.get_f64();
         }
-        let mut custom_error = String::from("Property must be of type `float64`");
-        let msg = Self::get_error_message(prefix).unwrap_or_default();
-        custom_error.push_str(&msg);
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        Err(self.type_error("float64", prefix))
     }
 
     fn read_string_length(&mut self) -> Result<u32> {
-        let lead_byte = self.view.get_u8()?;
+        let lead_byte = self.view.get_lead_u8()?;
         if Format::is_fixed_string(lead_byte) {
             return Ok((lead_byte & 0x1f) as u32);
         }
@@ -470,226 +363,233 @@ impl Read for ReadDecoder {
         match lead_byte {
             Format::STR8 => Ok(self.view.get_u8()? as u32),
             Format::STR16 => Ok(self.view.get_u16()? as u32),
-            Format::STR32 => Ok(self.view.get_u32()?),
-            _ => {
-                let mut custom_error = String::from("Property must be of type `string`");
-                let msg = Self::get_error_message(lead_byte).unwrap_or_default();
-                custom_error.push_str(&msg);
-                Err(Error::new(
-                    ErrorKind::Other,
-                    self.context.print_with_context(&custom_error),
-                ))
-            }
+            Format::STR32 => self.view.get_u32(),
+            _ => Err(self.type_error("string", lead_byte)),
         }
     }
 
     fn read_string(&mut self) -> Result<String> {
         let str_len = self.read_string_length()?;
         let str_bytes = self.view.get_bytes(str_len as i32)?;
-        Ok(String::from_utf8(str_bytes).unwrap_or_default())
+        String::from_utf8(str_bytes).map_err(|e| {
+            let custom_error = format!("Property must be valid UTF-8: {}", e);
+            self.view.error(&custom_error)
+        })
     }
 
     fn read_bytes_length(&mut self) -> Result<u32> {
         if self.is_next_nil() {
             return Ok(0);
         }
-        let lead_byte = self.view.get_u8()?;
-        if Format::is_fixed_string(lead_byte) {
-            return Ok((lead_byte & 0x1f) as u32);
-        }
+        let lead_byte = self.view.get_lead_u8()?;
         if Format::is_fixed_array(lead_byte) {
             return Ok((lead_byte & Format::FOUR_LEAST_SIG_BITS_IN_BYTE) as u32);
         }
         match lead_byte {
-            Format::STR8 => Ok(self.view.get_u8()? as u32),
-            Format::STR16 => Ok(self.view.get_u16()? as u32),
-            Format::STR32 => Ok(self.view.get_u32()?),
-            _ => {
-                let mut custom_error = String::from("Property must be of type `bytes`");
-                let msg = Self::get_error_message(lead_byte).unwrap_or_default();
-                custom_error.push_str(&msg);
-                Err(Error::new(
-                    ErrorKind::Other,
-                    self.context.print_with_context(&custom_error),
-                ))
-            }
+            Format::BIN8 => Ok(self.view.get_u8()? as u32),
+            Format::BIN16 => Ok(self.view.get_u16()? as u32),
+            Format::BIN32 => self.view.get_u32(),
+            _ => Err(self.type_error("bytes", lead_byte)),
         }
     }
 
     fn read_bytes(&mut self) -> Result<Vec<u8>> {
         let array_length = self.read_bytes_length()?;
-        Ok(self.view.get_bytes(array_length as i32)?)
+        self.view.get_bytes(array_length as i32)
     }
 
     fn read_bigint(&mut self) -> Result<BigInt> {
         let s = self.read_string()?;
-        Ok(BigInt::from_str(&s).unwrap_or_default())
+        BigInt::from_str(&s).map_err(|e| {
+            let custom_error = format!("Property must be of type `BigInt`: {} ({:?})", e, s);
+            self.view.error(&custom_error)
+        })
     }
 
     fn read_array_length(&mut self) -> Result<u32> {
-        let lead_byte = self.view.get_u8()?;
+        let lead_byte = self.view.get_lead_u8()?;
         if Format::is_fixed_array(lead_byte) {
             return Ok((lead_byte & Format::FOUR_LEAST_SIG_BITS_IN_BYTE) as u32);
-        } else if lead_byte == Format::ARRAY16 {
-            let r = self.view.get_u16()?;
-            return Ok(r as u32);
-        } else if lead_byte == Format::ARRAY32 {
-            return Ok(self.view.get_u32()?);
-        } else if lead_byte == Format::NIL {
-            return Ok(0);
         }
-        let mut custom_error = String::from("Property must be of type `array`");
-        let msg = Self::get_error_message(lead_byte).unwrap_or_default();
-        custom_error.push_str(&msg);
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        match lead_byte {
+            Format::ARRAY16 => Ok(self.view.get_u16()? as u32),
+            Format::ARRAY32 => self.view.get_u32(),
+            Format::NIL => Ok(0),
+            _ => Err(self.type_error("array", lead_byte)),
+        }
     }
 
-    fn read_array<T, F>(&mut self, _func: F) -> Result<Vec<T>>
+    fn read_array<T, F>(&mut self, mut item_reader: F) -> Result<Vec<T>>
     where
-        F: FnMut(u32),
+        F: FnMut(&mut Self) -> Result<T>,
     {
-        todo!()
+        let size = self.read_array_length()?;
+        let mut a = Vec::with_capacity(self.capacity_for(size));
+        for i in 0..size {
+            let mut reader = self.push_scoped(&format!("array[{}]", i), "", "");
+            a.push(item_reader(&mut reader)?);
+        }
+        Ok(a)
     }
 
     fn read_map_length(&mut self) -> Result<u32> {
-        let lead_byte = self.view.get_u8()?;
+        if self.is_next_nil() {
+            return Ok(0);
+        }
+        let lead_byte = self.view.get_lead_u8()?;
         if Format::is_fixed_map(lead_byte) {
             return Ok((lead_byte & Format::FOUR_LEAST_SIG_BITS_IN_BYTE) as u32);
-        } else if lead_byte == Format::MAP16 {
-            return Ok((self.view.get_u16()?) as u32);
-        } else if lead_byte == Format::MAP32 {
-            return Ok(self.view.get_u32()?);
         }
-        let mut custom_error = String::from("Property must be of type `map`");
-        let msg = Self::get_error_message(lead_byte).unwrap_or_default();
-        custom_error.push_str(&msg);
-        Err(Error::new(
-            ErrorKind::Other,
-            self.context.print_with_context(&custom_error),
-        ))
+        match lead_byte {
+            Format::MAP16 => Ok(self.view.get_u16()? as u32),
+            Format::MAP32 => self.view.get_u32(),
+            _ => Err(self.type_error("map", lead_byte)),
+        }
     }
 
-    fn read_map<F, K, V, W>(&mut self, _key_fn: F, _value_fnn: W) -> HashMap<K, V>
+    fn read_map<K, V, F, G>(
+        &mut self,
+        mut key_reader: F,
+        mut value_reader: G,
+    ) -> Result<HashMap<K, V>>
     where
-        F: FnMut(&K),
-        W: FnMut(&V),
+        K: Eq + Hash,
+        F: FnMut(&mut Self) -> Result<K>,
+        G: FnMut(&mut Self) -> Result<V>,
     {
-        todo!()
+        let size = self.read_map_length()?;
+        // Entries take at least two bytes, a key and a value
+        let mut m = HashMap::with_capacity(self.capacity_for(size) / 2);
+        for i in 0..size {
+            let mut reader = self.push_scoped(&format!("map[{}]", i), "", "");
+            let key = key_reader(&mut reader)?;
+            let value = value_reader(&mut reader)?;
+            m.insert(key, value);
+        }
+        Ok(m)
     }
 
-    fn read_nullable_bool(&mut self) -> Option<bool> {
+    fn read_nullable_bool(&mut self) -> Result<Option<bool>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_bool().unwrap_or_default())
+        self.read_bool().map(Some)
     }
 
-    fn read_nullable_i8(&mut self) -> Option<i8> {
+    fn read_nullable_i8(&mut self) -> Result<Option<i8>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_i8().unwrap_or_default())
+        self.read_i8().map(Some)
     }
 
-    fn read_nullable_i16(&mut self) -> Option<i16> {
+    fn read_nullable_i16(&mut self) -> Result<Option<i16>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_i16().unwrap_or_default())
+        self.read_i16().map(Some)
     }
 
-    fn read_nullable_i32(&mut self) -> Option<i32> {
+    fn read_nullable_i32(&mut self) -> Result<Option<i32>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_i32().unwrap_or_default())
+        self.read_i32().map(Some)
     }
 
-    fn read_nullable_i64(&mut self) -> Option<i64> {
+    fn read_nullable_i64(&mut self) -> Result<Option<i64>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_i64().unwrap_or_default())
+        self.read_i64().map(Some)
     }
 
-    fn read_nullable_u8(&mut self) -> Option<u8> {
+    fn read_nullable_u8(&mut self) -> Result<Option<u8>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_u8().unwrap_or_default())
+        self.read_u8().map(Some)
     }
 
-    fn read_nullable_u16(&mut self) -> Option<u16> {
+    fn read_nullable_u16(&mut self) -> Result<Option<u16>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_u16().unwrap_or_default())
+        self.read_u16().map(Some)
     }
 
-    fn read_nullable_u32(&mut self) -> Option<u32> {
+    fn read_nullable_u32(&mut self) -> Result<Option<u32>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_u32().unwrap_or_default())
+        self.read_u32().map(Some)
     }
 
-    fn read_nullable_u64(&mut self) -> Option<u64> {
+    fn read_nullable_u64(&mut self) -> Result<Option<u64>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_u64().unwrap_or_default())
+        self.read_u64().map(Some)
     }
 
-    fn read_nullable_f32(&mut self) -> Option<f32> {
+    fn read_nullable_f32(&mut self) -> Result<Option<f32>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_f32().unwrap_or_default())
+        self.read_f32().map(Some)
     }
 
-    fn read_nullable_f64(&mut self) -> Option<f64> {
+    fn read_nullable_f64(&mut self) -> Result<Option<f64>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_f64().unwrap_or_default())
+        self.read_f64().map(Some)
     }
 
-    fn read_nullable_string(&mut self) -> Option<String> {
+    fn read_nullable_string(&mut self) -> Result<Option<String>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_string().unwrap_or_default())
+        self.read_string().map(Some)
     }
 
-    fn read_nullable_bytes(&mut self) -> Option<Vec<u8>> {
+    fn read_nullable_bytes(&mut self) -> Result<Option<Vec<u8>>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_bytes().unwrap_or_default())
+        self.read_bytes().map(Some)
     }
 
-    fn read_nullable_bigint(&mut self) -> Option<BigInt> {
+    fn read_nullable_bigint(&mut self) -> Result<Option<BigInt>> {
         if self.is_next_nil() {
-            return None;
+            return Ok(None);
         }
-        Some(self.read_bigint().unwrap_or_default())
+        self.read_bigint().map(Some)
     }
 
-    fn read_nullable_array<T, F>(&mut self, _func: F) -> Option<Vec<T>>
+    fn read_nullable_array<T, F>(&mut self, item_reader: F) -> Result<Option<Vec<T>>>
     where
-        F: FnMut(T),
+        F: FnMut(&mut Self) -> Result<T>,
     {
-        todo!()
+        if self.is_next_nil() {
+            return Ok(None);
+        }
+        self.read_array(item_reader).map(Some)
     }
 
-    fn read_nullable_map<F, K, V, W>(&mut self, _key_fn: F, _value_fnn: W) -> Option<HashMap<K, V>>
+    fn read_nullable_map<K, V, F, G>(
+        &mut self,
+        key_reader: F,
+        value_reader: G,
+    ) -> Result<Option<HashMap<K, V>>>
     where
-        F: FnMut(&K),
-        W: FnMut(&V),
+        K: Eq + Hash,
+        F: FnMut(&mut Self) -> Result<K>,
+        G: FnMut(&mut Self) -> Result<V>,
     {
-        todo!()
+        if self.is_next_nil() {
+            return Ok(None);
+        }
+        self.read_map(key_reader, value_reader).map(Some)
     }
 }
@@ -0,0 +1,91 @@
+//! RequiredFields tracks which required fields have been
+//! read, and reports all the missing ones in one error
+
+use super::context::Context;
+use std::io::{Error, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A query or mutation method argument
+    Argument,
+    /// An object type property
+    Property,
+}
+
+impl FieldKind {
+    fn noun(self, count: usize) -> &'static str {
+        match (self, count) {
+            (FieldKind::Argument, 1) => "argument",
+            (FieldKind::Argument, _) => "arguments",
+            (FieldKind::Property, 1) => "property",
+            (FieldKind::Property, _) => "properties",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RequiredFields {
+    kind: FieldKind,
+    /// Field name, GraphQL type, and whether it has been read
+    fields: Vec<(&'static str, &'static str, bool)>,
+}
+
+impl RequiredFields {
+    /// `fields` are the required fields' names and GraphQL types,
+    /// e.g. `("uArrayArray", "[[UInt]]")`
+    pub fn new(kind: FieldKind, fields: &[(&'static str, &'static str)]) -> Self {
+        Self {
+            kind,
+            fields: fields
+                .iter()
+                .map(|&(name, type_name)| (name, type_name, false))
+                .collect(),
+        }
+    }
+
+    pub fn arguments(fields: &[(&'static str, &'static str)]) -> Self {
+        Self::new(FieldKind::Argument, fields)
+    }
+
+    pub fn properties(fields: &[(&'static str, &'static str)]) -> Self {
+        Self::new(FieldKind::Property, fields)
+    }
+
+    /// Marks `field` as read. Fields that are not required are ignored.
+    pub fn mark(&mut self, field: &str) {
+        for (name, _, set) in self.fields.iter_mut() {
+            if *name == field {
+                *set = true;
+            }
+        }
+    }
+
+    /// The missing fields, formatted as `name: Type`
+    pub fn missing(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .filter(|(_, _, set)| !set)
+            .map(|(name, type_name, _)| format!("{}: {}", name, type_name))
+            .collect()
+    }
+
+    /// Fails if any required field is missing, listing all of them, e.g.
+    /// "Missing required arguments: 'str: String!', 'u: UInt!'"
+    pub fn check(&self, context: &Context) -> Result<()> {
+        let missing = self.missing();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        let fields = missing
+            .iter()
+            .map(|field| format!("'{}'", field))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let message = format!(
+            "Missing required {}: {}",
+            self.kind.noun(missing.len()),
+            fields
+        );
+        Err(Error::other(context.print_with_context(&message)))
+    }
+}
@@ -9,7 +9,7 @@ static DUMMY: Lazy<Block> = Lazy::new(|| Block { mm_info: 0 });
 #[allow(unused)]
 static OFFSET_C: Lazy<usize> = Lazy::new(|| {
     let base: *const Block = &*DUMMY;
-    let mm_info: *const u32 = unsafe { &DUMMY.mm_info };
+    let mm_info: *const u32 = unsafe { ptr::addr_of!(DUMMY.mm_info) };
     (mm_info as usize) - (base as usize)
 });
 
@@ -28,30 +28,58 @@ pub struct Block {
 
 #[allow(dead_code)]
 fn offset_of() -> usize {
-    return unsafe {
+    unsafe {
         let base = MaybeUninit::<Block>::uninit();
         let base_ptr = base.as_ptr();
         let mm_info = ptr::addr_of!((*base_ptr).mm_info);
         (mm_info as usize) - (base_ptr as usize)
-    };
+    }
 }
 
 pub fn throw_index_out_of_range(
-    context: Context,
+    context: &Context,
     method: &str,
     length: i32,
     byte_offset: i32,
     byte_length: i32,
+    location: &str,
 ) -> Error {
-    let mut ctx = String::from(method);
     let msg = format!(
-        ": {}, [length: {}, byte_offset: {}, byte_length: {}]",
-        E_INDEX_OUT_OF_RANGE,
-        length.to_string(),
-        byte_offset.to_string(),
-        byte_length.to_string()
+        "{}: {} [length: {}, byte_offset: {}, byte_length: {}] {}",
+        method, E_INDEX_OUT_OF_RANGE, length, byte_offset, byte_length, location
     );
-    ctx.push_str(&msg);
-    let error = context.print_with_context(&ctx);
-    Error::new(ErrorKind::Interrupted, error)
+    Error::new(ErrorKind::Interrupted, context.print_with_context(&msg))
+}
+
+/// Describes where decoding failed: the offset of the lead byte
+/// of the offending value, its path, and the bytes around it
+pub fn describe_location(context: &Context, buffer: &[u8], lead_offset: i32) -> String {
+    format!(
+        "[lead_byte_offset: {}, path: {}]\n  {}",
+        lead_offset,
+        context.path(),
+        hex_window(buffer, lead_offset as usize, HEX_WINDOW_RADIUS)
+    )
+}
+
+pub const HEX_WINDOW_RADIUS: usize = 8;
+
+/// Hex dump of up to `radius` bytes either side of `offset`,
+/// with the byte at `offset` in brackets,
+/// e.g. `bytes 4..13: 81 a3 6f 70 74 [c3] 01 02 03`
+pub fn hex_window(buffer: &[u8], offset: usize, radius: usize) -> String {
+    let start = offset.saturating_sub(radius).min(buffer.len());
+    let end = (offset + radius + 1).min(buffer.len());
+    let mut window = format!("bytes {}..{}:", start, end);
+    for (i, byte) in buffer[start..end].iter().enumerate() {
+        if start + i == offset {
+            window.push_str(&format!(" [{:02x}]", byte));
+        } else {
+            window.push_str(&format!(" {:02x}", byte));
+        }
+    }
+    if offset >= buffer.len() {
+        window.push_str(" [end of buffer]");
+    }
+    window
 }
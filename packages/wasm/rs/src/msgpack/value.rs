@@ -0,0 +1,121 @@
+//! Value holds any msgpack value, for data whose
+//! type is not known ahead of time
+
+use super::format::Format;
+use super::read::{Read, W3Read};
+use super::read_decoder::ReadDecoder;
+use super::write::{W3Write, Write};
+use std::io::Result;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    /// Signed formats and negative fixints
+    Int(i64),
+    /// Unsigned formats and positive fixints
+    UInt(u64),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    /// An extension value, kept as encoded
+    Ext(Vec<u8>),
+}
+
+/// How deeply arrays and maps may nest in a `Value`, so that
+/// untrusted input cannot overflow the stack
+pub const MAX_DEPTH: usize = 512;
+
+impl W3Read for Value {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        read_value(reader, 0)
+    }
+}
+
+fn read_value(reader: &mut ReadDecoder, depth: usize) -> Result<Value> {
+    if depth > MAX_DEPTH {
+        let custom_error = format!("Value nested more than {} levels deep", MAX_DEPTH);
+        return Err(reader.error(&custom_error));
+    }
+    let read_item = |reader: &mut ReadDecoder| read_value(reader, depth + 1);
+
+    let format = reader.peek_format()?;
+    if Format::is_fixed_int(format) {
+        return reader.read_u64().map(Value::UInt);
+    }
+    if Format::is_negative_fixed_int(format) {
+        return reader.read_i64().map(Value::Int);
+    }
+    if Format::is_fixed_string(format) {
+        return reader.read_string().map(Value::String);
+    }
+    if Format::is_fixed_array(format) {
+        return reader.read_array(read_item).map(Value::Array);
+    }
+    if Format::is_fixed_map(format) {
+        return read_map(reader, depth);
+    }
+    match format {
+        Format::NIL => {
+            reader.is_next_nil();
+            Ok(Value::Nil)
+        }
+        Format::TRUE | Format::FALSE => reader.read_bool().map(Value::Bool),
+        Format::UINT8 | Format::UINT16 | Format::UINT32 | Format::UINT64 => {
+            reader.read_u64().map(Value::UInt)
+        }
+        Format::INT8 | Format::INT16 | Format::INT32 | Format::INT64 => {
+            reader.read_i64().map(Value::Int)
+        }
+        Format::FLOAT32 => reader.read_f32().map(Value::F32),
+        Format::FLOAT64 => reader.read_f64().map(Value::F64),
+        Format::STR8 | Format::STR16 | Format::STR32 => reader.read_string().map(Value::String),
+        Format::BIN8 | Format::BIN16 | Format::BIN32 => reader.read_bytes().map(Value::Bytes),
+        Format::ARRAY16 | Format::ARRAY32 => reader.read_array(read_item).map(Value::Array),
+        Format::MAP16 | Format::MAP32 => read_map(reader, depth),
+        _ => reader.read_raw_value().map(|raw| Value::Ext(raw.to_vec())),
+    }
+}
+
+fn read_map(reader: &mut ReadDecoder, depth: usize) -> Result<Value> {
+    let size = reader.read_map_length()?;
+    // Entries take at least two bytes, a key and a value
+    let mut entries = Vec::with_capacity(reader.capacity_for(size) / 2);
+    for _ in 0..size {
+        let key = read_value(reader, depth + 1)?;
+        let value = read_value(reader, depth + 1)?;
+        entries.push((key, value));
+    }
+    Ok(Value::Map(entries))
+}
+
+impl W3Write for Value {
+    fn write<W: Write>(&self, writer: &mut W) {
+        match self {
+            Value::Nil => writer.write_nil(),
+            Value::Bool(value) => writer.write_bool(*value),
+            Value::Int(value) => writer.write_i64(*value),
+            Value::UInt(value) => writer.write_u64(*value),
+            Value::F32(value) => writer.write_f32(*value),
+            Value::F64(value) => writer.write_f64(*value),
+            Value::String(value) => writer.write_string(value),
+            Value::Bytes(value) => {
+                // write_bytes would turn an empty buffer into nil
+                writer.write_bytes_length(value.len() as u32);
+                writer.write_raw(value);
+            }
+            Value::Array(items) => items.write(writer),
+            Value::Map(entries) => {
+                writer.write_map_length(entries.len() as u32);
+                for (key, value) in entries {
+                    key.write(writer);
+                    value.write(writer);
+                }
+            }
+            Value::Ext(raw) => writer.write_raw(raw),
+        }
+    }
+}
@@ -3,6 +3,12 @@ use std::collections::HashMap;
 
 pub type Result = std::result::Result<(), failure::Error>;
 
+/// A type that can be serialized to msgpack, by either
+/// a `WriteSizer` or a `WriteEncoder`
+pub trait W3Write {
+    fn write<W: Write>(&self, writer: &mut W);
+}
+
 pub trait Write {
     fn write_nil(&mut self);
     fn write_bool(&mut self, value: bool);
@@ -17,19 +23,22 @@ pub trait Write {
     fn write_f32(&mut self, value: f32);
     fn write_f64(&mut self, value: f64);
     fn write_string_length(&mut self, length: u32);
-    fn write_string(&mut self, value: String);
+    fn write_string(&mut self, value: &str);
     fn write_bytes_length(&mut self, length: u32);
     fn write_bytes(&mut self, buf: &[u8]) -> Result;
-    fn write_bigint(&mut self, value: BigInt);
+    fn write_bigint(&mut self, value: &BigInt);
     fn write_array_length(&mut self, length: u32);
-    fn write_array<T, F>(&mut self, a: &[T], func: F)
+    fn write_array<T, F>(&mut self, a: &[T], item_writer: F)
     where
-        F: FnMut(u32);
+        F: FnMut(&mut Self, &T);
     fn write_map_length(&mut self, length: u32);
-    fn write_map<F, K, V, W>(&mut self, map: HashMap<K, V>, key_fn: F, value_fn: W)
+    fn write_map<K, V, F, G>(&mut self, map: &HashMap<K, V>, key_writer: F, value_writer: G)
     where
-        F: FnMut(&K),
-        W: FnMut(&V);
+        F: FnMut(&mut Self, &K),
+        G: FnMut(&mut Self, &V);
+    /// Writes already encoded msgpack as is, e.g. a value
+    /// taken from `ReadDecoder::read_raw_value`
+    fn write_raw(&mut self, buf: &[u8]);
     fn write_nullable_bool(&mut self, value: Option<bool>) -> Result;
     fn write_nullable_i8(&mut self, value: Option<i8>) -> Result;
     fn write_nullable_i16(&mut self, value: Option<i16>) -> Result;
@@ -41,19 +50,106 @@ pub trait Write {
     fn write_nullable_u64(&mut self, value: Option<u64>) -> Result;
     fn write_nullable_f32(&mut self, value: Option<f32>) -> Result;
     fn write_nullable_f64(&mut self, value: Option<f64>) -> Result;
-    fn write_nullable_string(&mut self, value: Option<String>) -> Result;
-    fn write_nullable_bytes(&mut self, buf: Option<Vec<u8>>) -> Result;
-    fn write_nullable_bigint(&mut self, value: Option<BigInt>) -> Result;
-    fn write_nullable_array<T, F>(&mut self, a: Option<Vec<T>>, func: F) -> Result
+    fn write_nullable_string(&mut self, value: Option<&str>) -> Result;
+    fn write_nullable_bytes(&mut self, buf: Option<&[u8]>) -> Result;
+    fn write_nullable_bigint(&mut self, value: Option<&BigInt>) -> Result;
+    fn write_nullable_array<T, F>(&mut self, a: Option<&[T]>, item_writer: F) -> Result
     where
-        F: FnMut(T);
-    fn write_nullable_map<F, K, V, W>(
+        F: FnMut(&mut Self, &T);
+    fn write_nullable_map<K, V, F, G>(
         &mut self,
-        map: Option<HashMap<K, V>>,
-        key_fn: F,
-        value_fn: W,
+        map: Option<&HashMap<K, V>>,
+        key_writer: F,
+        value_writer: G,
     ) -> Result
     where
-        F: FnMut(&K),
-        W: FnMut(&V);
+        F: FnMut(&mut Self, &K),
+        G: FnMut(&mut Self, &V);
+}
+
+macro_rules! impl_w3_write {
+    ($($ty:ty => $write:ident),* $(,)?) => {
+        $(
+            impl W3Write for $ty {
+                fn write<W: Write>(&self, writer: &mut W) {
+                    writer.$write(*self);
+                }
+            }
+        )*
+    };
+}
+
+impl_w3_write! {
+    bool => write_bool,
+    i8 => write_i8,
+    i16 => write_i16,
+    i32 => write_i32,
+    i64 => write_i64,
+    u8 => write_u8,
+    u16 => write_u16,
+    u32 => write_u32,
+    u64 => write_u64,
+    f32 => write_f32,
+    f64 => write_f64,
+}
+
+impl W3Write for str {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_string(self);
+    }
+}
+
+impl W3Write for String {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_string(self);
+    }
+}
+
+impl W3Write for BigInt {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_bigint(self);
+    }
+}
+
+impl<T: W3Write + ?Sized> W3Write for &T {
+    fn write<W: Write>(&self, writer: &mut W) {
+        (**self).write(writer);
+    }
+}
+
+impl<T: W3Write + ?Sized> W3Write for Box<T> {
+    fn write<W: Write>(&self, writer: &mut W) {
+        (**self).write(writer);
+    }
+}
+
+impl<T: W3Write> W3Write for Option<T> {
+    fn write<W: Write>(&self, writer: &mut W) {
+        match self {
+            Some(value) => value.write(writer),
+            None => writer.write_nil(),
+        }
+    }
+}
+
+impl<T: W3Write> W3Write for [T] {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_array(self, |writer, item| item.write(writer));
+    }
+}
+
+impl<T: W3Write> W3Write for Vec<T> {
+    fn write<W: Write>(&self, writer: &mut W) {
+        self.as_slice().write(writer);
+    }
+}
+
+impl<K: W3Write, V: W3Write> W3Write for HashMap<K, V> {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_map(
+            self,
+            |writer, key| key.write(writer),
+            |writer, value| value.write(writer),
+        );
+    }
 }
@@ -1,27 +1,62 @@
-use super::context::Context;
+use super::context::{Context, Contextual};
 use super::data_view::DataView;
 use super::format::Format;
-use super::write::{Result, Write};
+use super::write::{Result, W3Write, Write};
+use super::write_sizer::W3Size;
 use num_bigint::BigInt;
 use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
 pub struct WriteEncoder {
-    context: Context,
     view: DataView,
 }
 
 impl WriteEncoder {
+    /// An encoder into a copy of `ua`. If `ua` is too large,
+    /// nothing is written and `get_error` returns the error.
     pub fn new(ua: &[u8], context: Context) -> Self {
-        Self {
-            context,
-            view: DataView::new(ua).unwrap(),
-        }
+        let view = DataView::with_context(ua, context.clone())
+            .unwrap_or_else(|error| DataView::invalid(&error, context));
+        Self { view }
+    }
+
+    /// Why the buffer was rejected, if it was
+    pub fn get_error(&self) -> Option<std::io::Error> {
+        self.view.get_error()
     }
 
     #[allow(dead_code)]
     pub fn get_context(&self) -> &Context {
-        &self.context
+        self.view.get_context()
+    }
+
+    /// The encoded bytes. The buffer keeps the length it was
+    /// created with, which is normally taken from a `WriteSizer`.
+    pub fn get_buffer(&self) -> &[u8] {
+        self.view.get_buffer()
+    }
+
+    pub fn into_buffer(self) -> Vec<u8> {
+        self.view.into_buffer()
+    }
+}
+
+/// Encodes `value` into a buffer of exactly its `W3Size`
+pub fn encode<T: W3Write + ?Sized>(value: &T) -> Vec<u8> {
+    encode_with_context(value, Context::new())
+}
+
+/// `encode`, with a context describing what is being encoded
+pub fn encode_with_context<T: W3Write + ?Sized>(value: &T, context: Context) -> Vec<u8> {
+    let buffer = vec![0u8; value.size() as usize];
+    let mut encoder = WriteEncoder::new(&buffer, context);
+    value.write(&mut encoder);
+    encoder.into_buffer()
+}
+
+impl Contextual for WriteEncoder {
+    fn context_mut(&mut self) -> &mut Context {
+        self.view.context_mut()
     }
 }
 
@@ -51,21 +86,17 @@ impl Write for WriteEncoder {
     }
 
     fn write_i64(&mut self, value: i64) {
-        if value >= 0 && (value < 1 << 7) {
+        if (0..1 << 7).contains(&value) {
             let _ = self.view.set_u8(value as u8);
-        } else if value < 0 && value >= -(1 << 5) {
-            if Format::is_negative_fixed_int(value as u8) {
-                let _ = self.view.set_u8(Format::NEGATIVE_FIXINT);
-            } else {
-                let _ = self.view.set_u8(value as u8);
-            }
-        } else if (value <= i8::MAX as i64) && (value >= i8::MIN as i64) {
+        } else if (-(1 << 5)..0).contains(&value) {
+            let _ = self.view.set_u8((value as u8) | Format::NEGATIVE_FIXINT);
+        } else if (i8::MIN as i64..=i8::MAX as i64).contains(&value) {
             let _ = self.view.set_u8(Format::INT8);
             let _ = self.view.set_i8(value as i8);
-        } else if (value <= i16::MAX as i64) && (value >= i16::MIN as i64) {
+        } else if (i16::MIN as i64..=i16::MAX as i64).contains(&value) {
             let _ = self.view.set_u8(Format::INT16);
             let _ = self.view.set_i16(value as i16);
-        } else if (value <= i32::MAX as i64) && (value >= i32::MIN as i64) {
+        } else if (i32::MIN as i64..=i32::MAX as i64).contains(&value) {
             let _ = self.view.set_u8(Format::INT32);
             let _ = self.view.set_i32(value as i32);
         } else {
@@ -116,10 +147,7 @@ impl Write for WriteEncoder {
 
     fn write_string_length(&mut self, length: u32) {
         if length < 32 {
-            if Format::is_fixed_string(length as u8) {
-                let _ = self.view.set_u8(Format::FIXSTR);
-            }
-            let _ = self.view.set_u8(length as u8);
+            let _ = self.view.set_u8((length as u8) | Format::FIXSTR);
         } else if length <= u8::MAX as u32 {
             let _ = self.view.set_u8(Format::STR8);
             let _ = self.view.set_u8(length as u8);
@@ -132,8 +160,8 @@ impl Write for WriteEncoder {
         }
     }
 
-    fn write_string(&mut self, value: String) {
-        let buf = String::as_bytes(&value);
+    fn write_string(&mut self, value: &str) {
+        let buf = value.as_bytes();
         self.write_string_length(buf.len() as u32);
         let _ = self.view.set_bytes(buf);
     }
@@ -152,7 +180,7 @@ impl Write for WriteEncoder {
     }
 
     fn write_bytes(&mut self, buf: &[u8]) -> Result {
-        if buf.len() == 0 {
+        if buf.is_empty() {
             self.write_nil();
             return Ok(());
         }
@@ -161,17 +189,13 @@ impl Write for WriteEncoder {
         Ok(())
     }
 
-    fn write_bigint(&mut self, value: BigInt) {
-        let val_str = value.to_string();
-        self.write_string(val_str);
+    fn write_bigint(&mut self, value: &BigInt) {
+        self.write_string(&value.to_string());
     }
 
     fn write_array_length(&mut self, length: u32) {
         if length < 16 {
-            if Format::is_fixed_array(length as u8) {
-                let _ = self.view.set_u8(Format::FIXARRAY);
-            }
-            let _ = self.view.set_u8(length as u8);
+            let _ = self.view.set_u8((length as u8) | Format::FIXARRAY);
         } else if length <= u16::MAX as u32 {
             let _ = self.view.set_u8(Format::ARRAY16);
             let _ = self.view.set_u16(length as u16);
@@ -181,19 +205,19 @@ impl Write for WriteEncoder {
         }
     }
 
-    fn write_array<T, F>(&mut self, _a: &[T], _func: F)
+    fn write_array<T, F>(&mut self, a: &[T], mut item_writer: F)
     where
-        F: FnMut(u32),
+        F: FnMut(&mut Self, &T),
     {
-        todo!()
+        self.write_array_length(a.len() as u32);
+        for item in a {
+            item_writer(self, item);
+        }
     }
 
     fn write_map_length(&mut self, length: u32) {
         if length < 16 {
-            if Format::is_fixed_map(length as u8) {
-                let _ = self.view.set_u8(Format::FIXMAP);
-            }
-            let _ = self.view.set_u8(length as u8);
+            let _ = self.view.set_u8((length as u8) | Format::FIXMAP);
         } else if length <= u16::MAX as u32 {
             let _ = self.view.set_u8(Format::MAP16);
             let _ = self.view.set_u16(length as u16);
@@ -203,158 +227,161 @@ impl Write for WriteEncoder {
         }
     }
 
-    fn write_map<F, K, V, W>(&mut self, _map: HashMap<K, V>, _key_fnn: F, _value_fn: W)
+    fn write_map<K, V, F, G>(&mut self, map: &HashMap<K, V>, mut key_writer: F, mut value_writer: G)
     where
-        F: FnMut(&K),
-        W: FnMut(&V),
+        F: FnMut(&mut Self, &K),
+        G: FnMut(&mut Self, &V),
     {
-        todo!()
+        self.write_map_length(map.len() as u32);
+        for (key, value) in map {
+            key_writer(self, key);
+            value_writer(self, value);
+        }
+    }
+
+    fn write_raw(&mut self, buf: &[u8]) {
+        let _ = self.view.set_bytes(buf);
     }
 
     fn write_nullable_bool(&mut self, value: Option<bool>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_bool(value),
+            None => self.write_nil(),
         }
-        self.write_bool(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_i8(&mut self, value: Option<i8>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_i8(value),
+            None => self.write_nil(),
         }
-        self.write_i8(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_i16(&mut self, value: Option<i16>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_i16(value),
+            None => self.write_nil(),
         }
-        self.write_i16(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_i32(&mut self, value: Option<i32>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_i32(value),
+            None => self.write_nil(),
         }
-        self.write_i32(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_i64(&mut self, value: Option<i64>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_i64(value),
+            None => self.write_nil(),
         }
-        self.write_i64(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_u8(&mut self, value: Option<u8>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_u8(value),
+            None => self.write_nil(),
         }
-        self.write_u8(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_u16(&mut self, value: Option<u16>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_u16(value),
+            None => self.write_nil(),
         }
-        self.write_u16(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_u32(&mut self, value: Option<u32>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_u32(value),
+            None => self.write_nil(),
         }
-        self.write_u32(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_u64(&mut self, value: Option<u64>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_u64(value),
+            None => self.write_nil(),
         }
-        self.write_u64(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_f32(&mut self, value: Option<f32>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_f32(value),
+            None => self.write_nil(),
         }
-        self.write_f32(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_f64(&mut self, value: Option<f64>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_f64(value),
+            None => self.write_nil(),
         }
-        self.write_f64(value.unwrap_or_default());
         Ok(())
     }
 
-    fn write_nullable_string(&mut self, value: Option<String>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+    fn write_nullable_string(&mut self, value: Option<&str>) -> Result {
+        match value {
+            Some(value) => self.write_string(value),
+            None => self.write_nil(),
         }
-        self.write_string(value.unwrap_or_default());
         Ok(())
     }
 
-    fn write_nullable_bytes(&mut self, buf: Option<Vec<u8>>) -> Result {
-        if buf.is_none() {
-            self.write_nil();
-            return Ok(());
+    fn write_nullable_bytes(&mut self, buf: Option<&[u8]>) -> Result {
+        match buf {
+            Some(buf) => self.write_bytes(buf),
+            None => {
+                self.write_nil();
+                Ok(())
+            }
         }
-        let buf: &[u8] = &buf.unwrap_or_default();
-        let _ = self.write_bytes(buf);
-        Ok(())
     }
 
-    fn write_nullable_bigint(&mut self, value: Option<BigInt>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+    fn write_nullable_bigint(&mut self, value: Option<&BigInt>) -> Result {
+        match value {
+            Some(value) => self.write_bigint(value),
+            None => self.write_nil(),
         }
-        self.write_bigint(value.unwrap_or_default());
         Ok(())
     }
 
-    fn write_nullable_array<T, F>(&mut self, _a: Option<Vec<T>>, _func: F) -> Result
+    fn write_nullable_array<T, F>(&mut self, a: Option<&[T]>, item_writer: F) -> Result
     where
-        F: FnMut(T),
+        F: FnMut(&mut Self, &T),
     {
-        todo!()
+        match a {
+            Some(a) => self.write_array(a, item_writer),
+            None => self.write_nil(),
+        }
+        Ok(())
     }
 
-    fn write_nullable_map<F, K, V, W>(
+    fn write_nullable_map<K, V, F, G>(
         &mut self,
-        _map: Option<HashMap<K, V>>,
-        _key_fn: F,
-        _value_fn: W,
+        map: Option<&HashMap<K, V>>,
+        key_writer: F,
+        value_writer: G,
     ) -> Result
     where
-        F: FnMut(&K),
-        W: FnMut(&V),
+        F: FnMut(&mut Self, &K),
+        G: FnMut(&mut Self, &V),
     {
-        todo!()
+        match map {
+            Some(map) => self.write_map(map, key_writer, value_writer),
+            None => self.write_nil(),
+        }
+        Ok(())
     }
 }
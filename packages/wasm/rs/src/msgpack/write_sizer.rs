@@ -1,4 +1,4 @@
-use crate::{Result, Write};
+use crate::{Result, W3Write, Write};
 use num_bigint::BigInt;
 use std::collections::HashMap;
 
@@ -12,6 +12,24 @@ impl WriteSizer {
     pub fn new() -> Self {
         Self { length: 0 }
     }
+
+    pub fn get_length(&self) -> i32 {
+        self.length
+    }
+}
+
+/// The encoded size of a `W3Write` value, for allocating
+/// the buffer a `WriteEncoder` writes it into
+pub trait W3Size {
+    fn size(&self) -> i32;
+}
+
+impl<T: W3Write + ?Sized> W3Size for T {
+    fn size(&self) -> i32 {
+        let mut sizer = WriteSizer::new();
+        self.write(&mut sizer);
+        sizer.get_length()
+    }
 }
 
 impl Write for WriteSizer {
@@ -36,13 +54,13 @@ impl Write for WriteSizer {
     }
 
     fn write_i64(&mut self, value: i64) {
-        if value >= -(1 << 5) && value < 1 << 7 {
+        if (-(1 << 5)..1 << 7).contains(&value) {
             self.length += 1;
-        } else if value < 1 << 7 && value >= -(1 << 7) {
+        } else if (-(1 << 7)..1 << 7).contains(&value) {
             self.length += 2;
-        } else if value < 1 << 15 && value >= -(1 << 15) {
+        } else if (-(1 << 15)..1 << 15).contains(&value) {
             self.length += 3;
-        } else if value < 1 << 31 && value >= -(1 << 31) {
+        } else if (-(1 << 31)..1 << 31).contains(&value) {
             self.length += 5;
         } else {
             self.length += 9;
@@ -95,7 +113,7 @@ impl Write for WriteSizer {
         }
     }
 
-    fn write_string(&mut self, value: String) {
+    fn write_string(&mut self, value: &str) {
         self.write_string_length(value.len() as u32);
         self.length += value.len() as i32;
     }
@@ -111,7 +129,7 @@ impl Write for WriteSizer {
     }
 
     fn write_bytes(&mut self, value: &[u8]) -> Result {
-        if value.len() == 0 {
+        if value.is_empty() {
             self.length += 1;
             return Ok(());
         }
@@ -120,9 +138,8 @@ impl Write for WriteSizer {
         Ok(())
     }
 
-    fn write_bigint(&mut self, value: BigInt) {
-        let val_str = value.to_string();
-        self.write_string(val_str);
+    fn write_bigint(&mut self, value: &BigInt) {
+        self.write_string(&value.to_string());
     }
 
     fn write_array_length(&mut self, length: u32) {
@@ -135,11 +152,14 @@ impl Write for WriteSizer {
         }
     }
 
-    fn write_array<T, F>(&mut self, _a: &[T], _func: F)
+    fn write_array<T, F>(&mut self, a: &[T], mut item_writer: F)
     where
-        F: FnMut(u32),
+        F: FnMut(&mut Self, &T),
     {
-        todo!()
+        self.write_array_length(a.len() as u32);
+        for item in a {
+            item_writer(self, item);
+        }
     }
 
     fn write_map_length(&mut self, length: u32) {
@@ -152,157 +172,161 @@ impl Write for WriteSizer {
         }
     }
 
-    fn write_map<F, K, V, W>(&mut self, _map: HashMap<K, V>, _key_fn: F, _value_fn: W)
+    fn write_map<K, V, F, G>(&mut self, map: &HashMap<K, V>, mut key_writer: F, mut value_writer: G)
     where
-        F: FnMut(&K),
-        W: FnMut(&V),
+        F: FnMut(&mut Self, &K),
+        G: FnMut(&mut Self, &V),
     {
-        todo!()
+        self.write_map_length(map.len() as u32);
+        for (key, value) in map {
+            key_writer(self, key);
+            value_writer(self, value);
+        }
+    }
+
+    fn write_raw(&mut self, buf: &[u8]) {
+        self.length += buf.len() as i32;
     }
 
     fn write_nullable_bool(&mut self, value: Option<bool>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_bool(value),
+            None => self.write_nil(),
         }
-        self.write_bool(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_i8(&mut self, value: Option<i8>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_i8(value),
+            None => self.write_nil(),
         }
-        self.write_i8(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_i16(&mut self, value: Option<i16>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_i16(value),
+            None => self.write_nil(),
         }
-        self.write_i16(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_i32(&mut self, value: Option<i32>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_i32(value),
+            None => self.write_nil(),
         }
-        self.write_i32(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_i64(&mut self, value: Option<i64>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_i64(value),
+            None => self.write_nil(),
         }
-        self.write_i64(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_u8(&mut self, value: Option<u8>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_u8(value),
+            None => self.write_nil(),
         }
-        self.write_u8(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_u16(&mut self, value: Option<u16>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_u16(value),
+            None => self.write_nil(),
         }
-        self.write_u16(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_u32(&mut self, value: Option<u32>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_u32(value),
+            None => self.write_nil(),
         }
-        self.write_u32(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_u64(&mut self, value: Option<u64>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_u64(value),
+            None => self.write_nil(),
         }
-        self.write_u64(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_f32(&mut self, value: Option<f32>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_f32(value),
+            None => self.write_nil(),
         }
-        self.write_f32(value.unwrap_or_default());
         Ok(())
     }
 
     fn write_nullable_f64(&mut self, value: Option<f64>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+        match value {
+            Some(value) => self.write_f64(value),
+            None => self.write_nil(),
         }
-        self.write_f64(value.unwrap_or_default());
         Ok(())
     }
 
-    fn write_nullable_string(&mut self, value: Option<String>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+    fn write_nullable_string(&mut self, value: Option<&str>) -> Result {
+        match value {
+            Some(value) => self.write_string(value),
+            None => self.write_nil(),
         }
-        self.write_string(value.unwrap_or_default());
         Ok(())
     }
 
-    fn write_nullable_bytes(&mut self, value: Option<Vec<u8>>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+    fn write_nullable_bytes(&mut self, buf: Option<&[u8]>) -> Result {
+        match buf {
+            Some(buf) => self.write_bytes(buf),
+            None => {
+                self.write_nil();
+                Ok(())
+            }
         }
-        let _ = self.write_bytes(&value.unwrap_or_default());
-        Ok(())
     }
 
-    fn write_nullable_bigint(&mut self, value: Option<BigInt>) -> Result {
-        if value.is_none() {
-            self.write_nil();
-            return Ok(());
+    fn write_nullable_bigint(&mut self, value: Option<&BigInt>) -> Result {
+        match value {
+            Some(value) => self.write_bigint(value),
+            None => self.write_nil(),
         }
-        self.write_bigint(value.unwrap_or_default());
         Ok(())
     }
 
-    fn write_nullable_array<T, F>(&mut self, _a: Option<Vec<T>>, _func: F) -> Result
+    fn write_nullable_array<T, F>(&mut self, a: Option<&[T]>, item_writer: F) -> Result
     where
-        F: FnMut(T),
+        F: FnMut(&mut Self, &T),
     {
-        todo!()
+        match a {
+            Some(a) => self.write_array(a, item_writer),
+            None => self.write_nil(),
+        }
+        Ok(())
     }
 
-    fn write_nullable_map<F, K, V, W>(
+    fn write_nullable_map<K, V, F, G>(
         &mut self,
-        _map: Option<HashMap<K, V>>,
-        _key_fn: F,
-        _value_fn: W,
+        map: Option<&HashMap<K, V>>,
+        key_writer: F,
+        value_writer: G,
     ) -> Result
     where
-        F: FnMut(&K),
-        W: FnMut(&V),
+        F: FnMut(&mut Self, &K),
+        G: FnMut(&mut Self, &V),
     {
-        todo!()
+        match map {
+            Some(map) => self.write_map(map, key_writer, value_writer),
+            None => self.write_nil(),
+        }
+        Ok(())
     }
 }
@@ -1 +1,110 @@
+//! Subinvoke API: lets a Web3API module invoke a method
+//! on another Web3API (or plugin) through the host
 
+use crate::{encode_with_context, Context, ReadDecoder, W3Read, W3Write};
+use std::io::{Error, Result};
+
+#[cfg(target_arch = "wasm32")]
+#[link(wasm_import_module = "w3")]
+extern "C" {
+    // Subinvoke API
+    pub fn __w3_subinvoke(
+        uri_ptr: *const u8,
+        uri_len: u32,
+        module_ptr: *const u8,
+        module_len: u32,
+        method_ptr: *const u8,
+        method_len: u32,
+        input_ptr: *const u8,
+        input_len: u32,
+    ) -> bool;
+
+    // Subinvoke Result
+    pub fn __w3_subinvoke_result_len() -> u32;
+    pub fn __w3_subinvoke_result(ptr: *mut u8);
+
+    // Subinvoke Error
+    pub fn __w3_subinvoke_error_len() -> u32;
+    pub fn __w3_subinvoke_error(ptr: *mut u8);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::mock::{
+    __w3_subinvoke, __w3_subinvoke_error_len, __w3_subinvoke_result_len, read_subinvoke_error,
+    read_subinvoke_result,
+};
+
+/// Reads the last subinvocation's result, into a buffer
+/// of `__w3_subinvoke_result_len` bytes
+#[cfg(target_arch = "wasm32")]
+fn read_subinvoke_result(buf: &mut [u8]) {
+    unsafe { __w3_subinvoke_result(buf.as_mut_ptr()) };
+}
+
+/// Reads the last subinvocation's error, into a buffer
+/// of `__w3_subinvoke_error_len` bytes
+#[cfg(target_arch = "wasm32")]
+fn read_subinvoke_error(buf: &mut [u8]) {
+    unsafe { __w3_subinvoke_error(buf.as_mut_ptr()) };
+}
+
+/// Subinvoke API Helper
+///
+/// Unlike the AssemblyScript helper, a failed subinvocation
+/// is returned as an `Err` holding the host's error message
+/// instead of aborting the module.
+pub fn w3_subinvoke(
+    uri: &str,
+    module: &str,
+    method: &str,
+    input: &[u8],
+) -> std::result::Result<Vec<u8>, String> {
+    let success = unsafe {
+        __w3_subinvoke(
+            uri.as_ptr(),
+            uri.len() as u32,
+            module.as_ptr(),
+            module.len() as u32,
+            method.as_ptr(),
+            method.len() as u32,
+            input.as_ptr(),
+            input.len() as u32,
+        )
+    };
+
+    if !success {
+        let error_len = unsafe { __w3_subinvoke_error_len() };
+        let mut message_buf = vec![0u8; error_len as usize];
+        read_subinvoke_error(&mut message_buf);
+        return Err(String::from_utf8_lossy(&message_buf).into_owned());
+    }
+
+    let result_len = unsafe { __w3_subinvoke_result_len() };
+    let mut result_buf = vec![0u8; result_len as usize];
+    read_subinvoke_result(&mut result_buf);
+    Ok(result_buf)
+}
+
+/// Typed Subinvoke API Helper
+///
+/// Serializes `input` (normally an `Input_<method>` type that
+/// writes its arguments as a msgpack map), subinvokes `method`
+/// and deserializes its result as `Out`.
+pub fn subinvoke<In, Out>(uri: &str, module: &str, method: &str, input: &In) -> Result<Out>
+where
+    In: W3Write,
+    Out: W3Read,
+{
+    let context =
+        Context::with_description(&format!("Serializing subinvoke args: {}/{}", uri, method));
+    let args = encode_with_context(input, context);
+
+    let result = w3_subinvoke(uri, module, method, &args).map_err(Error::other)?;
+
+    let context = Context::with_description(&format!(
+        "Deserializing subinvoke result: {}/{}",
+        uri, method
+    ));
+    let mut reader = ReadDecoder::with_context(&result, context);
+    Out::read(&mut reader)
+}
@@ -0,0 +1,34 @@
+use std::panic;
+use web3api_wasm_rs::MockHost;
+
+#[test]
+fn panics_are_reported_through_w3_abort() {
+    let host = MockHost::new();
+    let line = line!() + 1;
+    let result = panic::catch_unwind(|| panic!("bad {}", "input"));
+
+    assert!(result.is_err());
+    let abort = host.abort().unwrap();
+    assert_eq!(abort.msg, "bad input");
+    assert!(abort.file.ends_with("abort.rs"), "{}", abort.file);
+    assert_eq!(abort.line, line);
+    assert!(abort.column > 0);
+}
+
+#[test]
+fn non_string_payloads_are_reported() {
+    let host = MockHost::new();
+    let result = panic::catch_unwind(|| panic::panic_any(42));
+
+    assert!(result.is_err());
+    assert_eq!(host.abort().unwrap().msg, "panic occurred");
+}
+
+#[test]
+fn panics_without_a_host_are_not_reported() {
+    let result = panic::catch_unwind(|| panic!("no host"));
+    assert!(result.is_err());
+
+    let host = MockHost::new();
+    assert!(host.abort().is_none());
+}
@@ -0,0 +1,148 @@
+//! Runs the wasm-rs bindings generated for the `bind/sanity` test
+//! case. Only the query module is mounted here;
+//! `tests/fixtures/bindings` builds each of them.
+
+// The bindings are a library's public API, most of which is unused here
+#[rustfmt::skip]
+#[allow(clippy::module_inception, dead_code, unused_imports)]
+#[path = "../../../test-cases/cases/bind/sanity/output/wasm-rs/query/mod.rs"]
+mod query;
+
+mod common;
+
+use common::build_fixture;
+use query::imported::test_import_query::test_import_query::Input_anotherMethod;
+use query::query::wrapped::{object_method_wrapped, query_method_wrapped};
+use query::{
+    AnotherType, CustomEnum, CustomType, Input_objectMethod, Input_queryMethod, Query,
+    TestImport_Query,
+};
+use web3api_wasm_rs::{encode, BigInt, Bytes, MockHost, ReadDecoder, W3Read};
+
+// The module's methods, called by the generated wrappers
+struct Module;
+
+impl Query for Module {
+    fn query_method(input: Input_queryMethod) -> i32 {
+        input.str.len() as i32 + input.enum_array.len() as i32
+    }
+
+    fn object_method(input: Input_objectMethod) -> Option<AnotherType> {
+        input.opt_object
+    }
+}
+
+web3api_wasm_rs::w3_query_exports!(query::entry::w3_init::<Module>);
+
+fn decode<T: W3Read>(buffer: &[u8]) -> T {
+    let mut reader = ReadDecoder::new(buffer);
+    T::read(&mut reader).unwrap()
+}
+
+#[test]
+fn round_trips_object_types() {
+    let custom_type = CustomType {
+        str: "str".to_string(),
+        u64: u64::MAX,
+        i8: -8,
+        bigint: BigInt::from(-123456789i64) * BigInt::from(987654321i64),
+        bytes: Bytes::from(vec![1, 2, 3]),
+        u_opt_array_opt_array: vec![Some(vec![Some(1), None]), None],
+        crazy_array: Some(vec![None, Some(vec![vec![Some(vec![4])]])]),
+        opt_object: Some(Box::new(AnotherType {
+            prop: Some("prop".to_string()),
+            circular: Some(Box::new(CustomType::default())),
+        })),
+        en: CustomEnum::BYTES,
+        opt_enum_array: Some(vec![None, Some(CustomEnum::STRING)]),
+        ..CustomType::default()
+    };
+
+    let buffer = encode(&custom_type);
+    assert_eq!(decode::<CustomType>(&buffer), custom_type);
+}
+
+#[test]
+fn invokes_the_wrapped_methods() {
+    let input = Input_queryMethod {
+        str: "query".to_string(),
+        enum_array: vec![CustomEnum::STRING, CustomEnum::BYTES],
+        ..Input_queryMethod::default()
+    };
+    let result = query_method_wrapped::<Module>(&encode(&input)).unwrap();
+    assert_eq!(decode::<i32>(&result), 7);
+
+    let object = AnotherType {
+        prop: Some("prop".to_string()),
+        circular: None,
+    };
+    let input = Input_objectMethod {
+        opt_object: Some(object.clone()),
+        ..Input_objectMethod::default()
+    };
+    let result = object_method_wrapped::<Module>(&encode(&input)).unwrap();
+    assert_eq!(decode::<Option<AnotherType>>(&result), Some(object));
+}
+
+#[test]
+fn exports_the_registered_methods() {
+    let host = MockHost::new();
+    _w3_init();
+
+    let input = Input_queryMethod {
+        str: "str".to_string(),
+        ..Input_queryMethod::default()
+    };
+    let args = encode(&input);
+    host.set_invoke("queryMethod", &args);
+    assert!(_w3_invoke("queryMethod".len() as u32, args.len() as u32));
+    assert_eq!(host.invoke_result(), Some(encode(&3i32)));
+}
+
+#[test]
+fn reports_missing_arguments() {
+    let empty_map = [0x80];
+    let error = query_method_wrapped::<Module>(&empty_map).unwrap_err();
+    assert!(
+        error.contains(
+            "Missing required arguments: 'str: String', 'en: CustomEnum', \
+             'enumArray: [CustomEnum]'"
+        ),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn subinvokes_imported_queries() {
+    let host = MockHost::new();
+    host.on_subinvoke(
+        TestImport_Query::URI,
+        "query",
+        "anotherMethod",
+        Ok(encode(&42i64)),
+    );
+
+    let input = Input_anotherMethod {
+        arg: vec!["arg".to_string()],
+    };
+    assert_eq!(TestImport_Query::another_method(&input).unwrap(), 42);
+
+    let subinvocations = host.subinvocations();
+    assert_eq!(subinvocations.len(), 1);
+    assert_eq!(subinvocations[0].uri, "testimport.uri.eth");
+    assert_eq!(subinvocations[0].method, "anotherMethod");
+}
+
+#[test]
+fn builds_the_query_and_mutation_bindings() {
+    for features in &["query", "mutation"] {
+        let output = build_fixture("bindings", &[features]);
+        assert!(
+            output.status.success(),
+            "--features {}:\n{}",
+            features,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -0,0 +1,26 @@
+//! Helpers shared by the tests building the crates in `tests/fixtures`
+
+use std::env;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Builds `tests/fixtures/<name>` with the given features, in a
+/// target directory of its own
+pub fn build_fixture(name: &str, features: &[&str]) -> Output {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    Command::new(cargo)
+        .arg("build")
+        .arg("--quiet")
+        .arg("--features")
+        .arg(features.join(" "))
+        .current_dir(&fixture)
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join(name),
+        )
+        .output()
+        .expect("failed to run cargo")
+}
@@ -0,0 +1,87 @@
+use std::io::{Error, Result};
+use web3api_wasm_rs::{Context, Contextual, Read, ReadDecoder};
+
+#[test]
+fn pushes_and_pops_values() {
+    let mut context = Context::new();
+    assert_eq!(context.get_length(), 0);
+    assert!(context.is_empty());
+    context.push("property", "string", "");
+    context.push("property", "i32", "");
+    context.push("property", "bool", "");
+    assert_eq!(context.get_length(), 3);
+    assert!(!context.is_empty());
+    assert_eq!(context.pop().unwrap(), "property: bool");
+    context.pop().unwrap();
+    context.pop().unwrap();
+    assert_eq!(context.get_length(), 0);
+    assert!(context.is_empty());
+    assert!(context.pop().is_err());
+}
+
+#[test]
+fn prints_in_desired_format() {
+    let mut context = Context::with_description("Deserializing MyObject");
+    context.push("propertyOne", "unknown", "searching for property type");
+
+    assert_eq!(
+        format!("\n{}", context),
+        "
+Context: Deserializing MyObject
+  at propertyOne: unknown >> searching for property type"
+    );
+
+    assert_eq!(
+        context.print_with_context("\nInvalid length"),
+        "
+Invalid length
+  Context: Deserializing MyObject
+    at propertyOne: unknown >> searching for property type"
+    );
+
+    context.push("propertyOne", "i32", "type found, reading property");
+
+    assert_eq!(
+        context.print_with_context("\nInvalid length"),
+        "
+Invalid length
+  Context: Deserializing MyObject
+    at propertyOne: i32 >> type found, reading property
+      at propertyOne: unknown >> searching for property type"
+    );
+}
+
+#[test]
+fn scoped_push_pops_on_drop() {
+    let mut context = Context::new();
+    {
+        let mut scoped = context.push_scoped("propertyOne", "i32", "");
+        scoped.push_scoped("propertyTwo", "bool", "");
+        assert_eq!(scoped.get_length(), 1);
+    }
+    assert!(context.is_empty());
+}
+
+fn read_property(reader: &mut ReadDecoder) -> Result<i32> {
+    let mut reader = reader.push_scoped("property", "i32", "type found, reading property");
+    let value = reader.read_i32()?;
+    if value < 0 {
+        return Err(Error::other(
+            reader.get_context().print_with_context("must be positive"),
+        ));
+    }
+    Ok(value)
+}
+
+#[test]
+fn scoped_push_survives_early_returns() {
+    let context = Context::with_description("Deserializing query-type: queryMethod");
+    let mut reader = ReadDecoder::with_context(&[0xff, 0xc3], context);
+
+    let error = read_property(&mut reader).unwrap_err();
+    assert!(error.to_string().contains("at property: i32"));
+    assert!(reader.get_context().is_empty());
+
+    assert!(read_property(&mut reader).is_err());
+    assert!(reader.get_context().is_empty());
+}
@@ -0,0 +1,108 @@
+use web3api_wasm_rs::msgpack::data_view::DataView;
+use web3api_wasm_rs::{Context, Read, ReadDecoder};
+
+#[test]
+fn reads_and_writes_big_endian() {
+    let mut view = DataView::new(&[0u8; 15]).unwrap();
+    view.set_u8(0xab).unwrap();
+    view.set_i16(-2).unwrap();
+    view.set_u32(0x0102_0304).unwrap();
+    view.set_f64(1.5).unwrap();
+    assert_eq!(view.get_byte_offset(), 15);
+    assert_eq!(
+        view.get_buffer(),
+        [0xab, 0xff, 0xfe, 0x01, 0x02, 0x03, 0x04, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0]
+    );
+
+    let mut view = DataView::new(view.get_buffer()).unwrap();
+    assert_eq!(view.peek_u8().unwrap(), 0xab);
+    assert_eq!(view.get_u8().unwrap(), 0xab);
+    assert_eq!(view.get_i16().unwrap(), -2);
+    assert_eq!(view.get_bytes(4).unwrap(), [0x01, 0x02, 0x03, 0x04]);
+    assert_eq!(view.get_f64().unwrap(), 1.5);
+}
+
+#[test]
+fn out_of_bounds_reads_fail_without_advancing() {
+    let mut view = DataView::new(&[0x01, 0x02, 0x03]).unwrap();
+    view.discard(2).unwrap();
+
+    let error = view.get_u16().unwrap_err().to_string();
+    assert!(
+        error.starts_with("get_u16: Index out of range"),
+        "{}",
+        error
+    );
+    assert!(
+        error.contains("[length: 2, byte_offset: 2, byte_length: 3]"),
+        "{}",
+        error
+    );
+    assert_eq!(view.get_byte_offset(), 2);
+
+    assert!(view.get_bytes(2).is_err());
+    assert!(view.discard(2).is_err());
+    assert!(view.get_bytes(-1).is_err());
+    assert!(view.get_bytes(i32::MAX).is_err());
+    assert!(view.discard(i32::MAX).is_err());
+    assert_eq!(view.get_u8().unwrap(), 0x03);
+
+    assert!(view.peek_u8().is_err());
+    assert!(view.set_u8(0).is_err());
+    assert_eq!(view.get_bytes(0).unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn out_of_bounds_writes_leave_the_buffer_unchanged() {
+    let mut view = DataView::new(&[0u8; 3]).unwrap();
+    view.set_u8(1).unwrap();
+    assert!(view.set_u32(u32::MAX).is_err());
+    assert!(view.set_bytes(&[1, 2, 3]).is_err());
+    assert_eq!(view.get_byte_offset(), 1);
+    assert_eq!(view.into_buffer(), [1, 0, 0]);
+}
+
+#[test]
+fn starts_at_an_offset_within_the_buffer() {
+    let buf = [0x00, 0x00, 0x12, 0x34];
+    let mut view = DataView::at_offset(&buf, 2, Context::new()).unwrap();
+    assert_eq!(view.get_byte_offset(), 2);
+    assert_eq!(view.get_buffer(), buf);
+    assert_eq!(view.get_u16().unwrap(), 0x1234);
+
+    // Errors locate the lead byte within the whole buffer
+    let mut view = DataView::at_offset(&buf, 3, Context::new()).unwrap();
+    assert_eq!(view.get_lead_u8().unwrap(), 0x34);
+    let error = view.error("Bad value").to_string();
+    assert!(error.contains("lead_byte_offset: 3,"), "{}", error);
+    assert!(error.contains("bytes 0..4: 00 00 12 [34]"), "{}", error);
+
+    // An offset at the end is empty, past the end is invalid
+    let mut view = DataView::at_offset(&buf, 4, Context::new()).unwrap();
+    assert!(view.get_u8().is_err());
+    assert!(DataView::at_offset(&buf, 5, Context::new()).is_err());
+}
+
+#[test]
+fn huge_lengths_are_out_of_range() {
+    // STR32 and BIN32 headers claiming i32::MAX bytes, after other values
+    for format in [0xdb, 0xc6] {
+        let buf = [0x01, format, 0x7f, 0xff, 0xff, 0xff, 0x61];
+        let mut reader = ReadDecoder::new(&buf);
+        reader.read_u8().unwrap();
+        assert!(reader.skip().is_err());
+    }
+
+    let mut reader = ReadDecoder::new(&[0x01, 0xdb, 0x7f, 0xff, 0xff, 0xff, 0x61]);
+    reader.read_u8().unwrap();
+    let error = reader.read_string().unwrap_err().to_string();
+    assert!(
+        error.starts_with("get_bytes: Index out of range"),
+        "{}",
+        error
+    );
+
+    let mut reader = ReadDecoder::new(&[0x01, 0xc6, 0x7f, 0xff, 0xff, 0xff, 0x61]);
+    reader.read_u8().unwrap();
+    assert!(reader.read_bytes().is_err());
+}
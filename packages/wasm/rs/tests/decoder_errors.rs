@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use web3api_wasm_rs::msgpack::BLOCK_MAX_SIZE;
+use web3api_wasm_rs::{Context, Contextual, Read, ReadDecoder, W3Read, Write, WriteEncoder};
+
+#[test]
+fn type_errors_report_offset_path_and_bytes() {
+    // [1, 2, 3, 4, "x"]
+    let buf = [0x95, 0x01, 0x02, 0x03, 0x04, 0xa1, 0x78];
+    let context = Context::with_description("Deserializing query-type: queryMethod");
+    let mut reader = ReadDecoder::with_context(&buf, context);
+
+    let mut reader = reader.push_scoped("objectArray", "Vec<i32>", "type found, reading property");
+    assert_eq!(reader.read_array_length().unwrap(), 5);
+    for i in 0..4 {
+        let mut item = reader.push_scoped(&format!("array[{}]", i), "i32", "");
+        item.read_i32().unwrap();
+    }
+    let mut item = reader.push_scoped("array[4]", "i32", "");
+    let error = item.read_i32().unwrap_err().to_string();
+
+    assert!(error.starts_with("Property must be of type `int`. Found `string`."));
+    assert!(error.contains("[lead_byte_offset: 5, path: /objectArray/4]"));
+    assert!(error.contains("bytes 0..7: 95 01 02 03 04 [a1] 78"));
+    assert!(error.contains("at array[4]: i32"));
+}
+
+#[test]
+fn out_of_range_errors_point_past_the_end() {
+    // str8 header claiming 5 bytes, only 2 present
+    let buf = [0xd9, 0x05, 0x61, 0x62];
+    let mut reader = ReadDecoder::new(&buf);
+    let mut reader = reader.push_scoped("optStr", "Option<String>", "");
+    let error = reader.read_string().unwrap_err().to_string();
+
+    assert!(error.starts_with("get_bytes: Index out of range"));
+    assert!(error.contains("[lead_byte_offset: 0, path: /optStr]"));
+    assert!(error.contains("[d9] 05 61 62"));
+
+    let mut reader = ReadDecoder::new(&[]);
+    let error = reader.read_bool().unwrap_err().to_string();
+    assert!(error.contains("[lead_byte_offset: 0, path: /]"));
+    assert!(error.contains("[end of buffer]"));
+}
+
+#[test]
+fn overflow_errors_are_located() {
+    // u16 300 at offset 1, after a nil
+    let buf = [0xc0, 0xcd, 0x01, 0x2c];
+    let mut reader = ReadDecoder::new(&buf);
+    assert!(reader.is_next_nil());
+    let error = reader.read_u8().unwrap_err().to_string();
+
+    assert!(error.starts_with("unsigned integer overflow: value = 300; bits = 8"));
+    assert!(error.contains("lead_byte_offset: 1"));
+}
+
+#[test]
+fn nullable_readers_report_mistyped_values() {
+    // [nil, "x"]
+    let buf = [0x92, 0xc0, 0xa1, 0x78];
+    let mut reader = ReadDecoder::new(&buf);
+    assert_eq!(reader.read_array_length().unwrap(), 2);
+
+    assert_eq!(reader.read_nullable_u32().unwrap(), None);
+    let error = reader.read_nullable_u32().unwrap_err().to_string();
+    assert!(error.starts_with("Property must be of type `uint`. Found `string`."));
+    assert!(error.contains("[lead_byte_offset: 2, path: /]"));
+
+    let mut reader = ReadDecoder::new(&[0x07]);
+    let error = reader.read_nullable_string().unwrap_err().to_string();
+    assert!(error.starts_with("Property must be of type `string`. Found `int`."));
+}
+
+#[test]
+fn huge_lengths_do_not_preallocate() {
+    // array32 and map32 claiming u32::MAX entries, with none present
+    let error = Vec::<u64>::read(&mut ReadDecoder::new(&[0xdd, 0xff, 0xff, 0xff, 0xff]))
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Index out of range"), "{}", error);
+
+    let result =
+        HashMap::<String, u64>::read(&mut ReadDecoder::new(&[0xdf, 0xff, 0xff, 0xff, 0xff]));
+    assert!(result.is_err());
+}
+
+#[test]
+fn oversized_buffers_fail_on_first_use() {
+    // Zeroed, so the pages are never touched
+    let buf = vec![0u8; BLOCK_MAX_SIZE + 1];
+    let context = Context::with_description("Deserializing query-type: queryMethod");
+
+    let mut reader = ReadDecoder::with_context(&buf, context.clone());
+    let error = reader.read_u8().unwrap_err().to_string();
+    assert!(error.contains("Invalid length"), "{}", error);
+    assert!(
+        error.contains("Context: Deserializing query-type: queryMethod"),
+        "{}",
+        error
+    );
+
+    let mut encoder = WriteEncoder::new(&buf, context);
+    encoder.write_u8(1);
+    assert!(encoder.get_error().is_some());
+    assert!(encoder.get_buffer().is_empty());
+}
+
+#[test]
+fn offsets_past_the_end_fail_on_first_read() {
+    let mut reader = ReadDecoder::at_offset(&[0x01, 0x02], 3, Context::new());
+    let error = reader.read_u8().unwrap_err().to_string();
+    assert!(error.contains("Invalid length"), "{}", error);
+    assert!(error.contains("byte_offset: 3"), "{}", error);
+
+    let mut reader = ReadDecoder::at_offset(&[0x01, 0x02], 1, Context::new());
+    assert_eq!(reader.read_u8().unwrap(), 2);
+}
@@ -0,0 +1,114 @@
+use std::io::ErrorKind;
+use web3api_wasm_rs::{
+    encode, Context, Contextual, EnumError, InvalidEnum, ReadDecoder, W3Enum, W3Read,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, W3Enum)]
+enum CustomEnum {
+    #[w3(rename = "STRING")]
+    String,
+    #[w3(rename = "BYTES")]
+    Bytes,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, W3Enum)]
+#[w3(name = "TestImport_Enum")]
+#[allow(clippy::upper_case_acronyms)]
+enum TestImportEnum {
+    STRING,
+    BYTES,
+}
+
+fn decode<T: W3Read>(buffer: &[u8]) -> std::io::Result<T> {
+    T::read(&mut ReadDecoder::new(buffer))
+}
+
+#[test]
+fn maps_values_and_keys() {
+    assert_eq!(CustomEnum::NAME, "CustomEnum");
+    assert_eq!(TestImportEnum::NAME, "TestImport_Enum");
+    assert_eq!(CustomEnum::Bytes.value(), 1);
+    assert_eq!(CustomEnum::Bytes.key(), "BYTES");
+    assert_eq!(TestImportEnum::STRING.key(), "STRING");
+    assert_eq!(CustomEnum::from_key("STRING"), Some(CustomEnum::String));
+    assert_eq!(CustomEnum::from_key("String"), None);
+    assert_eq!(CustomEnum::sanitize(1).unwrap(), CustomEnum::Bytes);
+    assert_eq!(
+        CustomEnum::sanitize(2).unwrap_err().to_string(),
+        "Invalid value for enum 'CustomEnum': 2"
+    );
+    assert_eq!(
+        CustomEnum::get_value("FOO").unwrap_err().to_string(),
+        "Invalid key for enum 'CustomEnum': FOO"
+    );
+}
+
+#[test]
+fn reads_ints_or_names_and_writes_ints() {
+    assert_eq!(encode(&CustomEnum::Bytes), [0x01]);
+    assert_eq!(decode::<CustomEnum>(&[0x01]).unwrap(), CustomEnum::Bytes);
+    assert_eq!(
+        decode::<CustomEnum>(&encode(&"STRING")).unwrap(),
+        CustomEnum::String
+    );
+}
+
+#[test]
+fn unknown_values_fail_with_a_typed_error() {
+    let context = Context::with_description("Deserializing object-type CustomType");
+    let mut reader = ReadDecoder::with_context(&encode(&"FOO"), context);
+    let error = {
+        let mut reader = reader.push_scoped("en", "CustomEnum", "type found, reading property");
+        CustomEnum::read(&mut reader).unwrap_err()
+    };
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("path: /en"), "{}", error);
+
+    let enum_error = error
+        .get_ref()
+        .unwrap()
+        .downcast_ref::<EnumError>()
+        .unwrap();
+    assert_eq!(enum_error.enum_name, "CustomEnum");
+    assert_eq!(enum_error.invalid, InvalidEnum::Key("FOO".to_string()));
+
+    let error = decode::<CustomEnum>(&[0x05]).unwrap_err();
+    let enum_error = error
+        .get_ref()
+        .unwrap()
+        .downcast_ref::<EnumError>()
+        .unwrap();
+    assert_eq!(enum_error.invalid, InvalidEnum::Value(5));
+}
+
+#[test]
+fn nullable_and_array_enums_compose() {
+    // optEnum: CustomEnum
+    assert_eq!(encode(&None::<CustomEnum>), [0xc0]);
+    assert_eq!(decode::<Option<CustomEnum>>(&[0xc0]).unwrap(), None);
+    assert_eq!(
+        decode::<Option<CustomEnum>>(&encode(&"BYTES")).unwrap(),
+        Some(CustomEnum::Bytes)
+    );
+
+    // enumArray: [CustomEnum!]!, optEnumArray: [CustomEnum]
+    let enum_array = vec![CustomEnum::Bytes, CustomEnum::String];
+    assert_eq!(
+        decode::<Vec<CustomEnum>>(&encode(&enum_array)).unwrap(),
+        enum_array
+    );
+    let opt_enum_array = Some(vec![Some(CustomEnum::String), None]);
+    assert_eq!(
+        decode::<Option<Vec<Option<CustomEnum>>>>(&encode(&opt_enum_array)).unwrap(),
+        opt_enum_array
+    );
+
+    // Names and ints can be mixed in one array
+    let mixed = [0x92, 0xa5, b'B', b'Y', b'T', b'E', b'S', 0x00];
+    assert_eq!(
+        decode::<Vec<CustomEnum>>(&mixed).unwrap(),
+        vec![CustomEnum::Bytes, CustomEnum::String]
+    );
+    let error = decode::<Vec<CustomEnum>>(&[0x91, 0x07]).unwrap_err();
+    assert!(error.to_string().contains("path: /0"), "{}", error);
+}
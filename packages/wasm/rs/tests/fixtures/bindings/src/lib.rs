@@ -0,0 +1,37 @@
+//! The wasm-rs bindings generated for the `bind/sanity` test case,
+//! with `Module` implementing the methods of both modules.
+
+#[rustfmt::skip]
+#[allow(clippy::module_inception)]
+#[path = "../../../../../../test-cases/cases/bind/sanity/output/wasm-rs/query/mod.rs"]
+pub mod query;
+
+#[rustfmt::skip]
+#[allow(clippy::module_inception)]
+#[path = "../../../../../../test-cases/cases/bind/sanity/output/wasm-rs/mutation/mod.rs"]
+pub mod mutation;
+
+pub struct Module;
+
+impl query::Query for Module {
+    fn query_method(input: query::Input_queryMethod) -> i32 {
+        input.str.len() as i32
+    }
+
+    fn object_method(input: query::Input_objectMethod) -> Option<query::AnotherType> {
+        input.opt_object
+    }
+}
+
+impl mutation::Mutation for Module {
+    fn mutation_method(input: mutation::Input_mutationMethod) -> i32 {
+        input.enum_array.len() as i32
+    }
+
+    fn object_method(input: mutation::Input_objectMethod) -> Option<mutation::AnotherType> {
+        Some(input.object)
+    }
+}
+
+web3api_wasm_rs::w3_query_exports!(query::entry::w3_init::<Module>);
+web3api_wasm_rs::w3_mutation_exports!(mutation::entry::w3_init::<Module>);
@@ -0,0 +1,7 @@
+use web3api_wasm_rs::W3Enum;
+
+#[derive(Clone, Copy, Debug, PartialEq, W3Enum)]
+pub enum Status {
+    Empty,
+    Stored,
+}
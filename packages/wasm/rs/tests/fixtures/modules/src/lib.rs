@@ -0,0 +1,6 @@
+//! A Web3API with both a query and a mutation module,
+//! sharing the types defined in `common`
+
+pub mod common;
+pub mod mutation;
+pub mod query;
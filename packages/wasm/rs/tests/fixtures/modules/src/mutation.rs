@@ -0,0 +1,17 @@
+use crate::common::Status;
+use web3api_wasm_rs::w3_module;
+
+pub struct Mutation;
+
+#[w3_module]
+impl Mutation {
+    pub fn set_data(key: String, value: Option<String>) -> Result<Status, String> {
+        if key.is_empty() {
+            return Err("key must not be empty".to_string());
+        }
+        Ok(match value {
+            Some(_) => Status::Stored,
+            None => Status::Empty,
+        })
+    }
+}
@@ -0,0 +1,15 @@
+use crate::common::Status;
+use web3api_wasm_rs::w3_module;
+
+#[w3_module]
+pub mod query {
+    use super::*;
+
+    pub fn get_status(key: String) -> Status {
+        if key.is_empty() {
+            Status::Empty
+        } else {
+            Status::Stored
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use num_bigint::BigInt;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+use web3api_wasm_rs::{
+    encode, Bytes, Context, ReadDecoder, W3Read, W3Size, W3Write, Write, WriteEncoder, WriteSizer,
+};
+
+// crazyArray: [[[[UInt64!]]!]]
+type CrazyArray = Option<Vec<Option<Vec<Vec<Option<Vec<u64>>>>>>>;
+
+fn round_trip<T: W3Write + W3Read + PartialEq + Debug>(value: T) {
+    let buffer = encode(&value);
+    let mut reader = ReadDecoder::new(&buffer);
+    assert_eq!(T::read(&mut reader).unwrap(), value);
+}
+
+#[test]
+fn primitives_round_trip() {
+    round_trip(true);
+    round_trip(-100i8);
+    round_trip(i16::MIN);
+    round_trip(-70_000i32);
+    round_trip(i64::MAX);
+    round_trip(200u8);
+    round_trip(u16::MAX);
+    round_trip(70_000u32);
+    round_trip(u64::MAX);
+    round_trip(0.5f32);
+    round_trip(-1.25f64);
+    round_trip("a string longer than the thirty-one byte fixstr limit".to_string());
+    round_trip(BigInt::from_str("-123456789012345678901234567890").unwrap());
+    round_trip(Bytes(vec![1, 2, 3]));
+}
+
+#[test]
+fn nested_types_compose() {
+    // uArrayOptArrayArray: [[[UInt64!]!]]!
+    let u_array_opt_array_array: Vec<Option<Vec<Vec<u64>>>> = vec![
+        Some(vec![vec![1, 2], vec![]]),
+        None,
+        Some(vec![vec![u64::MAX]]),
+    ];
+    round_trip(u_array_opt_array_array);
+
+    let crazy_array: CrazyArray = Some(vec![None, Some(vec![vec![Some(vec![3]), None]])]);
+    round_trip(crazy_array);
+    round_trip(None as CrazyArray);
+
+    let mut map = HashMap::new();
+    map.insert("first".to_string(), vec![Some(1i32), None]);
+    map.insert("second".to_string(), vec![]);
+    round_trip(map);
+}
+
+#[test]
+fn bytes_and_byte_arrays_differ() {
+    assert_eq!(encode(&Bytes(vec![1, 2])), vec![0xc4, 0x02, 0x01, 0x02]);
+    assert_eq!(encode(&vec![1u8, 2u8]), vec![0x92, 0x01, 0x02]);
+}
+
+#[test]
+fn size_matches_encoding() {
+    let value: Vec<Option<String>> = vec![Some("x".repeat(40)), None, Some(String::new())];
+    assert_eq!(value.size() as usize, encode(&value).len());
+    assert_eq!("str".size(), 4);
+}
+
+#[test]
+fn nested_errors_carry_the_element_path() {
+    // [[1], ["x"]] read as Vec<Vec<u8>>
+    let buffer = [0x92, 0x91, 0x01, 0x91, 0xa1, 0x78];
+    let mut reader = ReadDecoder::new(&buffer);
+    let error = Vec::<Vec<u8>>::read(&mut reader).unwrap_err().to_string();
+    assert!(error.contains("path: /1/0"), "{}", error);
+    assert!(error.contains("lead_byte_offset: 4"), "{}", error);
+}
+
+#[test]
+fn borrowed_writers_match_owned_values() {
+    let name = "borrowed".to_string();
+    let big = BigInt::from(-42);
+    let mut map = HashMap::new();
+    map.insert("k".to_string(), 1u8);
+
+    let mut sizer = WriteSizer::new();
+    write_borrowed(&mut sizer, &name, &big, &map);
+    let buffer = vec![0u8; sizer.get_length() as usize];
+    let mut encoder = WriteEncoder::new(&buffer, Context::new());
+    write_borrowed(&mut encoder, &name, &big, &map);
+
+    let expected = [
+        encode(&name),
+        encode(&Some(big)),
+        encode(&None::<String>),
+        encode(&Bytes(vec![7])),
+        encode(&vec![1u8, 2u8]),
+        encode(&Some(map)),
+    ]
+    .concat();
+    assert_eq!(encoder.into_buffer(), expected);
+}
+
+fn write_borrowed<W: Write>(writer: &mut W, name: &str, big: &BigInt, map: &HashMap<String, u8>) {
+    writer.write_string(name);
+    writer.write_nullable_bigint(Some(big)).unwrap();
+    writer.write_nullable_string(None).unwrap();
+    writer.write_nullable_bytes(Some(&[7])).unwrap();
+    writer
+        .write_nullable_array(Some(&[1u8, 2u8]), |writer, item| writer.write_u8(*item))
+        .unwrap();
+    writer
+        .write_nullable_map(
+            Some(map),
+            |writer, key| writer.write_string(key),
+            |writer, value| writer.write_u8(*value),
+        )
+        .unwrap();
+}
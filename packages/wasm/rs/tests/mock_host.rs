@@ -0,0 +1,215 @@
+use std::io::Result;
+use web3api_wasm_rs::{
+    encode, subinvoke, w3_abort, w3_add_invoke, w3_invoke, w3_subinvoke, MockHost, Read,
+    ReadDecoder, W3Read, W3Write, Write,
+};
+
+struct InputEcho {
+    message: String,
+}
+
+impl W3Write for InputEcho {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_map_length(1);
+        writer.write_string("message");
+        writer.write_string(&self.message);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Echoed(String);
+
+impl W3Read for Echoed {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        Ok(Echoed(reader.read_string()?))
+    }
+}
+
+fn echo(args: &[u8]) -> std::result::Result<Vec<u8>, String> {
+    let mut reader = ReadDecoder::new(args);
+    if reader.read_map_length().map_err(|e| e.to_string())? != 1 {
+        return Err("Missing required argument: 'message: String'".to_string());
+    }
+    let _field = reader.read_string().map_err(|e| e.to_string())?;
+    let message = reader.read_string().map_err(|e| e.to_string())?;
+    Ok(encode(&InputEcho { message }))
+}
+
+#[test]
+fn invoke_captures_result() {
+    let host = MockHost::new();
+    w3_add_invoke("echo", echo);
+    let args = encode(&InputEcho {
+        message: "hello".to_string(),
+    });
+    host.set_invoke("echo", &args);
+
+    assert!(host.invoke());
+    assert_eq!(host.invoke_result(), Some(args));
+    assert_eq!(host.invoke_error(), None);
+}
+
+#[test]
+fn invoke_captures_handler_error() {
+    let host = MockHost::new();
+    w3_add_invoke("echo", echo);
+    host.set_invoke("echo", &[0x80]);
+
+    assert!(!host.invoke());
+    assert_eq!(host.invoke_result(), None);
+    assert_eq!(
+        host.invoke_error().unwrap(),
+        "Missing required argument: 'message: String'"
+    );
+}
+
+#[test]
+fn invoke_unknown_method() {
+    let host = MockHost::new();
+    host.set_invoke("unknownMethod", &[]);
+
+    assert!(!host.invoke());
+    assert_eq!(
+        host.invoke_error().unwrap(),
+        "Could not find invoke function \"unknownMethod\""
+    );
+}
+
+#[test]
+fn new_host_forgets_registered_invokes() {
+    let _previous = MockHost::new();
+    w3_add_invoke("echo", echo);
+
+    let host = MockHost::new();
+    host.set_invoke("echo", &[0x80]);
+    assert!(!host.invoke());
+    assert_eq!(
+        host.invoke_error().unwrap(),
+        "Could not find invoke function \"echo\""
+    );
+}
+
+#[test]
+fn subinvoke_returns_registered_response() {
+    let host = MockHost::new();
+    host.on_subinvoke(
+        "w3://ens/ethereum.web3api.eth",
+        "query",
+        "getNetwork",
+        Ok(vec![0xa3, b'f', b'o', b'o']),
+    );
+
+    let result = w3_subinvoke(
+        "w3://ens/ethereum.web3api.eth",
+        "query",
+        "getNetwork",
+        &[0x80],
+    );
+    assert_eq!(result, Ok(vec![0xa3, b'f', b'o', b'o']));
+
+    let subinvocations = host.subinvocations();
+    assert_eq!(subinvocations.len(), 1);
+    assert_eq!(subinvocations[0].method, "getNetwork");
+    assert_eq!(subinvocations[0].input, vec![0x80]);
+}
+
+#[test]
+fn subinvoke_returns_errors() {
+    let host = MockHost::new();
+    host.on_subinvoke(
+        "w3://ens/ipfs.web3api.eth",
+        "query",
+        "catFile",
+        Err("File not found".to_string()),
+    );
+
+    let result = w3_subinvoke("w3://ens/ipfs.web3api.eth", "query", "catFile", &[]);
+    assert_eq!(result, Err("File not found".to_string()));
+
+    let result = w3_subinvoke("w3://ens/ipfs.web3api.eth", "query", "addFile", &[]);
+    assert!(result.unwrap_err().contains("no response registered"));
+    assert_eq!(host.subinvocations().len(), 2);
+}
+
+#[test]
+fn typed_subinvoke() {
+    let host = MockHost::new();
+    host.on_subinvoke(
+        "testimport.uri.eth",
+        "query",
+        "importedMethod",
+        Ok(vec![0xa5, b'w', b'o', b'r', b'l', b'd']),
+    );
+
+    let input = InputEcho {
+        message: "hello".to_string(),
+    };
+    let result: Echoed =
+        subinvoke("testimport.uri.eth", "query", "importedMethod", &input).unwrap();
+    assert_eq!(result, Echoed("world".to_string()));
+    assert_eq!(host.subinvocations()[0].input, encode(&input));
+}
+
+#[test]
+fn abort_is_captured() {
+    let host = MockHost::new();
+    let result = std::panic::catch_unwind(|| w3_abort("bad input", "src/query.rs", 4, 2));
+
+    assert!(result.is_err());
+    let abort = host.abort().unwrap();
+    assert_eq!(
+        abort.to_string(),
+        "__w3_abort: bad input\nFile: src/query.rs\nLocation: [4,2]"
+    );
+}
+
+#[test]
+fn panicking_handler_aborts() {
+    let host = MockHost::new();
+    w3_add_invoke(
+        "panics",
+        |_args: &[u8]| -> std::result::Result<Vec<u8>, String> { panic!("oops") },
+    );
+    host.set_invoke("panics", &[]);
+
+    let result = std::panic::catch_unwind(|| host.invoke());
+    assert!(result.is_err());
+    let abort = host.abort().unwrap();
+    assert_eq!(abort.msg, "oops");
+    assert!(abort.file.ends_with("mock_host.rs"));
+}
+
+#[test]
+fn mismatched_invoke_sizes_panic() {
+    let host = MockHost::new();
+    host.set_invoke("foo", &[0x01]);
+
+    let result = std::panic::catch_unwind(|| w3_invoke(0, 0));
+    assert!(result.is_err());
+    let abort = host.abort().unwrap();
+    assert_eq!(
+        abort.msg,
+        "MockHost: _w3_invoke method_size is 0 bytes, but the host has 3 to write"
+    );
+}
+
+#[test]
+fn handlers_may_hold_thread_local_state() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let host = MockHost::new();
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let recorded = calls.clone();
+    w3_add_invoke(
+        "record",
+        move |args: &[u8]| -> std::result::Result<Vec<u8>, String> {
+            recorded.borrow_mut().push(args.to_vec());
+            Ok(vec![])
+        },
+    );
+
+    host.set_invoke("record", &[0xc0]);
+    assert!(host.invoke());
+    assert_eq!(*calls.borrow(), vec![vec![0xc0]]);
+}
@@ -0,0 +1,42 @@
+//! Builds `tests/fixtures/modules`, a crate defining both a query
+//! and a mutation module, once for each of them and once natively
+//! without either
+
+mod common;
+
+use common::build_fixture;
+
+#[test]
+fn builds_each_module_on_its_own() {
+    // Both modules define `_w3_init`, so these only
+    // link if the other module's exports are left out
+    for features in &["query", "mutation"] {
+        let output = build_fixture("modules", &[features]);
+        assert!(
+            output.status.success(),
+            "--features {}:\n{}",
+            features,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+#[test]
+fn builds_both_modules_natively_without_a_feature() {
+    // Neither module's exports are `#[no_mangle]` then, so
+    // that the crate builds for `cargo test` and the `MockHost`
+    let output = build_fixture("modules", &[]);
+    assert!(
+        output.status.success(),
+        "no features:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn rejects_both_modules_at_once() {
+    let output = build_fixture("modules", &["query", "mutation"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be enabled together"), "{}", stderr);
+}
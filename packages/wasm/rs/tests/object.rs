@@ -0,0 +1,189 @@
+use std::io::Result;
+use web3api_wasm_rs::{
+    encode, read_object, Context, Contextual, Read, ReadDecoder, UnknownFieldPolicy, Value,
+    W3Object, W3Read, Write, WriteEncoder, WriteSizer,
+};
+
+/// Version 2 of the type, as written by a newer module
+fn write_v2<W: Write>(writer: &mut W) {
+    writer.write_map_length(3);
+    writer.write_string("str");
+    writer.write_string("value");
+    writer.write_string("added");
+    writer.write_array_length(2);
+    writer.write_u8(1);
+    writer.write_nil();
+    writer.write_string("optU");
+    writer.write_u32(7);
+}
+
+fn v2() -> Vec<u8> {
+    let mut sizer = WriteSizer::new();
+    write_v2(&mut sizer);
+    let buffer = vec![0u8; sizer.get_length() as usize];
+    let mut encoder = WriteEncoder::new(&buffer, Context::new());
+    write_v2(&mut encoder);
+    encoder.into_buffer()
+}
+
+fn read_v1_field(
+    field: &str,
+    reader: &mut ReadDecoder,
+    str: &mut String,
+    opt_u: &mut Option<u32>,
+) -> Result<bool> {
+    match field {
+        "str" => {
+            let mut reader = reader.push_scoped(field, "String", "type found, reading property");
+            *str = reader.read_string()?;
+        }
+        "optU" => {
+            let mut reader =
+                reader.push_scoped(field, "Option<u32>", "type found, reading property");
+            *opt_u = Option::read(&mut reader)?;
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Ignoring {
+    str: String,
+    opt_u: Option<u32>,
+    defaulted: bool,
+}
+
+impl W3Object for Ignoring {
+    const TYPE_NAME: &'static str = "Ignoring";
+
+    fn read_field(&mut self, field: &str, reader: &mut ReadDecoder) -> Result<bool> {
+        read_v1_field(field, reader, &mut self.str, &mut self.opt_u)
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Collecting {
+    str: String,
+    opt_u: Option<u32>,
+    extra: Vec<(String, Value)>,
+}
+
+impl W3Object for Collecting {
+    const TYPE_NAME: &'static str = "Collecting";
+    const UNKNOWN_FIELDS: UnknownFieldPolicy = UnknownFieldPolicy::Collect;
+
+    fn read_field(&mut self, field: &str, reader: &mut ReadDecoder) -> Result<bool> {
+        read_v1_field(field, reader, &mut self.str, &mut self.opt_u)
+    }
+
+    fn extra_mut(&mut self) -> Option<&mut Vec<(String, Value)>> {
+        Some(&mut self.extra)
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Rejecting {
+    str: String,
+    opt_u: Option<u32>,
+}
+
+impl W3Object for Rejecting {
+    const TYPE_NAME: &'static str = "Rejecting";
+    const UNKNOWN_FIELDS: UnknownFieldPolicy = UnknownFieldPolicy::Reject;
+
+    fn read_field(&mut self, field: &str, reader: &mut ReadDecoder) -> Result<bool> {
+        read_v1_field(field, reader, &mut self.str, &mut self.opt_u)
+    }
+}
+
+#[test]
+fn ignores_unknown_fields_and_keeps_defaults() {
+    let buffer = v2();
+    let mut reader = ReadDecoder::new(&buffer);
+    let object: Ignoring = read_object(&mut reader).unwrap();
+    assert_eq!(
+        object,
+        Ignoring {
+            str: "value".to_string(),
+            opt_u: Some(7),
+            defaulted: false,
+        }
+    );
+    assert!(reader.get_context().is_empty());
+}
+
+#[test]
+fn collects_unknown_fields() {
+    let buffer = v2();
+    let object: Collecting = read_object(&mut ReadDecoder::new(&buffer)).unwrap();
+    assert_eq!(object.opt_u, Some(7));
+    assert_eq!(
+        object.extra,
+        vec![(
+            "added".to_string(),
+            Value::Array(vec![Value::UInt(1), Value::Nil])
+        )]
+    );
+}
+
+#[test]
+fn rejects_unknown_fields() {
+    let buffer = v2();
+    let error = read_object::<Rejecting>(&mut ReadDecoder::new(&buffer))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.starts_with("Unknown property: 'added' for type Rejecting"),
+        "{}",
+        error
+    );
+    assert!(error.contains("path: /added"), "{}", error);
+}
+
+#[test]
+fn missing_fields_come_from_default() {
+    // {"str": "only"}
+    let buffer = [0x81, 0xa3, b's', b't', b'r', 0xa4, b'o', b'n', b'l', b'y'];
+    let object: Rejecting = read_object(&mut ReadDecoder::new(&buffer)).unwrap();
+    assert_eq!(
+        object,
+        Rejecting {
+            str: "only".to_string(),
+            opt_u: None,
+        }
+    );
+}
+
+#[test]
+fn values_round_trip() {
+    let value = Value::Map(vec![
+        (Value::String("k".to_string()), Value::Int(-40)),
+        (Value::UInt(u64::MAX), Value::Bytes(vec![])),
+        (Value::Bool(true), Value::F64(0.5)),
+    ]);
+    let buffer = encode(&value);
+    assert_eq!(Value::read(&mut ReadDecoder::new(&buffer)).unwrap(), value);
+}
+
+#[test]
+fn deeply_nested_values_are_errors() {
+    // 2,000,000 nested single-item arrays
+    let buffer = vec![0x91; 2_000_000];
+    let error = Value::read(&mut ReadDecoder::new(&buffer))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("Value nested more than 512 levels deep"),
+        "{}",
+        error
+    );
+
+    // The limit itself is fine: 512 arrays around a nil
+    let buffer = [vec![0x91; 512], vec![0xc0]].concat();
+    assert!(Value::read(&mut ReadDecoder::new(&buffer)).is_ok());
+
+    // Maps do not preallocate for missing entries
+    let buffer = [0xdf, 0xff, 0xff, 0xff, 0xff];
+    assert!(Value::read(&mut ReadDecoder::new(&buffer)).is_err());
+}
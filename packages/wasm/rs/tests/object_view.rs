@@ -0,0 +1,128 @@
+use web3api_wasm_rs::{Context, ObjectView, W3Write, Write, WriteEncoder, WriteSizer};
+
+fn write_custom_type<W: Write>(writer: &mut W) {
+    writer.write_map_length(4);
+    writer.write_string("str");
+    writer.write_string("a string");
+    writer.write_string("u32");
+    writer.write_u32(70_000);
+    writer.write_string("optStr");
+    writer.write_nil();
+    writer.write_string("uArrayArray");
+    vec![vec![1u8, 2], vec![3]].write(writer);
+}
+
+fn custom_type() -> Vec<u8> {
+    let mut sizer = WriteSizer::new();
+    write_custom_type(&mut sizer);
+    let buffer = vec![0u8; sizer.get_length() as usize];
+    let mut encoder = WriteEncoder::new(&buffer, Context::new());
+    write_custom_type(&mut encoder);
+    encoder.into_buffer()
+}
+
+#[test]
+fn looks_up_fields_by_name() {
+    let buffer = custom_type();
+    let view = ObjectView::new(&buffer).unwrap();
+
+    assert_eq!(view.len(), 4);
+    assert!(view.contains("u32"));
+    assert!(!view.contains("missing"));
+    assert_eq!(view.get::<u32>("u32", "UInt32").unwrap(), 70_000);
+    assert_eq!(
+        view.get::<Vec<Vec<u8>>>("uArrayArray", "[[UInt8]]")
+            .unwrap(),
+        vec![vec![1, 2], vec![3]]
+    );
+    assert_eq!(
+        view.get::<Option<String>>("optStr", "String").unwrap(),
+        None
+    );
+    assert_eq!(
+        view.get_optional::<String>("missing", "String").unwrap(),
+        None
+    );
+    assert_eq!(view.get_raw("str").unwrap(), b"\xa8a string");
+}
+
+#[test]
+fn iterates_in_encoded_order() {
+    let buffer = custom_type();
+    let view = ObjectView::new(&buffer).unwrap();
+    let fields: Vec<&str> = view.iter().map(|(field, _)| field).collect();
+    assert_eq!(fields, ["str", "u32", "optStr", "uArrayArray"]);
+
+    let (_, raw) = view.iter().nth(2).unwrap();
+    assert_eq!(raw, [0xc0]);
+}
+
+#[test]
+fn reports_missing_and_mistyped_fields() {
+    let buffer = custom_type();
+    let context = Context::with_description("Deserializing object-type CustomType");
+    let view = ObjectView::with_context(&buffer, context).unwrap();
+
+    let error = view
+        .get::<bool>("missing", "Boolean")
+        .unwrap_err()
+        .to_string();
+    assert_eq!(error, "Missing required property: 'missing: Boolean'");
+
+    let error = view.get::<bool>("str", "Boolean").unwrap_err().to_string();
+    assert!(
+        error.contains("Property must be of type `bool`. Found `string`."),
+        "{}",
+        error
+    );
+    assert!(error.contains("path: /str"), "{}", error);
+    assert!(error.contains("at str: Boolean"), "{}", error);
+    assert!(
+        error.contains("Context: Deserializing object-type CustomType"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn only_keys_must_be_valid() {
+    // {"a": <truncated array>} fails, since values are still skipped over
+    assert!(ObjectView::new(&[0x81, 0xa1, 0x61, 0x92, 0x01]).is_err());
+
+    // {"ok": 1, "bad": "\xff"} still indexes; the invalid string only
+    // fails when it is read
+    let buffer = [
+        0x82, 0xa2, b'o', b'k', 0x01, 0xa3, b'b', b'a', b'd', 0xa1, 0xff,
+    ];
+    let view = ObjectView::new(&buffer).unwrap();
+    assert_eq!(view.get::<u8>("ok", "UInt8").unwrap(), 1);
+    assert!(view.get::<String>("bad", "String").is_err());
+}
+
+#[test]
+fn locates_errors_within_the_object() {
+    let buffer = custom_type();
+    let view = ObjectView::new(&buffer).unwrap();
+
+    // "str" is encoded after the map header and its key, at byte 5
+    let error = view.get::<bool>("str", "Boolean").unwrap_err().to_string();
+    assert!(error.contains("lead_byte_offset: 5,"), "{}", error);
+    assert!(error.contains("bytes 0..14: 84 a3"), "{}", error);
+    assert!(error.contains("[a8]"), "{}", error);
+}
+
+#[test]
+fn rejects_more_fields_than_fit() {
+    // map32 claiming 0x0fffffff fields, with none present
+    let error = ObjectView::new(&[0xdf, 0x0f, 0xff, 0xff, 0xff])
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("Map of 268435455 fields is longer than the 0 bytes left"),
+        "{}",
+        error
+    );
+
+    // {"a": 1} claiming two fields
+    assert!(ObjectView::new(&[0x82, 0xa1, b'a', 0x01]).is_err());
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use web3api_wasm_rs::{encode, Context, Read, ReadDecoder, Write, WriteEncoder, WriteSizer};
+
+#[test]
+fn reads_nested_values_whole() {
+    let mut inner = HashMap::new();
+    inner.insert(
+        "list".to_string(),
+        vec![vec![Some(1u64)], vec![None, Some(u64::MAX)]],
+    );
+    let nested = encode(&inner);
+    let trailing = encode(&"after".to_string());
+    let buffer = [nested.clone(), trailing].concat();
+
+    let mut reader = ReadDecoder::new(&buffer);
+    assert_eq!(reader.read_raw_value().unwrap(), nested.as_slice());
+    assert_eq!(reader.read_string().unwrap(), "after");
+}
+
+#[test]
+fn reads_ext_values() {
+    // fixext4, ext8 with 2 data bytes, then true
+    let buffer = [0xd6, 0x01, 0, 0, 0, 0, 0xc7, 0x02, 0x05, 0xaa, 0xbb, 0xc3];
+    let mut reader = ReadDecoder::new(&buffer);
+    assert_eq!(reader.read_raw_value().unwrap(), &buffer[..6]);
+    assert_eq!(reader.read_raw_value().unwrap(), &buffer[6..11]);
+    assert!(reader.read_bool().unwrap());
+}
+
+#[test]
+fn truncated_values_are_errors() {
+    // array of 3 with only 2 items
+    let mut reader = ReadDecoder::new(&[0x93, 0x01, 0x02]);
+    let error = reader.read_raw_value().unwrap_err().to_string();
+    assert!(error.contains("Index out of range"), "{}", error);
+}
+
+#[test]
+fn huge_ext_lengths_are_errors() {
+    // ext32 claiming i32::MAX and u32::MAX data bytes
+    for length in [[0x7f, 0xff, 0xff, 0xff], [0xff, 0xff, 0xff, 0xff]] {
+        let buffer = [&[0xc9][..], &length, &[0x01, 0x00]].concat();
+        let mut reader = ReadDecoder::new(&buffer);
+        let error = reader.read_raw_value().unwrap_err().to_string();
+        assert!(error.contains("Index out of range"), "{}", error);
+    }
+}
+
+fn write_forwarded<W: Write>(writer: &mut W, raw: &[u8]) {
+    writer.write_map_length(1);
+    writer.write_string("forwarded");
+    writer.write_raw(raw);
+}
+
+#[test]
+fn forwards_raw_values_without_decoding() {
+    let payload = encode(&vec!["a".to_string(), "b".to_string()]);
+    let mut reader = ReadDecoder::new(&payload);
+    let raw = reader.read_raw_value().unwrap();
+
+    let mut sizer = WriteSizer::new();
+    write_forwarded(&mut sizer, raw);
+    let buffer = vec![0u8; sizer.get_length() as usize];
+    let mut encoder = WriteEncoder::new(&buffer, Context::new());
+    write_forwarded(&mut encoder, raw);
+
+    let mut expected = HashMap::new();
+    expected.insert(
+        "forwarded".to_string(),
+        vec!["a".to_string(), "b".to_string()],
+    );
+    assert_eq!(encoder.into_buffer(), encode(&expected));
+}
@@ -0,0 +1,121 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+use web3api_wasm_rs::{w3_subinvoke, MockHost};
+
+const ETHEREUM: &str = "w3://ens/ethereum.web3api.eth";
+const IPFS: &str = "w3://ens/ipfs.web3api.eth";
+
+fn fixture_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "web3api-wasm-rs-{}-{}.msgpack",
+        name,
+        std::process::id()
+    ))
+}
+
+fn session() -> Vec<Result<Vec<u8>, String>> {
+    vec![
+        w3_subinvoke(ETHEREUM, "query", "getNetwork", &[0x80]),
+        w3_subinvoke(
+            IPFS,
+            "query",
+            "catFile",
+            &[0x81, 0xa3, b'c', b'i', b'd', 0xa1, b'Q'],
+        ),
+        w3_subinvoke(ETHEREUM, "mutation", "sendTransaction", &[0x80]),
+    ]
+}
+
+fn record(path: &PathBuf) -> Vec<Result<Vec<u8>, String>> {
+    let host = MockHost::new();
+    host.record(|uri, _module, method, input| match method {
+        "sendTransaction" => Err(format!("{}: insufficient funds", uri)),
+        _ => Ok([method.as_bytes(), input].concat()),
+    });
+    let results = session();
+    host.save_recording(path).unwrap();
+    results
+}
+
+#[test]
+fn replays_recorded_session() {
+    let path = fixture_path("session");
+    let recorded = record(&path);
+    assert_eq!(
+        recorded[2],
+        Err(format!("{}: insufficient funds", ETHEREUM))
+    );
+
+    let host = MockHost::new();
+    host.replay(&path).unwrap();
+    assert_eq!(session(), recorded);
+    host.assert_replayed();
+    assert_eq!(host.subinvocations().len(), 3);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn replay_rejects_unexpected_subinvocation() {
+    let path = fixture_path("unexpected");
+    record(&path);
+
+    let host = MockHost::new();
+    host.replay(&path).unwrap();
+    let result = catch_unwind(|| w3_subinvoke(IPFS, "query", "catFile", &[]));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("unexpected subinvocation of w3://ens/ipfs.web3api.eth/query/catFile"));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn replay_rejects_exhausted_fixture() {
+    let path = fixture_path("exhausted");
+    record(&path);
+
+    let host = MockHost::new();
+    host.replay(&path).unwrap();
+    session();
+    let result = catch_unwind(|| w3_subinvoke(ETHEREUM, "query", "getNetwork", &[0x80]));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("the replay fixture is exhausted"));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn assert_replayed_reports_leftovers() {
+    let path = fixture_path("leftovers");
+    record(&path);
+
+    let host = MockHost::new();
+    host.replay(&path).unwrap();
+    w3_subinvoke(ETHEREUM, "query", "getNetwork", &[0x80]).unwrap();
+    let result = catch_unwind(AssertUnwindSafe(|| host.assert_replayed()));
+    assert!(result.is_err());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn rejects_subinvocations_from_the_responder() {
+    let host = MockHost::new();
+    host.record(|_uri, module, method, input| {
+        // A responder must call the live host, not the mock
+        let nested = w3_subinvoke(IPFS, module, "catFile", input);
+        assert_eq!(
+            nested.unwrap_err(),
+            "MockHost: subinvocation of w3://ens/ipfs.web3api.eth/query/catFile \
+             from the recording responder"
+        );
+        Ok(method.as_bytes().to_vec())
+    });
+
+    let result = w3_subinvoke(ETHEREUM, "query", "getNetwork", &[0x80]);
+    assert_eq!(result.unwrap(), b"getNetwork");
+
+    let subinvocations = host.subinvocations();
+    assert_eq!(subinvocations.len(), 1);
+    assert_eq!(subinvocations[0].method, "getNetwork");
+}
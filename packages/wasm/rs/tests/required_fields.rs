@@ -0,0 +1,113 @@
+use std::io::Result;
+use web3api_wasm_rs::{read_object, Context, Read, ReadDecoder, RequiredFields, W3Object, W3Read};
+
+#[derive(Debug, Default)]
+struct InputQueryMethod {
+    str: String,
+    opt_str: Option<String>,
+    u_array_array: Vec<Vec<u32>>,
+}
+
+/// A hand-written args deserializer, as for `queryMethod`
+fn deserialize_query_method_args(args: &[u8]) -> Result<InputQueryMethod> {
+    let context = Context::with_description("Deserializing query-type: queryMethod");
+    let mut reader = ReadDecoder::with_context(args, context);
+    let mut required = RequiredFields::arguments(&[
+        ("str", "String!"),
+        ("u", "UInt!"),
+        ("uArrayArray", "[[UInt]]!"),
+    ]);
+    let mut input = InputQueryMethod::default();
+
+    let num_of_fields = reader.read_map_length()?;
+    for _ in 0..num_of_fields {
+        let field = reader.read_string()?;
+        match field.as_str() {
+            "str" => input.str = reader.read_string()?,
+            "optStr" => input.opt_str = Option::read(&mut reader)?,
+            "uArrayArray" => input.u_array_array = Vec::read(&mut reader)?,
+            _ => reader.skip()?,
+        }
+        required.mark(&field);
+    }
+    required.check(reader.get_context())?;
+    Ok(input)
+}
+
+#[test]
+fn reports_every_missing_argument() {
+    // {"optStr": "x"}
+    let args = [0x81, 0xa6, b'o', b'p', b't', b'S', b't', b'r', 0xa1, b'x'];
+    let error = deserialize_query_method_args(&args)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        error,
+        "Missing required arguments: 'str: String!', 'u: UInt!', 'uArrayArray: [[UInt]]!'
+  Context: Deserializing query-type: queryMethod
+    context stack is empty"
+    );
+}
+
+#[test]
+fn uses_the_singular_for_one_missing_field() {
+    let mut required = RequiredFields::properties(&[("prop", "String!"), ("other", "Int!")]);
+    required.mark("other");
+    required.mark("notRequired");
+    assert_eq!(required.missing(), vec!["prop: String!"]);
+    let error = required.check(&Context::new()).unwrap_err().to_string();
+    assert!(error.starts_with("Missing required property: 'prop: String!'\n"));
+
+    required.mark("prop");
+    assert!(required.check(&Context::new()).is_ok());
+}
+
+#[derive(Debug, Default)]
+struct AnotherType {
+    prop: Option<String>,
+    circular: Option<Box<AnotherType>>,
+}
+
+impl W3Object for AnotherType {
+    const TYPE_NAME: &'static str = "AnotherType";
+    const REQUIRED_FIELDS: &'static [(&'static str, &'static str)] = &[("prop", "String!")];
+
+    fn read_field(&mut self, field: &str, reader: &mut ReadDecoder) -> Result<bool> {
+        match field {
+            "prop" => self.prop = Some(reader.read_string()?),
+            "circular" => self.circular = Option::read(reader)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+impl W3Read for AnotherType {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        read_object(reader)
+    }
+}
+
+#[test]
+fn objects_check_their_required_fields() {
+    // {"prop": "a", "circular": {}}
+    let buffer = [
+        0x82, 0xa4, b'p', b'r', b'o', b'p', 0xa1, b'a', 0xa8, b'c', b'i', b'r', b'c', b'u', b'l',
+        b'a', b'r', 0x80,
+    ];
+    let error = AnotherType::read(&mut ReadDecoder::new(&buffer))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.starts_with("Missing required property: 'prop: String!'"),
+        "{}",
+        error
+    );
+    assert!(error.contains("at circular: unknown"), "{}", error);
+
+    // {"prop": "a"}
+    let buffer = [0x81, 0xa4, b'p', b'r', b'o', b'p', 0xa1, b'a'];
+    let object = AnotherType::read(&mut ReadDecoder::new(&buffer)).unwrap();
+    assert_eq!(object.prop.as_deref(), Some("a"));
+    assert!(object.circular.is_none());
+}
@@ -0,0 +1,108 @@
+use web3api_wasm_rs::{encode, w3_import, MockHost, ReadDecoder, W3Enum, W3Read};
+
+#[derive(Clone, Copy, Debug, PartialEq, W3Enum)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[w3(name = "TestImport_Enum")]
+pub enum TestImport_Enum {
+    STRING,
+    BYTES,
+}
+
+// Both imports declare `anotherMethod`, and each gets its own
+// `Input_anotherMethod` in `test_import_query`/`imported`
+
+#[w3_import(uri = "testimport.uri.eth")]
+pub trait TestImport_Query {
+    fn imported_method(
+        str: String,
+        opt_u: Option<u32>,
+        en: TestImport_Enum,
+        opt_enum_array: Option<Vec<Option<TestImport_Enum>>>,
+    ) -> Option<String>;
+
+    fn another_method(arg: Vec<String>) -> i64;
+}
+
+#[w3_import(uri = "testimport.uri.eth", module = "mutation")]
+pub trait Imported {
+    #[w3(rename = "anotherMethod")]
+    fn another(arg: Vec<String>) -> i64;
+}
+
+use test_import_query::{Input_anotherMethod, Input_importedMethod};
+
+#[test]
+fn subinvokes_the_imported_method() {
+    let host = MockHost::new();
+    host.on_subinvoke(
+        TestImport_Query::URI,
+        "query",
+        "importedMethod",
+        Ok(encode(&Some("result".to_string()))),
+    );
+
+    let input = Input_importedMethod {
+        str: "str".to_string(),
+        opt_u: None,
+        en: TestImport_Enum::BYTES,
+        opt_enum_array: Some(vec![Some(TestImport_Enum::STRING), None]),
+    };
+    let result = TestImport_Query::imported_method(&input).unwrap();
+    assert_eq!(result, Some("result".to_string()));
+
+    let subinvocations = host.subinvocations();
+    assert_eq!(subinvocations.len(), 1);
+    assert_eq!(subinvocations[0].uri, "testimport.uri.eth");
+    let mut reader = ReadDecoder::new(&subinvocations[0].input);
+    let args = Input_importedMethod::read(&mut reader).unwrap();
+    assert_eq!(args.str, "str");
+    assert_eq!(args.en, TestImport_Enum::BYTES);
+    assert_eq!(args.opt_enum_array, input.opt_enum_array);
+}
+
+#[test]
+fn reports_subinvoke_and_result_errors() {
+    let host = MockHost::new();
+    let input = Input_anotherMethod {
+        arg: vec!["a".to_string()],
+    };
+
+    host.on_subinvoke(
+        "testimport.uri.eth",
+        "query",
+        "anotherMethod",
+        Err("method failed".to_string()),
+    );
+    let error = TestImport_Query::another_method(&input).unwrap_err();
+    assert_eq!(error.to_string(), "method failed");
+
+    host.on_subinvoke(
+        "testimport.uri.eth",
+        "query",
+        "anotherMethod",
+        Ok(encode(&"not an int")),
+    );
+    let error = TestImport_Query::another_method(&input).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Deserializing subinvoke result: testimport.uri.eth/anotherMethod"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn imports_mutation_modules() {
+    let host = MockHost::new();
+    host.on_subinvoke(
+        Imported::URI,
+        "mutation",
+        "anotherMethod",
+        Ok(encode(&7i64)),
+    );
+
+    let input = imported::Input_anotherMethod { arg: vec![] };
+    assert_eq!(Imported::another(&input).unwrap(), 7);
+    assert_eq!(host.subinvocations()[0].module, "mutation");
+}
@@ -0,0 +1,162 @@
+use std::io::Result;
+use web3api_wasm_rs::{
+    encode, read_object, w3_module, MockHost, ReadDecoder, W3Enum, W3Object, W3Read, W3Write, Write,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, W3Enum)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum CustomEnum {
+    STRING,
+    BYTES,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AnotherType {
+    pub prop: Option<String>,
+}
+
+impl W3Object for AnotherType {
+    const TYPE_NAME: &'static str = "AnotherType";
+
+    fn read_field(&mut self, field: &str, reader: &mut ReadDecoder) -> Result<bool> {
+        match field {
+            "prop" => self.prop = Option::read(reader)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+impl W3Read for AnotherType {
+    fn read(reader: &mut ReadDecoder) -> Result<Self> {
+        read_object(reader)
+    }
+}
+
+impl W3Write for AnotherType {
+    fn write<W: Write>(&self, writer: &mut W) {
+        writer.write_map_length(1);
+        writer.write_string("prop");
+        self.prop.write(writer);
+    }
+}
+
+#[w3_module]
+mod query {
+    use super::{AnotherType, CustomEnum};
+    use web3api_wasm_rs::W3Enum;
+
+    pub fn query_method(
+        str: String,
+        opt_str: Option<String>,
+        en: CustomEnum,
+        opt_enum: Option<CustomEnum>,
+        enum_array: Vec<CustomEnum>,
+        opt_enum_array: Option<Vec<Option<CustomEnum>>>,
+    ) -> i32 {
+        let opt_enums = opt_enum_array.map_or(0, |array| array.len());
+        (str.len() + opt_str.map_or(0, |s| s.len()) + enum_array.len() + opt_enums) as i32
+            + en.value()
+            + opt_enum.map_or(0, |en| en.value())
+    }
+
+    pub fn object_method(
+        object: AnotherType,
+        opt_object: Option<AnotherType>,
+        #[w3(rename = "objectArray")] objects: Vec<AnotherType>,
+        opt_object_array: Option<Vec<Option<AnotherType>>>,
+    ) -> std::result::Result<Option<AnotherType>, String> {
+        if objects.is_empty() && opt_object_array.is_none() {
+            return Err("no objects".to_string());
+        }
+        Ok(opt_object.or(Some(object)))
+    }
+
+    /// Not exported
+    #[allow(dead_code)]
+    fn helper() {}
+}
+
+#[test]
+fn wraps_and_registers_methods() {
+    let host = MockHost::new();
+    query::_w3_init();
+
+    let args = encode(&query::Input_queryMethod {
+        str: "abc".to_string(),
+        opt_str: None,
+        en: CustomEnum::BYTES,
+        opt_enum: Some(CustomEnum::BYTES),
+        enum_array: vec![CustomEnum::STRING],
+        opt_enum_array: Some(vec![None, None]),
+    });
+    host.set_invoke("queryMethod", &args);
+    assert!(query::_w3_invoke(
+        "queryMethod".len() as u32,
+        args.len() as u32
+    ));
+    assert_eq!(host.invoke_result(), Some(encode(&(3 + 1 + 2 + 1 + 1))));
+}
+
+#[test]
+fn reports_argument_errors_with_context() {
+    let host = MockHost::new();
+    query::_w3_init();
+
+    // {"optStr": 1}
+    let args = [0x81, 0xa6, b'o', b'p', b't', b'S', b't', b'r', 0x01];
+    host.set_invoke("queryMethod", &args);
+    assert!(!host.invoke());
+    let error = host.invoke_error().unwrap();
+    assert!(
+        error.starts_with("Property must be of type `string`. Found `int`."),
+        "{}",
+        error
+    );
+    assert!(error.contains("path: /optStr"), "{}", error);
+    assert!(
+        error.contains("Context: Deserializing query-type: queryMethod"),
+        "{}",
+        error
+    );
+    assert!(error.contains("at optStr: String"), "{}", error);
+
+    host.set_invoke("queryMethod", &[0x80]);
+    assert!(!host.invoke());
+    assert!(host.invoke_error().unwrap().starts_with(
+        "Missing required arguments: 'str: String', 'en: CustomEnum', 'enumArray: [CustomEnum]'"
+    ));
+}
+
+#[test]
+fn forwards_results_and_errors() {
+    let host = MockHost::new();
+    query::_w3_init();
+    let object = AnotherType {
+        prop: Some("prop".to_string()),
+    };
+
+    let args = encode(&query::Input_objectMethod {
+        object: object.clone(),
+        opt_object: None,
+        objects: vec![object.clone()],
+        opt_object_array: None,
+    });
+    host.set_invoke("objectMethod", &args);
+    assert!(host.invoke());
+    assert_eq!(host.invoke_result(), Some(encode(&Some(object.clone()))));
+    assert_eq!(
+        query::object_method_wrapped(&args),
+        Ok(encode(&Some(object.clone())))
+    );
+
+    let args = encode(&query::Input_objectMethod {
+        object,
+        opt_object: None,
+        objects: vec![],
+        opt_object_array: None,
+    });
+    host.set_invoke("objectMethod", &args);
+    assert!(!host.invoke());
+    assert_eq!(host.invoke_error().unwrap(), "no objects");
+}
@@ -0,0 +1,89 @@
+use web3api_wasm_rs::{encode, w3_module, MockHost, ReadDecoder, W3Read};
+
+pub struct Mutation;
+
+#[w3_module]
+impl Mutation {
+    pub fn mutation_method(str: String, #[w3(type = "UInt")] opt_u: Option<u32>) -> String {
+        format!("{}{}", str, opt_u.unwrap_or_default())
+    }
+
+    #[w3(rename = "setData")]
+    pub fn set(value: u64) -> Result<bool, String> {
+        if value == 0 {
+            return Err("value must be positive".to_string());
+        }
+        Ok(true)
+    }
+
+    /// Takes `self`, so not exported
+    pub fn describe(&self) -> &'static str {
+        "mutation"
+    }
+}
+
+#[test]
+fn exports_associated_functions() {
+    let host = MockHost::new();
+    _w3_init();
+    assert_eq!(Mutation.describe(), "mutation");
+
+    let args = encode(&Input_mutationMethod {
+        str: "u".to_string(),
+        opt_u: Some(8),
+    });
+    host.set_invoke("mutationMethod", &args);
+    assert!(host.invoke());
+    let result = host.invoke_result().unwrap();
+    assert_eq!(String::read(&mut ReadDecoder::new(&result)).unwrap(), "u8");
+
+    host.set_invoke("setData", &encode(&Input_setData { value: 0 }));
+    assert!(!host.invoke());
+    assert_eq!(host.invoke_error().unwrap(), "value must be positive");
+}
+
+#[test]
+fn names_the_mutation_type_in_context() {
+    let error = set_wrapped(&[0x81, 0xa5, b'v', b'a', b'l', b'u', b'e', 0xc3]).unwrap_err();
+    assert!(
+        error.contains("Context: Deserializing mutation-type: setData"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn names_schema_types_in_context() {
+    let error = set_wrapped(&[0x81, 0xa5, b'v', b'a', b'l', b'u', b'e', 0xc3]).unwrap_err();
+    assert!(error.contains("at value: UInt64"), "{}", error);
+
+    // {"str": "s", "optU": "u"}
+    let args = [
+        0x82, 0xa3, b's', b't', b'r', 0xa1, b's', 0xa4, b'o', b'p', b't', b'U', 0xa1, b'u',
+    ];
+    let error = mutation_method_wrapped(&args).unwrap_err();
+    assert!(error.contains("at optU: UInt"), "{}", error);
+    assert!(!error.contains("UInt32"), "{}", error);
+}
+
+mod queries {
+    pub struct Query;
+
+    // The type is read from the path's last segment
+    #[web3api_wasm_rs::w3_module]
+    impl self::Query {
+        pub fn get(key: String) -> String {
+            key
+        }
+    }
+}
+
+#[test]
+fn infers_the_module_type_from_paths() {
+    let error = queries::get_wrapped(&[0x80]).unwrap_err();
+    assert!(
+        error.contains("Context: Deserializing query-type: get"),
+        "{}",
+        error
+    );
+}